@@ -9,7 +9,7 @@ use dsc_lib::progress::ProgressFormat;
 use dsc_lib::types::{FullyQualifiedTypeName, ResourceVersionReq, TypeNameFilter};
 use rust_i18n::t;
 use schemars::JsonSchema;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Eq, ValueEnum)]
 pub enum OutputFormat {
@@ -18,6 +18,14 @@ pub enum OutputFormat {
     Yaml,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, ValueEnum)]
+pub enum InputFormat {
+    /// A single instance as JSON or YAML.
+    Json,
+    /// One instance per line as JSON Lines.
+    JsonLines,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, ValueEnum)]
 pub enum GetOutputFormat {
     Json,
@@ -27,6 +35,15 @@ pub enum GetOutputFormat {
     Yaml,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, ValueEnum)]
+pub enum ExportOutputFormat {
+    Json,
+    /// Write each exported instance as a line of JSON as soon as the resource exports it.
+    JsonLines,
+    PrettyJson,
+    Yaml,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, ValueEnum)]
 pub enum ListOutputFormat {
     Json,
@@ -35,6 +52,22 @@ pub enum ListOutputFormat {
     TableNoTruncate,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, ValueEnum)]
+pub enum VerifyOutputFormat {
+    Json,
+    PrettyJson,
+    Yaml,
+    Junit,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ResourceLanguage {
+    Rust,
+    Bash,
+    Powershell,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, ValueEnum, Deserialize)]
 pub enum TraceFormat {
     Default,
@@ -74,7 +107,11 @@ pub enum SubCommand {
         #[clap(short, long, help = t!("args.parameters").to_string())]
         parameters: Option<String>,
         #[clap(short = 'f', long, help = t!("args.parametersFile").to_string())]
-        parameters_file: Option<String>,
+        parameters_file: Vec<String>,
+        #[clap(long, value_name = "PREFIX", help = t!("args.parametersFromEnv").to_string())]
+        parameters_from_env: Option<String>,
+        #[clap(long, help = t!("args.showParameters").to_string())]
+        show_parameters: bool,
         #[clap(short = 'r', long, help = t!("args.systemRoot").to_string())]
         system_root: Option<String>,
         // Used to inform when DSC is used as a group resource to modify it's output
@@ -167,6 +204,15 @@ pub enum ConfigSubCommand {
         #[clap(short = 'o', long, help = t!("args.outputFormat").to_string())]
         output_format: Option<OutputFormat>,
     },
+    #[clap(name = "lock", about = t!("args.lockAbout").to_string())]
+    Lock {
+        #[clap(short = 'i', long, help = t!("args.input").to_string(), conflicts_with = "file")]
+        input: Option<String>,
+        #[clap(short = 'f', long, help = t!("args.file").to_string(), conflicts_with = "input")]
+        file: Option<String>,
+        #[clap(short = 'o', long, help = t!("args.outputFormat").to_string())]
+        output_format: Option<OutputFormat>,
+    },
     #[clap(name = "resolve", about = t!("args.resolveAbout").to_string(), hide = true)]
     Resolve {
         #[clap(short = 'i', long, help = t!("args.input").to_string(), conflicts_with = "file")]
@@ -219,6 +265,8 @@ pub enum ResourceSubCommand {
         description: Option<String>,
         #[clap(short, long, help = t!("args.tags").to_string())]
         tags: Option<Vec<String>>,
+        #[clap(long, help = t!("args.showUntrusted").to_string())]
+        show_untrusted: bool,
         #[clap(short = 'o', long, help = t!("args.outputFormat").to_string())]
         output_format: Option<ListOutputFormat>,
     },
@@ -234,6 +282,8 @@ pub enum ResourceSubCommand {
         input: Option<String>,
         #[clap(short = 'f', long, help = t!("args.file").to_string(), conflicts_with = "input")]
         file: Option<String>,
+        #[clap(long, default_value = "json", help = t!("args.inputFormat").to_string(), conflicts_with = "all")]
+        input_format: InputFormat,
         #[clap(short = 'o', long, help = t!("args.outputFormat").to_string())]
         output_format: Option<GetOutputFormat>,
     },
@@ -247,6 +297,8 @@ pub enum ResourceSubCommand {
         input: Option<String>,
         #[clap(short = 'f', long, help = t!("args.file").to_string(), conflicts_with = "input")]
         file: Option<String>,
+        #[clap(long, default_value = "json", help = t!("args.inputFormat").to_string())]
+        input_format: InputFormat,
         #[clap(short = 'o', long, help = t!("args.outputFormat").to_string())]
         output_format: Option<OutputFormat>,
         #[clap(short = 'w', long, visible_aliases = ["dry-run", "noop"], help = t!("args.whatIf").to_string())]
@@ -262,6 +314,8 @@ pub enum ResourceSubCommand {
         input: Option<String>,
         #[clap(short = 'f', long, help = t!("args.file").to_string(), conflicts_with = "input")]
         file: Option<String>,
+        #[clap(long, default_value = "json", help = t!("args.inputFormat").to_string())]
+        input_format: InputFormat,
         #[clap(short = 'o', long, help = t!("args.outputFormat").to_string())]
         output_format: Option<OutputFormat>,
     },
@@ -299,6 +353,74 @@ pub enum ResourceSubCommand {
         input: Option<String>,
         #[clap(short = 'f', long, help = t!("args.file").to_string(), conflicts_with = "input")]
         file: Option<String>,
+        #[clap(short = 'o', long, help = t!("args.outputFormat").to_string())]
+        output_format: Option<ExportOutputFormat>,
+    },
+    #[clap(name = "verify", about = t!("args.resourceVerifyAbout").to_string(), arg_required_else_help = true)]
+    Verify {
+        #[clap(short, long, help = t!("args.resource").to_string())]
+        resource: FullyQualifiedTypeName,
+        #[clap(short = 'v', long, alias = "version", help = t!("args.version").to_string())]
+        required_version: Option<ResourceVersionReq>,
+        #[clap(short, long, help = t!("args.resourceVerifyInput").to_string(), conflicts_with = "file")]
+        input: Option<String>,
+        #[clap(short = 'f', long, help = t!("args.file").to_string(), conflicts_with = "input")]
+        file: Option<String>,
+        #[clap(short = 'o', long, help = t!("args.outputFormat").to_string())]
+        output_format: Option<VerifyOutputFormat>,
+    },
+    #[clap(name = "new", about = t!("args.resourceNewAbout").to_string(), arg_required_else_help = true)]
+    New {
+        #[clap(short = 't', long = "type", help = t!("args.resourceNewType").to_string())]
+        resource_type: FullyQualifiedTypeName,
+        #[clap(short, long, help = t!("args.resourceNewLanguage").to_string(), value_enum)]
+        language: ResourceLanguage,
+        #[clap(short, long, help = t!("args.resourceNewPath").to_string())]
+        path: Option<String>,
+        #[clap(short = 'o', long, help = t!("args.outputFormat").to_string())]
+        output_format: Option<OutputFormat>,
+    },
+    #[clap(name = "validate-manifest", about = t!("args.resourceValidateManifestAbout").to_string(), arg_required_else_help = true)]
+    ValidateManifest {
+        #[clap(required = true, help = t!("args.resourceValidateManifestPaths").to_string())]
+        paths: Vec<String>,
+        #[clap(short = 'o', long, help = t!("args.outputFormat").to_string())]
+        output_format: Option<OutputFormat>,
+    },
+    #[clap(name = "install", about = t!("args.resourceInstallAbout").to_string(), arg_required_else_help = true)]
+    Install {
+        #[clap(help = t!("args.resourceInstallBundle").to_string())]
+        bundle: String,
+        #[clap(long, help = t!("args.resourceInstallForce").to_string())]
+        force: bool,
+        #[clap(short = 'o', long, help = t!("args.outputFormat").to_string())]
+        output_format: Option<OutputFormat>,
+    },
+    #[clap(name = "uninstall", about = t!("args.resourceUninstallAbout").to_string(), arg_required_else_help = true)]
+    Uninstall {
+        #[clap(short, long, help = t!("args.resource").to_string())]
+        resource: FullyQualifiedTypeName,
+        #[clap(short = 'v', long, alias = "version", help = t!("args.resourceUninstallVersion").to_string())]
+        required_version: Option<ResourceVersionReq>,
+        #[clap(short = 'o', long, help = t!("args.outputFormat").to_string())]
+        output_format: Option<OutputFormat>,
+    },
+    #[clap(name = "cache", about = t!("args.resourceCacheAbout").to_string())]
+    Cache {
+        #[clap(subcommand)]
+        subcommand: ResourceCacheSubCommand,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq, Subcommand)]
+pub enum ResourceCacheSubCommand {
+    #[clap(name = "clear", about = t!("args.resourceCacheClear").to_string())]
+    Clear {
+        #[clap(short = 'o', long, help = t!("args.outputFormat").to_string())]
+        output_format: Option<OutputFormat>,
+    },
+    #[clap(name = "rebuild", about = t!("args.resourceCacheRebuild").to_string())]
+    Rebuild {
         #[clap(short = 'o', long, help = t!("args.outputFormat").to_string())]
         output_format: Option<OutputFormat>,
     },
@@ -7,11 +7,11 @@ use clap_complete::generate;
 use dsc_lib::{progress::ProgressFormat, util::DSC_IGNORE_SETTINGS_FILE};
 use server::start_server;
 use rust_i18n::{i18n, t};
-use std::{env::set_var, io, process::exit};
+use std::{env::set_var, io};
 use sysinfo::{Process, RefreshKind, System, get_current_pid, ProcessRefreshKind};
 use tracing::{error, info, warn, debug};
 
-use crate::util::{EXIT_INVALID_INPUT, get_input};
+use crate::util::{EXIT_INVALID_INPUT, exit, get_input};
 
 #[cfg(debug_assertions)]
 use crossterm::event;
@@ -21,6 +21,8 @@ use std::env;
 pub mod args;
 pub mod resolve;
 pub mod resource_command;
+pub mod resource_verify;
+pub mod scaffold;
 pub mod server;
 pub mod subcommand;
 pub mod tablewriter;
@@ -86,13 +88,43 @@ fn dsc_main() {
             let mut cmd = Args::command();
             generate(shell, &mut cmd, "dsc", &mut io::stdout());
         },
-        SubCommand::Config { subcommand, parameters, parameters_file, system_root, as_group, as_assert, as_include } => {
-            let params = get_input(None, parameters_file.as_ref());
-            let file_params = if params.is_empty() {
-                None
-            } else {
-                Some(params)
+        SubCommand::Config { subcommand, parameters, parameters_file, parameters_from_env, show_parameters, system_root, as_group, as_assert, as_include } => {
+            let mut parameter_options = util::ParameterOptions {
+                env_prefix: parameters_from_env,
+                show_parameters,
+                ..Default::default()
             };
+            let mut file_params: Option<String> = None;
+            for path in &parameters_file {
+                let params = get_input(None, Some(path));
+                if params.is_empty() {
+                    continue;
+                }
+
+                if let Err(err) = util::add_parameter_sources(&params, &util::ParameterSource::File(path.clone()), &mut parameter_options.sources) {
+                    error!("{}: {err}", t!("main.failedMergingParameters"));
+                    exit(EXIT_INVALID_INPUT);
+                }
+                file_params = match file_params {
+                    Some(base) => {
+                        info!("{}", t!("main.layeringParametersFile", path = path));
+                        match util::deep_merge_parameters(&base, &params) {
+                            Ok(merged) => Some(merged),
+                            Err(err) => {
+                                error!("{}: {err}", t!("main.failedMergingParameters"));
+                                exit(EXIT_INVALID_INPUT);
+                            }
+                        }
+                    },
+                    None => Some(params),
+                };
+            }
+
+            if let Some(inline_content) = &parameters
+                && let Err(err) = util::add_parameter_sources(inline_content, &util::ParameterSource::Inline, &mut parameter_options.sources) {
+                error!("{}: {err}", t!("main.failedMergingParameters"));
+                exit(EXIT_INVALID_INPUT);
+            }
 
             let merged_parameters = match (file_params, parameters) {
                 (Some(file_content), Some(inline_content)) => {
@@ -110,7 +142,7 @@ fn dsc_main() {
                 (None, None) => None,
             };
 
-            subcommand::config(&subcommand, &merged_parameters, system_root.as_ref(), &as_group, &as_assert, &as_include, progress_format);
+            subcommand::config(&subcommand, &merged_parameters, &parameter_options, system_root.as_ref(), &as_group, &as_assert, &as_include, progress_format);
         },
         SubCommand::Extension { subcommand } => {
             subcommand::extension(&subcommand, progress_format);
@@ -161,7 +193,9 @@ fn ctrlc_handler() {
     };
 
     terminate_subprocesses(&sys, current_process);
-    exit(util::EXIT_CTRL_C);
+    // resource servers are subprocesses that were just terminated, and the main thread may hold
+    // their locks, so exit without the graceful shutdown
+    std::process::exit(util::EXIT_CTRL_C);
 }
 
 fn terminate_subprocesses(sys: &System, process: &Process) {
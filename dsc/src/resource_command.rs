@@ -1,16 +1,17 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
-use crate::args::{GetOutputFormat, OutputFormat};
-use crate::util::{EXIT_DSC_ERROR, EXIT_INVALID_ARGS, EXIT_JSON_ERROR, EXIT_DSC_RESOURCE_NOT_FOUND, write_object};
-use dsc_lib::configure::config_doc::{Configuration, ExecutionKind};
-use dsc_lib::configure::add_resource_export_results_to_configuration;
+use crate::args::{ExportOutputFormat, GetOutputFormat, OutputFormat};
+use crate::util::{EXIT_DSC_ERROR, EXIT_INVALID_ARGS, EXIT_JSON_ERROR, EXIT_DSC_RESOURCE_NOT_FOUND, exit, write_object};
+use dsc_lib::configure::config_doc::{Configuration, ExecutionKind, Resource};
+use dsc_lib::configure::{add_resource_export_results_to_configuration, stream_resource_export_results};
 use dsc_lib::discovery::discovery_trait::DiscoveryFilter;
 use dsc_lib::dscresources::{resource_manifest::Kind, invoke_result::{DeleteResultKind, GetResult, ResourceGetResponse, ResourceSetResponse, SetResult}};
 use dsc_lib::dscresources::dscresource::{Capability, get_diff};
 use dsc_lib::dscerror::DscError;
 use dsc_lib::types::{FullyQualifiedTypeName, ResourceVersionReq};
 use rust_i18n::t;
+use serde::Serialize;
 use serde_json::Value;
 use tracing::{debug, error, info};
 
@@ -18,7 +19,6 @@ use dsc_lib::{
     dscresources::dscresource::{Invoke, DscResource},
     DscManager
 };
-use std::process::exit;
 
 pub fn get(dsc: &mut DscManager, resource_type: &FullyQualifiedTypeName, version: Option<&ResourceVersionReq>, input: &str, format: Option<&GetOutputFormat>) {
     let Some(resource) = get_resource(dsc, resource_type, version) else {
@@ -258,6 +258,93 @@ pub fn test(dsc: &mut DscManager, resource_type: &FullyQualifiedTypeName, versio
     }
 }
 
+pub fn get_batch(dsc: &mut DscManager, resource_type: &FullyQualifiedTypeName, version: Option<&ResourceVersionReq>, inputs: &[String], format: Option<&GetOutputFormat>) {
+    let resource = get_batch_resource(dsc, resource_type, version);
+    match resource.get_batch(inputs) {
+        Ok(results) => match format {
+            Some(GetOutputFormat::PassThrough) => write_results(&get_actual_states(&results), Some(&OutputFormat::Json)),
+            Some(GetOutputFormat::JsonArray) => write_results(&[get_actual_states(&results)], Some(&OutputFormat::Json)),
+            Some(GetOutputFormat::Json) => write_results(&results, Some(&OutputFormat::Json)),
+            Some(GetOutputFormat::PrettyJson) => write_results(&results, Some(&OutputFormat::PrettyJson)),
+            Some(GetOutputFormat::Yaml) => write_results(&results, Some(&OutputFormat::Yaml)),
+            None => write_results(&results, None),
+        },
+        Err(err) => {
+            error!("{err}");
+            exit(EXIT_DSC_ERROR);
+        }
+    }
+}
+
+pub fn set_batch(dsc: &mut DscManager, resource_type: &FullyQualifiedTypeName, version: Option<&ResourceVersionReq>, inputs: &[String], format: Option<&OutputFormat>, what_if: bool) {
+    let resource = get_batch_resource(dsc, resource_type, version);
+    let execution_kind = if what_if { ExecutionKind::WhatIf } else { ExecutionKind::Actual };
+    match resource.set_batch(inputs, true, &execution_kind) {
+        Ok(results) => write_results(&results, format),
+        Err(err) => {
+            error!("{err}");
+            exit(EXIT_DSC_ERROR);
+        }
+    }
+}
+
+pub fn test_batch(dsc: &mut DscManager, resource_type: &FullyQualifiedTypeName, version: Option<&ResourceVersionReq>, inputs: &[String], format: Option<&OutputFormat>) {
+    let resource = get_batch_resource(dsc, resource_type, version);
+    match resource.test_batch(inputs) {
+        Ok(results) => write_results(&results, format),
+        Err(err) => {
+            error!("{err}");
+            exit(EXIT_DSC_ERROR);
+        }
+    }
+}
+
+fn get_batch_resource<'a>(dsc: &'a mut DscManager, resource_type: &FullyQualifiedTypeName, version: Option<&ResourceVersionReq>) -> &'a DscResource {
+    let Some(resource) = get_resource(dsc, resource_type, version) else {
+        error!("{}", DscError::ResourceNotFound(resource_type.to_string(), version.map_or(String::new(), |v| v.to_string())));
+        exit(EXIT_DSC_RESOURCE_NOT_FOUND);
+    };
+
+    debug!("{} {} {:?}", resource.type_name, t!("resource_command.implementedAs"), resource.implemented_as);
+    if resource.kind == Kind::Adapter {
+        error!("{}: {}", t!("resource_command.invalidOperationOnAdapter"), resource.type_name);
+        exit(EXIT_DSC_ERROR);
+    }
+    resource
+}
+
+/// Get the actual state of each result, keeping group results as they are.
+fn get_actual_states(results: &[GetResult]) -> Vec<Value> {
+    results.iter().map(|result| match result {
+        GetResult::Resource(response) => Ok(response.actual_state.clone()),
+        GetResult::Group(_) => serde_json::to_value(result),
+    }).collect::<Result<Vec<Value>, _>>().unwrap_or_else(|err| {
+        error!("{}", t!("resource_command.jsonError", err = err));
+        exit(EXIT_JSON_ERROR);
+    })
+}
+
+/// Write each result in the output format, or as a single line of JSON so the output matches the
+/// json-lines input when no format is specified.
+fn write_results<T: Serialize>(results: &[T], format: Option<&OutputFormat>) {
+    let mut include_separator = false;
+    for result in results {
+        let json = match serde_json::to_string(result) {
+            Ok(json) => json,
+            Err(err) => {
+                error!("JSON: {err}");
+                exit(EXIT_JSON_ERROR);
+            }
+        };
+        if format.is_none() {
+            println!("{json}");
+        } else {
+            write_object(&json, format, include_separator);
+            include_separator = true;
+        }
+    }
+}
+
 pub fn delete(dsc: &mut DscManager, resource_type: &FullyQualifiedTypeName, version: Option<&ResourceVersionReq>, input: &str, format: Option<&OutputFormat>, what_if: bool) {
     let Some(resource) = get_resource(dsc, resource_type, version) else {
         error!("{}", DscError::ResourceNotFound(resource_type.to_string(), version.map_or(String::new(), |v| v.to_string())));
@@ -327,7 +414,7 @@ pub fn schema(dsc: &mut DscManager, resource_type: &FullyQualifiedTypeName, vers
     }
 }
 
-pub fn export(dsc: &mut DscManager, resource_type: &FullyQualifiedTypeName, version: Option<&ResourceVersionReq>, input: &str, format: Option<&OutputFormat>) {
+pub fn export(dsc: &mut DscManager, resource_type: &FullyQualifiedTypeName, version: Option<&ResourceVersionReq>, input: &str, format: Option<&ExportOutputFormat>) {
     let Some(dsc_resource) = get_resource(dsc, resource_type, version) else {
         error!("{}", DscError::ResourceNotFound(resource_type.to_string(), version.map_or(String::new(), |v| v.to_string())).to_string());
         exit(EXIT_DSC_RESOURCE_NOT_FOUND);
@@ -338,6 +425,19 @@ pub fn export(dsc: &mut DscManager, resource_type: &FullyQualifiedTypeName, vers
         exit(EXIT_DSC_ERROR);
     }
 
+    if format == Some(&ExportOutputFormat::JsonLines) {
+        // each instance is written as soon as it's exported, so the instances aren't collected
+        let mut write_line = |resource: Resource| -> Result<(), DscError> {
+            println!("{}", serde_json::to_string(&resource)?);
+            Ok(())
+        };
+        if let Err(err) = stream_resource_export_results(dsc_resource, input, &mut write_line) {
+            error!("{err}");
+            exit(EXIT_DSC_ERROR);
+        }
+        return;
+    }
+
     let mut conf = Configuration::new();
     if let Err(err) = add_resource_export_results_to_configuration(dsc_resource, &mut conf, input) {
         error!("{err}");
@@ -351,6 +451,12 @@ pub fn export(dsc: &mut DscManager, resource_type: &FullyQualifiedTypeName, vers
             exit(EXIT_JSON_ERROR);
         }
     };
+    let format = match format {
+        Some(ExportOutputFormat::PrettyJson) => Some(&OutputFormat::PrettyJson),
+        Some(ExportOutputFormat::Yaml) => Some(&OutputFormat::Yaml),
+        None => None,
+        _ => Some(&OutputFormat::Json),
+    };
     write_object(&json, format, false);
 }
 
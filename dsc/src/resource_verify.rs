@@ -0,0 +1,340 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use crate::args::{OutputFormat, VerifyOutputFormat};
+use crate::resource_command::get_resource;
+use crate::util::{exit, write_object, EXIT_DSC_ERROR, EXIT_DSC_RESOURCE_NOT_FOUND, EXIT_INVALID_ARGS, EXIT_JSON_ERROR, EXIT_VALIDATION_FAILED};
+use dsc_lib::configure::config_doc::ExecutionKind;
+use dsc_lib::dscerror::DscError;
+use dsc_lib::dscresources::dscresource::{validate_json, Capability, DscResource, Invoke};
+use dsc_lib::dscresources::invoke_result::{GetResult, ResourceSetResponse, ResourceTestResponse, SetResult, TestResult};
+use dsc_lib::dscresources::resource_manifest::Kind;
+use dsc_lib::types::{FullyQualifiedTypeName, ResourceVersionReq};
+use dsc_lib::DscManager;
+use jsonschema::Validator;
+use rust_i18n::t;
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt::Write;
+use std::time::Instant;
+use tracing::{debug, error, info};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CheckStatus {
+    Passed,
+    Failed,
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifyCheck {
+    /// The name of the check.
+    pub name: String,
+    pub status: CheckStatus,
+    /// Why the check failed or was skipped.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// The duration of the check in seconds.
+    pub duration: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifyResult {
+    #[serde(rename = "type")]
+    pub resource_type: String,
+    pub version: String,
+    /// Whether none of the checks failed.
+    pub passed: bool,
+    pub checks: Vec<VerifyCheck>,
+}
+
+#[derive(Default)]
+struct Verifier {
+    checks: Vec<VerifyCheck>,
+}
+
+impl Verifier {
+    /// Run a check and record whether it passed, returning the value produced by a passing check.
+    fn run<T>(&mut self, name: &str, check: impl FnOnce() -> Result<T, String>) -> Option<T> {
+        debug!("{}", t!("resource_verify.runningCheck", name = name));
+        let start = Instant::now();
+        let result = check();
+        let duration = start.elapsed().as_secs_f64();
+        match result {
+            Ok(value) => {
+                self.add(name, CheckStatus::Passed, None, duration);
+                Some(value)
+            },
+            Err(message) => {
+                info!("{}", t!("resource_verify.checkFailed", name = name, message = message));
+                self.add(name, CheckStatus::Failed, Some(message), duration);
+                None
+            }
+        }
+    }
+
+    fn skip(&mut self, name: &str, reason: String) {
+        self.add(name, CheckStatus::Skipped, Some(reason), 0.0);
+    }
+
+    fn add(&mut self, name: &str, status: CheckStatus, message: Option<String>, duration: f64) {
+        self.checks.push(VerifyCheck { name: name.to_string(), status, message, duration });
+    }
+}
+
+/// Run the conformance checks against a resource and write the result.
+///
+/// # Arguments
+///
+/// * `dsc` - The DSC manager used to find the resource
+/// * `resource_type` - The type of the resource to verify
+/// * `version` - The optional version requirement of the resource
+/// * `input` - The sample instance used for the checks
+/// * `format` - The output format for the result
+pub fn verify(dsc: &mut DscManager, resource_type: &FullyQualifiedTypeName, version: Option<&ResourceVersionReq>, input: &str, format: Option<&VerifyOutputFormat>) {
+    if input.is_empty() {
+        error!("{}", t!("resource_verify.inputRequired"));
+        exit(EXIT_INVALID_ARGS);
+    }
+
+    let Some(resource) = get_resource(dsc, resource_type, version) else {
+        error!("{}", DscError::ResourceNotFound(resource_type.to_string(), version.map_or(String::new(), |v| v.to_string())));
+        exit(EXIT_DSC_RESOURCE_NOT_FOUND);
+    };
+    if resource.kind == Kind::Adapter {
+        error!("{}: {}", t!("resource_command.invalidOperationOnAdapter"), resource.type_name);
+        exit(EXIT_DSC_ERROR);
+    }
+
+    let result = verify_resource(resource, input);
+    if format == Some(&VerifyOutputFormat::Junit) {
+        println!("{}", to_junit(&result));
+    } else {
+        let json = match serde_json::to_string(&result) {
+            Ok(json) => json,
+            Err(err) => {
+                error!("{}", t!("resource_command.jsonError", err = err));
+                exit(EXIT_JSON_ERROR);
+            }
+        };
+        let format = match format {
+            Some(VerifyOutputFormat::Json) => Some(&OutputFormat::Json),
+            Some(VerifyOutputFormat::PrettyJson) => Some(&OutputFormat::PrettyJson),
+            Some(VerifyOutputFormat::Yaml) => Some(&OutputFormat::Yaml),
+            _ => None,
+        };
+        write_object(&json, format, false);
+    }
+
+    if !result.passed {
+        exit(EXIT_VALIDATION_FAILED);
+    }
+}
+
+/// Run the scripted sequence of checks: schema, get, test, what-if, set, test after set,
+/// idempotency of set, export, and test of each exported instance.
+#[must_use]
+pub fn verify_resource(resource: &DscResource, input: &str) -> VerifyResult {
+    let mut verifier = Verifier::default();
+    let type_name = resource.type_name.to_string();
+
+    let schema = verifier.run("schema", || {
+        let schema: Value = serde_json::from_str(&resource.schema().map_err(|err| err.to_string())?).map_err(|err| err.to_string())?;
+        Validator::new(&schema).map_err(|err| t!("resource_verify.invalidSchema", error = err).to_string())?;
+        Ok(schema)
+    });
+
+    let actual_state = verifier.run("get", || get_state(resource, input));
+    match (&schema, &actual_state) {
+        (Some(schema), Some(actual_state)) => {
+            verifier.run("getMatchesSchema", || validate_json(&type_name, schema, actual_state).map_err(|err| err.to_string()));
+        },
+        _ => verifier.skip("getMatchesSchema", t!("resource_verify.dependsOn", checks = "schema, get").to_string()),
+    }
+
+    let initial_test = verifier.run("test", || test_state(resource, input));
+
+    if resource.capabilities.contains(&Capability::Set) {
+        let what_if = if resource.capabilities.contains(&Capability::SetWhatIf) {
+            let what_if = verifier.run("whatIf", || set_state(resource, input, &ExecutionKind::WhatIf));
+            match (&what_if, &initial_test) {
+                (Some(_), Some(initial_test)) => {
+                    verifier.run("whatIfNoChange", || {
+                        let after_what_if = test_state(resource, input)?;
+                        if after_what_if.in_desired_state == initial_test.in_desired_state && sorted(&after_what_if.diff_properties) == sorted(&initial_test.diff_properties) {
+                            Ok(())
+                        } else {
+                            Err(t!("resource_verify.whatIfChangedState").to_string())
+                        }
+                    });
+                },
+                _ => verifier.skip("whatIfNoChange", t!("resource_verify.dependsOn", checks = "test, whatIf").to_string()),
+            }
+            what_if
+        } else {
+            verifier.skip("whatIf", t!("resource_verify.notSupported", capability = "setWhatIf").to_string());
+            verifier.skip("whatIfNoChange", t!("resource_verify.notSupported", capability = "setWhatIf").to_string());
+            None
+        };
+
+        let set = verifier.run("set", || set_state(resource, input, &ExecutionKind::Actual));
+        match (&what_if, &set) {
+            (Some(what_if), Some(set)) => {
+                verifier.run("whatIfMatchesSet", || {
+                    let expected = sorted(what_if.changed_properties.as_deref().unwrap_or_default());
+                    let actual = sorted(set.changed_properties.as_deref().unwrap_or_default());
+                    if expected == actual {
+                        Ok(())
+                    } else {
+                        Err(t!("resource_verify.whatIfMismatch", what_if = expected.join(", "), set = actual.join(", ")).to_string())
+                    }
+                });
+            },
+            _ => verifier.skip("whatIfMatchesSet", t!("resource_verify.dependsOn", checks = "whatIf, set").to_string()),
+        }
+
+        if set.is_some() {
+            verifier.run("testAfterSet", || {
+                let test = test_state(resource, input)?;
+                if test.in_desired_state {
+                    Ok(())
+                } else {
+                    Err(t!("resource_verify.notInDesiredState", properties = test.diff_properties.join(", ")).to_string())
+                }
+            });
+            verifier.run("setIdempotent", || {
+                let set = set_state(resource, input, &ExecutionKind::Actual)?;
+                match set.changed_properties {
+                    Some(changed) if !changed.is_empty() => Err(t!("resource_verify.setChangedAgain", properties = changed.join(", ")).to_string()),
+                    _ => Ok(()),
+                }
+            });
+        } else {
+            verifier.skip("testAfterSet", t!("resource_verify.dependsOn", checks = "set").to_string());
+            verifier.skip("setIdempotent", t!("resource_verify.dependsOn", checks = "set").to_string());
+        }
+    } else {
+        for name in ["whatIf", "whatIfNoChange", "set", "whatIfMatchesSet", "testAfterSet", "setIdempotent"] {
+            verifier.skip(name, t!("resource_verify.notSupported", capability = "set").to_string());
+        }
+    }
+
+    if resource.capabilities.contains(&Capability::Export) {
+        let instances = verifier.run("export", || resource.export("").map(|result| result.actual_state).map_err(|err| err.to_string()));
+        match (&schema, &instances) {
+            (Some(schema), Some(instances)) => {
+                verifier.run("exportMatchesSchema", || {
+                    for (index, instance) in instances.iter().enumerate() {
+                        validate_json(&type_name, schema, instance).map_err(|err| t!("resource_verify.exportedInstance", index = index, error = err).to_string())?;
+                    }
+                    Ok(())
+                });
+            },
+            _ => verifier.skip("exportMatchesSchema", t!("resource_verify.dependsOn", checks = "schema, export").to_string()),
+        }
+        match &instances {
+            Some(instances) => {
+                verifier.run("exportRoundTrip", || {
+                    for (index, instance) in instances.iter().enumerate() {
+                        let test = test_state(resource, &instance.to_string()).map_err(|err| t!("resource_verify.exportedInstance", index = index, error = err).to_string())?;
+                        if !test.in_desired_state {
+                            let error = t!("resource_verify.notInDesiredState", properties = test.diff_properties.join(", "));
+                            return Err(t!("resource_verify.exportedInstance", index = index, error = error).to_string());
+                        }
+                    }
+                    Ok(())
+                });
+            },
+            None => verifier.skip("exportRoundTrip", t!("resource_verify.dependsOn", checks = "export").to_string()),
+        }
+    } else {
+        for name in ["export", "exportMatchesSchema", "exportRoundTrip"] {
+            verifier.skip(name, t!("resource_verify.notSupported", capability = "export").to_string());
+        }
+    }
+
+    VerifyResult {
+        resource_type: type_name,
+        version: resource.version.to_string(),
+        passed: verifier.checks.iter().all(|check| check.status != CheckStatus::Failed),
+        checks: verifier.checks,
+    }
+}
+
+fn get_state(resource: &DscResource, input: &str) -> Result<Value, String> {
+    match resource.get(input) {
+        Ok(GetResult::Resource(response)) => Ok(response.actual_state),
+        Ok(GetResult::Group(_)) => Err(t!("resource_verify.groupResult").to_string()),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+fn test_state(resource: &DscResource, input: &str) -> Result<ResourceTestResponse, String> {
+    match resource.test(input) {
+        Ok(TestResult::Resource(response)) => Ok(response),
+        Ok(TestResult::Group(_)) => Err(t!("resource_verify.groupResult").to_string()),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+fn set_state(resource: &DscResource, input: &str, execution_type: &ExecutionKind) -> Result<ResourceSetResponse, String> {
+    match resource.set(input, false, execution_type) {
+        Ok(SetResult::Resource(response)) => Ok(response),
+        Ok(SetResult::Group(_)) => Err(t!("resource_verify.groupResult").to_string()),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+fn sorted(properties: &[String]) -> Vec<String> {
+    let mut properties = properties.to_vec();
+    properties.sort();
+    properties
+}
+
+/// Convert the result to a `JUnit` XML report with a test case per check.
+#[must_use]
+pub fn to_junit(result: &VerifyResult) -> String {
+    let count = |status: CheckStatus| result.checks.iter().filter(|check| check.status == status).count();
+    let tests = result.checks.len();
+    let failures = count(CheckStatus::Failed);
+    let skipped = count(CheckStatus::Skipped);
+    let time: f64 = result.checks.iter().map(|check| check.duration).sum();
+    let name = escape_xml(&result.resource_type);
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(xml, "<testsuites name=\"dsc resource verify\" tests=\"{tests}\" failures=\"{failures}\" skipped=\"{skipped}\" time=\"{time:.3}\">");
+    let _ = writeln!(xml, "  <testsuite name=\"{name}\" tests=\"{tests}\" failures=\"{failures}\" skipped=\"{skipped}\" time=\"{time:.3}\">");
+    for check in &result.checks {
+        let check_name = escape_xml(&check.name);
+        let message = escape_xml(check.message.as_deref().unwrap_or_default());
+        match check.status {
+            CheckStatus::Passed => {
+                let _ = writeln!(xml, "    <testcase name=\"{check_name}\" classname=\"{name}\" time=\"{:.3}\"/>", check.duration);
+            },
+            CheckStatus::Failed => {
+                let _ = writeln!(xml, "    <testcase name=\"{check_name}\" classname=\"{name}\" time=\"{:.3}\">", check.duration);
+                let _ = writeln!(xml, "      <failure message=\"{message}\">{message}</failure>");
+                let _ = writeln!(xml, "    </testcase>");
+            },
+            CheckStatus::Skipped => {
+                let _ = writeln!(xml, "    <testcase name=\"{check_name}\" classname=\"{name}\" time=\"{:.3}\">", check.duration);
+                let _ = writeln!(xml, "      <skipped message=\"{message}\"/>");
+                let _ = writeln!(xml, "    </testcase>");
+            },
+        }
+    }
+    let _ = writeln!(xml, "  </testsuite>");
+    xml.push_str("</testsuites>");
+    xml
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
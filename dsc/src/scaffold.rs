@@ -0,0 +1,223 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use crate::args::{OutputFormat, ResourceLanguage, SchemaType};
+use crate::util::{exit, get_schema, write_object, EXIT_DSC_ERROR, EXIT_JSON_ERROR};
+use dsc_lib::dscerror::DscError;
+use dsc_lib::dscresources::{dscresource::validate_json, resource_manifest::ResourceManifest};
+use dsc_lib::types::FullyQualifiedTypeName;
+use dsc_resource_sdk::{manifest::{generate_manifest_from, ManifestInfo}, Operation};
+use rust_i18n::t;
+use schemars::{schema_for, JsonSchema};
+use serde_json::{json, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{error, info};
+
+const CARGO_TOML_TEMPLATE: &str = include_str!("scaffold/Cargo.toml.template");
+const MAIN_RS_TEMPLATE: &str = include_str!("scaffold/main.rs.template");
+const POWERSHELL_TEMPLATE: &str = include_str!("scaffold/resource.ps1.template");
+const BASH_TEMPLATE: &str = include_str!("scaffold/resource.sh.template");
+const SMOKE_TEST_TEMPLATE: &str = include_str!("scaffold/smoke.dsc.yaml.template");
+
+const MANIFEST_SCHEMA: &str = "https://aka.ms/dsc/schemas/v3/bundled/resource/manifest.json";
+const INSTANCE_SCHEMA: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Generate a resource project with a manifest, a skeleton implementation and a smoke test
+/// configuration, then write the list of created files.
+///
+/// # Arguments
+///
+/// * `resource_type` - The type name of the new resource
+/// * `language` - The language of the skeleton implementation
+/// * `path` - The directory to create the project in, defaults to a directory named after the resource
+/// * `format` - The output format for the result
+pub fn new_resource(resource_type: &FullyQualifiedTypeName, language: &ResourceLanguage, path: Option<&String>, format: Option<&OutputFormat>) {
+    let name = get_project_name(resource_type);
+    let directory = path.map_or_else(|| PathBuf::from(&name), PathBuf::from);
+    let files = match create_project(resource_type, &name, language, &directory) {
+        Ok(files) => files,
+        Err(err) => {
+            error!("{}: {err}", t!("scaffold.failedCreateProject"));
+            exit(EXIT_DSC_ERROR);
+        }
+    };
+
+    let result = json!({
+        "type": resource_type.to_string(),
+        "language": language,
+        "path": directory.to_string_lossy(),
+        "files": files,
+    });
+    let json = match serde_json::to_string(&result) {
+        Ok(json) => json,
+        Err(err) => {
+            error!("JSON: {err}");
+            exit(EXIT_JSON_ERROR);
+        }
+    };
+    write_object(&json, format, false);
+}
+
+/// Get the name used for the files of the project from the last segment of the type name.
+fn get_project_name(resource_type: &FullyQualifiedTypeName) -> String {
+    let type_name = resource_type.to_string();
+    let last_segment = type_name.rsplit('/').next().unwrap_or(&type_name);
+    last_segment
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' || c == '-' { c.to_ascii_lowercase() } else { '-' })
+        .collect()
+}
+
+fn create_project(resource_type: &FullyQualifiedTypeName, name: &str, language: &ResourceLanguage, directory: &Path) -> Result<Vec<String>, DscError> {
+    let manifest = generate_manifest(resource_type, name, language);
+    validate_manifest(&manifest)?;
+
+    let mut files: Vec<(PathBuf, String)> = vec![
+        (directory.join(format!("{name}.dsc.resource.json")), serde_json::to_string_pretty(&manifest)?),
+        (directory.join(format!("{name}.smoke.dsc.yaml")), render(SMOKE_TEST_TEMPLATE, resource_type, name)),
+    ];
+    match language {
+        ResourceLanguage::Rust => {
+            files.push((directory.join("Cargo.toml"), render(CARGO_TOML_TEMPLATE, resource_type, name)));
+            files.push((directory.join("src").join("main.rs"), render(MAIN_RS_TEMPLATE, resource_type, name)));
+        },
+        ResourceLanguage::Bash => {
+            files.push((directory.join(format!("{name}.sh")), render(BASH_TEMPLATE, resource_type, name)));
+        },
+        ResourceLanguage::Powershell => {
+            files.push((directory.join(format!("{name}.ps1")), render(POWERSHELL_TEMPLATE, resource_type, name)));
+        },
+    }
+
+    // check all files first so a failure doesn't leave a partial project behind
+    if let Some((path, _)) = files.iter().find(|(path, _)| path.exists()) {
+        return Err(DscError::Validation(t!("scaffold.fileExists", path = path.to_string_lossy()).to_string()));
+    }
+
+    let mut created = Vec::new();
+    for (path, content) in files {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        info!("{}", t!("scaffold.writingFile", path = path.to_string_lossy()));
+        fs::write(&path, content)?;
+        created.push(path.to_string_lossy().to_string());
+    }
+    Ok(created)
+}
+
+/// Generate the manifest invoking the skeleton implementation for get, set, test, and export.
+fn generate_manifest(resource_type: &FullyQualifiedTypeName, name: &str, language: &ResourceLanguage) -> Value {
+    let (executable, args_prefix) = match language {
+        ResourceLanguage::Rust => return generate_sdk_manifest(resource_type, name),
+        ResourceLanguage::Bash => ("bash".to_string(), vec![format!("./{name}.sh")]),
+        ResourceLanguage::Powershell => ("pwsh".to_string(), vec![
+            "-NoLogo".to_string(),
+            "-NonInteractive".to_string(),
+            "-NoProfile".to_string(),
+            "-ExecutionPolicy".to_string(),
+            "Bypass".to_string(),
+            "-Command".to_string(),
+            format!("$input | ./{name}.ps1"),
+        ]),
+    };
+    let args = |operation: &str| {
+        let mut args = args_prefix.clone();
+        args.push(operation.to_string());
+        args
+    };
+
+    json!({
+        "$schema": MANIFEST_SCHEMA,
+        "type": resource_type.to_string(),
+        "version": "0.1.0",
+        "description": t!("scaffold.description", type_name = resource_type).to_string(),
+        "get": {
+            "executable": executable,
+            "args": args("get"),
+            "input": "stdin"
+        },
+        "set": {
+            "executable": executable,
+            "args": args("set"),
+            "input": "stdin",
+            "implementsPretest": false,
+            "return": "state"
+        },
+        "test": {
+            "executable": executable,
+            "args": args("test"),
+            "input": "stdin",
+            "return": "state"
+        },
+        "export": {
+            "executable": executable,
+            "args": args("export")
+        },
+        "exitCodes": {
+            "0": "Success",
+            "1": "Invalid arguments",
+            "2": "Invalid input"
+        },
+        "schema": {
+            "embedded": {
+                "$schema": INSTANCE_SCHEMA,
+                "title": resource_type.to_string(),
+                "type": "object",
+                "additionalProperties": false,
+                "properties": {
+                    "name": {
+                        "type": "string",
+                        "description": t!("scaffold.nameDescription").to_string()
+                    }
+                }
+            }
+        }
+    })
+}
+
+// the instance of the skeleton in `scaffold/main.rs.template`, kept in sync with it so the
+// scaffolded manifest embeds the schema the skeleton generates
+/// The properties of an instance of the resource.
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[serde(deny_unknown_fields)]
+struct SkeletonInstance {
+    /// The name of the instance.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+}
+
+/// Generate the manifest with `dsc-resource-sdk` from the declarations of the Rust skeleton, so it's
+/// the manifest the `manifest` subcommand of the skeleton writes.
+fn generate_sdk_manifest(resource_type: &FullyQualifiedTypeName, name: &str) -> Value {
+    let description = t!("scaffold.description", type_name = resource_type).to_string();
+    let info = ManifestInfo {
+        type_name: resource_type.as_ref(),
+        version: "0.1.0",
+        description: &description,
+        executable: name,
+        operations: &[Operation::Get, Operation::Set, Operation::Test, Operation::Export],
+        tags: &[],
+        supports_what_if: false,
+    };
+    let mut schema = serde_json::to_value(schema_for!(SkeletonInstance)).unwrap_or_default();
+    schema["title"] = Value::String(resource_type.to_string());
+    generate_manifest_from(&info, schema)
+}
+
+/// Validate the generated manifest against the resource manifest schema and the manifest type
+/// used during discovery.
+fn validate_manifest(manifest: &Value) -> Result<(), DscError> {
+    let schema = serde_json::to_value(get_schema(SchemaType::ResourceManifest))?;
+    validate_json("manifest", &schema, manifest)?;
+    serde_json::from_value::<ResourceManifest>(manifest.clone())?;
+    Ok(())
+}
+
+fn render(template: &str, resource_type: &FullyQualifiedTypeName, name: &str) -> String {
+    template
+        .replace("{{type}}", resource_type.as_ref())
+        .replace("{{name}}", name)
+}
@@ -6,8 +6,8 @@ use dsc_lib::{
     configure::{
         config_doc::Configuration,
         config_result::{
-            ConfigurationExportResult, ConfigurationGetResult, ConfigurationSetResult,
-            ConfigurationTestResult,
+            check_resource_errors, ConfigurationExportResult, ConfigurationGetResult,
+            ConfigurationSetResult, ConfigurationTestResult,
         },
         Configurator,
     },
@@ -173,6 +173,9 @@ impl McpServer {
                         Ok(res) => res,
                         Err(e) => return Err(McpError::internal_error(e.to_string(), None)),
                     };
+                    if let Err(e) = check_resource_errors(result.had_errors, &result.messages) {
+                        return Err(McpError::internal_error(e.to_string(), None));
+                    }
                     Ok(ConfigOperationResult::GetResult(Box::new(result)))
                 }
                 ConfigOperation::Set => {
@@ -180,6 +183,9 @@ impl McpServer {
                         Ok(res) => res,
                         Err(e) => return Err(McpError::internal_error(e.to_string(), None)),
                     };
+                    if let Err(e) = check_resource_errors(result.had_errors, &result.messages) {
+                        return Err(McpError::internal_error(e.to_string(), None));
+                    }
                     Ok(ConfigOperationResult::SetResult(Box::new(result)))
                 }
                 ConfigOperation::Test => {
@@ -187,6 +193,9 @@ impl McpServer {
                         Ok(res) => res,
                         Err(e) => return Err(McpError::internal_error(e.to_string(), None)),
                     };
+                    if let Err(e) = check_resource_errors(result.had_errors, &result.messages) {
+                        return Err(McpError::internal_error(e.to_string(), None));
+                    }
                     Ok(ConfigOperationResult::TestResult(Box::new(result)))
                 }
                 ConfigOperation::Export => {
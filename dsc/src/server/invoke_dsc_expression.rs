@@ -2,7 +2,7 @@
 // Licensed under the MIT License.
 
 use crate::server::mcp_server::McpServer;
-use dsc_lib::{configure::context::Context, parser::Statement};
+use dsc_lib::{configure::{context::Context, function_library::{FunctionImport, UserFunctionRegistry}}, parser::Statement};
 use rmcp::{ErrorData as McpError, Json, tool, tool_router, handler::server::wrapper::Parameters};
 use rust_i18n::t;
 use schemars::JsonSchema;
@@ -26,6 +26,8 @@ pub struct ExpressionResponse {
 pub struct ExpressionRequest {
     #[schemars(description = "The DSC expression to invoke")]
     pub expression: String,
+    #[schemars(description = "Function libraries to import so their user functions can be used in the expression")]
+    pub imports: Option<Vec<FunctionImport>>,
 }
 
 #[tool_router(router = invoke_dsc_expression_router, vis = "pub")]
@@ -40,10 +42,15 @@ impl McpServer {
             open_world_hint = true,
         )
     )]
-    pub async fn invoke_dsc_expression(&self, Parameters(ExpressionRequest { expression }): Parameters<ExpressionRequest>) -> Result<Json<ExpressionResponse>, McpError> {
+    pub async fn invoke_dsc_expression(&self, Parameters(ExpressionRequest { expression, imports }): Parameters<ExpressionRequest>) -> Result<Json<ExpressionResponse>, McpError> {
         let result = task::spawn_blocking(move || {
             let mut statement = Statement::new().map_err(|e| McpError::internal_error(t!("server.invoke_dsc_expression.parserInitializationFailed", error = e), None))?;
-            let result = statement.parse_and_execute(&expression, &Context::new())
+            let mut context = Context::new();
+            if let Some(imports) = imports {
+                UserFunctionRegistry::new().import(&imports, &mut context.user_functions)
+                    .map_err(|e| McpError::invalid_request(t!("server.invoke_dsc_expression.importFailed", error = e), None))?;
+            }
+            let result = statement.parse_and_execute(&expression, &context)
                 .map_err(|e| McpError::invalid_request(t!("server.invoke_dsc_expression.expressionEvaluationFailed", expression = expression, error = e), None))?;
             Ok(ExpressionResponse { result: ExpressionResult::Value(result) })
         }).await.map_err(|e| McpError::internal_error(e.to_string(), None))??;
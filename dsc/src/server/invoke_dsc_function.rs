@@ -2,7 +2,7 @@
 // Licensed under the MIT License.
 
 use crate::server::mcp_server::McpServer;
-use dsc_lib::{configure::context::Context, functions::FunctionDispatcher};
+use dsc_lib::{configure::{context::Context, function_library::{FunctionImport, UserFunctionRegistry}}, functions::FunctionDispatcher};
 use rmcp::{ErrorData as McpError, Json, tool, tool_router, handler::server::wrapper::Parameters};
 use rust_i18n::t;
 use schemars::JsonSchema;
@@ -29,6 +29,8 @@ pub struct FunctionRequest {
     pub function: String,
     #[schemars(description = "The parameters to pass to the DSC function as JSON array.  Must match the function JSON schema from `list_dsc_functions` tool.")]
     pub parameters: Vec<Value>,
+    #[schemars(description = "Function libraries to import so their user functions can be invoked")]
+    pub imports: Option<Vec<FunctionImport>>,
 }
 
 #[tool_router(router = invoke_dsc_function_router, vis = "pub")]
@@ -43,11 +45,16 @@ impl McpServer {
             open_world_hint = true,
         )
     )]
-    pub async fn invoke_dsc_function(&self, Parameters(FunctionRequest { function, parameters }): Parameters<FunctionRequest>) -> Result<Json<FunctionResponse>, McpError> {
+    pub async fn invoke_dsc_function(&self, Parameters(FunctionRequest { function, parameters, imports }): Parameters<FunctionRequest>) -> Result<Json<FunctionResponse>, McpError> {
         let result = task::spawn_blocking(move || {
             // if parameters is not JSON array, return error
             let function_dispatcher = FunctionDispatcher::new();
-            let result = function_dispatcher.invoke(&function, &parameters, &Context::new())
+            let mut context = Context::new();
+            if let Some(imports) = imports {
+                UserFunctionRegistry::new().import(&imports, &mut context.user_functions)
+                    .map_err(|e| McpError::invalid_request(t!("server.invoke_dsc_function.importFailed", error = e), None))?;
+            }
+            let result = function_dispatcher.invoke(&function, &parameters, &context)
                 .map_err(|e| McpError::invalid_request(t!("server.invoke_dsc_function.functionInvocationFailed", function = function, error = e), None))?;
             Ok(FunctionResponse { result: FunctionValue::Value(result) })
         }).await.map_err(|e| McpError::internal_error(e.to_string(), None))??;
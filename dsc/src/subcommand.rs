@@ -1,24 +1,33 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
-use crate::args::{ConfigSubCommand, SchemaType, ExtensionSubCommand, FunctionSubCommand, GetOutputFormat, ListOutputFormat, OutputFormat, ResourceSubCommand};
+use crate::args::{ConfigSubCommand, SchemaType, ExtensionSubCommand, FunctionSubCommand, GetOutputFormat, InputFormat, ListOutputFormat, OutputFormat, ResourceCacheSubCommand, ResourceSubCommand};
 use crate::resolve::{get_contents, Include};
 use crate::resource_command::{get_resource, self};
+use crate::resource_verify;
+use crate::scaffold;
 use crate::tablewriter::Table;
-use crate::util::{get_input, get_schema, in_desired_state, set_dscconfigroot, write_object, DSC_CONFIG_ROOT, EXIT_DSC_ASSERTION_FAILED, EXIT_DSC_ERROR, EXIT_INVALID_ARGS, EXIT_INVALID_INPUT, EXIT_JSON_ERROR};
+use crate::util::{exit, get_env_parameters, get_input, get_input_lines, get_schema, in_desired_state, set_dscconfigroot, write_object, ParameterOptions, ParameterSource, DSC_CONFIG_ROOT, EXIT_DSC_ASSERTION_FAILED, EXIT_DSC_ERROR, EXIT_INVALID_ARGS, EXIT_INVALID_INPUT, EXIT_JSON_ERROR, EXIT_VALIDATION_FAILED};
 use dsc_lib::types::{FullyQualifiedTypeName, ResourceVersionReq, TypeNameFilter};
 use dsc_lib::{
     configure::{
         config_doc::{
             Configuration,
+            DataType,
             ExecutionKind,
             Resource,
         },
-        config_result::ResourceGetResult,
+        config_result::{MessageLevel, ResourceGetResult, ResourceMessage},
+        lock::ConfigurationLock,
+        parameters::{import_parameters, SECURE_VALUE_REDACTED},
         Configurator,
     },
     discovery::discovery_trait::{DiscoveryFilter, DiscoveryKind},
     discovery::command_discovery::ImportedManifest,
+    discovery::manifest_cache::{clear_manifest_cache, rebuild_manifest_cache},
+    discovery::manifest_signature::ManifestTrust,
+    discovery::manifest_validation::validate_manifest_file,
+    discovery::resource_bundle::{install_bundle, uninstall_resource},
     dscerror::DscError,
     DscManager,
     dscresources::invoke_result::{
@@ -39,15 +48,17 @@ use std::{
     collections::HashMap,
     io::{self, IsTerminal},
     path::Path,
-    process::exit
 };
-use tracing::{debug, error, trace};
+use tracing::{debug, error, info, trace};
 
 pub fn config_get(configurator: &mut Configurator, format: Option<&OutputFormat>, as_group: &bool)
 {
     match configurator.invoke_get() {
         Ok(result) => {
             if *as_group {
+                if result.had_errors {
+                    exit_with_resource_errors(&result.messages);
+                }
                 let json = match serde_json::to_string(&(result.results)) {
                     Ok(json) => json,
                     Err(err) => {
@@ -67,7 +78,7 @@ pub fn config_get(configurator: &mut Configurator, format: Option<&OutputFormat>
                 };
                 write_object(&json, format, false);
                 if result.had_errors {
-                    exit(EXIT_DSC_ERROR);
+                    exit_with_resource_errors(&result.messages);
                 }
             }
         },
@@ -83,6 +94,9 @@ pub fn config_set(configurator: &mut Configurator, format: Option<&OutputFormat>
     match configurator.invoke_set(false) {
         Ok(result) => {
             if *as_group {
+                if result.had_errors {
+                    exit_with_resource_errors(&result.messages);
+                }
                 let json = match serde_json::to_string(&(result.results)) {
                     Ok(json) => json,
                     Err(err) => {
@@ -102,7 +116,7 @@ pub fn config_set(configurator: &mut Configurator, format: Option<&OutputFormat>
                 };
                 write_object(&json, format, false);
                 if result.had_errors {
-                    exit(EXIT_DSC_ERROR);
+                    exit_with_resource_errors(&result.messages);
                 }
             }
         },
@@ -118,6 +132,9 @@ pub fn config_test(configurator: &mut Configurator, format: Option<&OutputFormat
     match configurator.invoke_test() {
         Ok(result) => {
             if *as_group {
+                if result.had_errors {
+                    exit_with_resource_errors(&result.messages);
+                }
                 let json = if *as_config {
                     let mut result_configuration = Configuration::new();
                     result_configuration.resources = Vec::new();
@@ -203,7 +220,7 @@ pub fn config_test(configurator: &mut Configurator, format: Option<&OutputFormat
                 };
                 write_object(&json, format, false);
                 if result.had_errors {
-                    exit(EXIT_DSC_ERROR);
+                    exit_with_resource_errors(&result.messages);
                 }
             }
         },
@@ -243,6 +260,14 @@ pub fn config_export(configurator: &mut Configurator, format: Option<&OutputForm
     }
 }
 
+/// Write the errors reported by resources in a configuration result and exit.
+fn exit_with_resource_errors(messages: &[ResourceMessage]) -> ! {
+    for msg in messages.iter().filter(|msg| msg.level == MessageLevel::Error) {
+        error!("{}", msg.message);
+    }
+    exit(EXIT_DSC_ERROR);
+}
+
 fn initialize_config_root(path: Option<&String>) -> Option<String> {
     // code that calls this pass in either None, Some("-"), or Some(path)
     // in the case of `-` we treat it as None, but need to pass it back as subsequent processing needs to handle it
@@ -273,15 +298,58 @@ fn initialize_config_root(path: Option<&String>) -> Option<String> {
     None
 }
 
+/// Set the value of a parameter in parameter input, which is normalized to the simple
+/// `{ "parameters": { "name": value } }` form.
+fn insert_parameter(parameters: &mut Option<serde_json::Value>, name: &str, value: serde_json::Value) -> Result<(), DscError> {
+    let mut parameter_values = match parameters.as_ref() {
+        Some(input) => import_parameters(input)?,
+        None => HashMap::new(),
+    };
+    parameter_values.insert(name.to_string(), value);
+    *parameters = Some(serde_json::json!({ "parameters": parameter_values }));
+    Ok(())
+}
+
+/// Write the effective value, type, and source of each parameter with secure values redacted.
+fn show_parameters(configurator: &Configurator, sources: &HashMap<String, ParameterSource>, format: Option<&OutputFormat>) {
+    let mut names: Vec<&String> = configurator.context.parameters.keys().collect();
+    names.sort();
+    let mut effective_parameters = Vec::new();
+    for name in names {
+        let (value, parameter_type) = &configurator.context.parameters[name];
+        let value = match parameter_type {
+            DataType::SecureString | DataType::SecureObject => serde_json::Value::String(SECURE_VALUE_REDACTED.to_string()),
+            _ => value.clone(),
+        };
+        let source = sources.get(name).unwrap_or(&ParameterSource::Default);
+        let mut parameter = serde_json::Map::new();
+        parameter.insert("name".to_string(), serde_json::Value::String(name.clone()));
+        parameter.insert("type".to_string(), serde_json::Value::String(parameter_type.to_string()));
+        parameter.insert("value".to_string(), value);
+        parameter.insert("source".to_string(), serde_json::Value::String(source.to_string()));
+        effective_parameters.push(serde_json::Value::Object(parameter));
+    }
+
+    let json = match serde_json::to_string(&effective_parameters) {
+        Ok(json) => json,
+        Err(err) => {
+            error!("JSON Error: {err}");
+            exit(EXIT_JSON_ERROR);
+        }
+    };
+    write_object(&json, format, false);
+}
+
 #[allow(clippy::too_many_lines)]
 #[allow(clippy::too_many_arguments)]
-pub fn config(subcommand: &ConfigSubCommand, parameters: &Option<String>, mounted_path: Option<&String>, as_group: &bool, as_assert: &bool, as_include: &bool, progress_format: ProgressFormat) {
+pub fn config(subcommand: &ConfigSubCommand, parameters: &Option<String>, parameter_options: &ParameterOptions, mounted_path: Option<&String>, as_group: &bool, as_assert: &bool, as_include: &bool, progress_format: ProgressFormat) {
     let (new_parameters, json_string) = match subcommand {
         ConfigSubCommand::Get { input, file, .. } |
         ConfigSubCommand::Set { input, file, .. } |
         ConfigSubCommand::Test { input, file, .. } |
         ConfigSubCommand::Validate { input, file, .. } |
-        ConfigSubCommand::Export { input, file, .. } => {
+        ConfigSubCommand::Export { input, file, .. } |
+        ConfigSubCommand::Lock { input, file, .. } => {
             let new_path = initialize_config_root(file.as_ref());
             let document = get_input(input.as_ref(), new_path.as_ref());
             if *as_include {
@@ -311,6 +379,15 @@ pub fn config(subcommand: &ConfigSubCommand, parameters: &Option<String>, mounte
         }
     };
 
+    // the lock file is next to the configuration document, so documents from stdin can't be locked
+    let lock_path = match subcommand {
+        ConfigSubCommand::Get { file, .. } |
+        ConfigSubCommand::Set { file, .. } |
+        ConfigSubCommand::Test { file, .. } |
+        ConfigSubCommand::Lock { file, .. } => file.as_ref().filter(|file| *file != "-").map(|file| ConfigurationLock::get_lock_path(Path::new(file))),
+        _ => None,
+    };
+
     let mut configurator = match Configurator::new(&json_string, progress_format) {
         Ok(configurator) => configurator,
         Err(err) => {
@@ -325,7 +402,7 @@ pub fn config(subcommand: &ConfigSubCommand, parameters: &Option<String>, mounte
         configurator.context.execution_type = ExecutionKind::WhatIf;
     }
 
-    let parameters: Option<serde_json::Value> = match if new_parameters.is_some() {
+    let mut parameters: Option<serde_json::Value> = match if new_parameters.is_some() {
         &new_parameters
     } else {
         parameters
@@ -359,6 +436,30 @@ pub fn config(subcommand: &ConfigSubCommand, parameters: &Option<String>, mounte
         }
     };
 
+    let mut parameter_sources = parameter_options.sources.clone();
+    if let Some(prefix) = &parameter_options.env_prefix {
+        let defined_parameters = configurator.get_config().parameters.clone().unwrap_or_default();
+        let env_parameters = match get_env_parameters(prefix, &defined_parameters) {
+            Ok(env_parameters) => env_parameters,
+            Err(err) => {
+                error!("{}: {err}", t!("subcommand.failedSetParameters"));
+                exit(EXIT_INVALID_INPUT);
+            }
+        };
+        for (name, variable, value) in env_parameters {
+            // inline parameters are the most explicit so they take precedence over the environment
+            if parameter_sources.get(&name) == Some(&ParameterSource::Inline) {
+                debug!("{}", t!("subcommand.envParameterOverriddenInline", name = name, variable = variable));
+                continue;
+            }
+            if let Err(err) = insert_parameter(&mut parameters, &name, value) {
+                error!("{}: {err}", t!("subcommand.failedSetParameters"));
+                exit(EXIT_INVALID_INPUT);
+            }
+            parameter_sources.insert(name, ParameterSource::Environment(variable));
+        }
+    }
+
     if let Some(path) = mounted_path {
         if !Path::new(&path).exists() {
             error!("{}: '{path}'", t!("subcommand.invalidPath"));
@@ -378,6 +479,36 @@ pub fn config(subcommand: &ConfigSubCommand, parameters: &Option<String>, mounte
         exit(EXIT_INVALID_INPUT);
     }
 
+    if parameter_options.show_parameters {
+        let output_format = match subcommand {
+            ConfigSubCommand::Get { output_format, .. } |
+            ConfigSubCommand::Set { output_format, .. } |
+            ConfigSubCommand::Test { output_format, .. } |
+            ConfigSubCommand::Validate { output_format, .. } |
+            ConfigSubCommand::Export { output_format, .. } |
+            ConfigSubCommand::Lock { output_format, .. } |
+            ConfigSubCommand::Resolve { output_format, .. } => output_format.as_ref(),
+        };
+        show_parameters(&configurator, &parameter_sources, output_format);
+        return;
+    }
+
+    if let Some(lock_path) = &lock_path
+        && lock_path.exists()
+        && !matches!(subcommand, ConfigSubCommand::Lock { .. }) {
+        let lock = match ConfigurationLock::load(lock_path) {
+            Ok(lock) => lock,
+            Err(err) => {
+                error!("{err}");
+                exit(EXIT_DSC_ERROR);
+            }
+        };
+        if let Err(err) = configurator.set_lock(&lock) {
+            error!("{}: {err}", t!("subcommand.lockNotSatisfied", path = lock_path.to_string_lossy()));
+            exit(EXIT_DSC_ERROR);
+        }
+    }
+
     match subcommand {
         ConfigSubCommand::Get { output_format, .. } => {
             config_get(&mut configurator, output_format.as_ref(), as_group);
@@ -427,6 +558,9 @@ pub fn config(subcommand: &ConfigSubCommand, parameters: &Option<String>, mounte
         ConfigSubCommand::Export { output_format, .. } => {
             config_export(&mut configurator, output_format.as_ref());
         },
+        ConfigSubCommand::Lock { output_format, .. } => {
+            config_lock(&mut configurator, lock_path.as_deref(), output_format.as_ref());
+        },
         ConfigSubCommand::Resolve { output_format, .. } => {
             let configuration = match serde_json::from_str(&json_string) {
                 Ok(json) => json,
@@ -461,6 +595,34 @@ pub fn config(subcommand: &ConfigSubCommand, parameters: &Option<String>, mounte
     }
 }
 
+fn config_lock(configurator: &mut Configurator, lock_path: Option<&Path>, format: Option<&OutputFormat>)
+{
+    let lock = match configurator.lock() {
+        Ok(lock) => lock,
+        Err(err) => {
+            error!("{}: {err}", t!("subcommand.failedLockConfiguration"));
+            exit(EXIT_DSC_ERROR);
+        }
+    };
+
+    if let Some(lock_path) = lock_path {
+        if let Err(err) = lock.save(lock_path) {
+            error!("{}: {err}", t!("subcommand.failedWriteLockFile", path = lock_path.to_string_lossy()));
+            exit(EXIT_DSC_ERROR);
+        }
+        info!("{}", t!("subcommand.wroteLockFile", path = lock_path.to_string_lossy()));
+    }
+
+    let json = match serde_json::to_string(&lock) {
+        Ok(json) => json,
+        Err(err) => {
+            error!("JSON Error: {err}");
+            exit(EXIT_JSON_ERROR);
+        }
+    };
+    write_object(&json, format, false);
+}
+
 /// Validate configuration.
 ///
 /// # Arguments
@@ -552,8 +714,15 @@ pub fn resource(subcommand: &ResourceSubCommand, progress_format: ProgressFormat
     let mut dsc = DscManager::new();
 
     match subcommand {
-        ResourceSubCommand::List { resource_name, adapter_name, description, tags, output_format } => {
-            list_resources(&mut dsc, resource_name, adapter_name.as_ref(), description.as_ref(), tags.as_ref(), output_format.as_ref(), progress_format);
+        ResourceSubCommand::List { resource_name, adapter_name, description, tags, show_untrusted, output_format } => {
+            let filter = ResourceListFilter {
+                resource_name,
+                adapter_name: adapter_name.as_ref(),
+                description: description.as_ref(),
+                tags: tags.as_ref(),
+                show_untrusted: *show_untrusted,
+            };
+            list_resources(&mut dsc, &filter, output_format.as_ref(), progress_format);
         },
         ResourceSubCommand::Schema { resource, required_version: version, output_format } => {
             if let Err(err) = dsc.find_resources(&[DiscoveryFilter::new(resource, version.clone(), None)], progress_format) {
@@ -570,12 +739,16 @@ pub fn resource(subcommand: &ResourceSubCommand, progress_format: ProgressFormat
             let parsed_input = get_input(input.as_ref(), file.as_ref());
             resource_command::export(&mut dsc, resource, version.as_ref(), &parsed_input, output_format.as_ref());
         },
-        ResourceSubCommand::Get { resource, required_version: version, input, file: path, all, output_format } => {
+        ResourceSubCommand::Get { resource, required_version: version, input, file: path, all, input_format, output_format } => {
             if let Err(err) = dsc.find_resources(&[DiscoveryFilter::new(resource, version.clone(), None)], progress_format) {
                 error!("{}: {err}", t!("subcommand.failedDiscoverResource"));
                 exit(EXIT_DSC_ERROR);
             }
-            if *all {
+            if *input_format == InputFormat::JsonLines {
+                let inputs = get_input_lines(input.as_ref(), path.as_ref());
+                resource_command::get_batch(&mut dsc, resource, version.as_ref(), &inputs, output_format.as_ref());
+            }
+            else if *all {
                 resource_command::get_all(&mut dsc, resource, version.as_ref(), output_format.as_ref());
             }
             else {
@@ -587,21 +760,33 @@ pub fn resource(subcommand: &ResourceSubCommand, progress_format: ProgressFormat
                 resource_command::get(&mut dsc, resource, version.as_ref(), &parsed_input, output_format.as_ref());
             }
         },
-        ResourceSubCommand::Set { resource, required_version: version, input, file: path, output_format, what_if } => {
+        ResourceSubCommand::Set { resource, required_version: version, input, file: path, input_format, output_format, what_if } => {
             if let Err(err) = dsc.find_resources(&[DiscoveryFilter::new(resource, version.clone(), None)], progress_format) {
                 error!("{}: {err}", t!("subcommand.failedDiscoverResource"));
                 exit(EXIT_DSC_ERROR);
             }
-            let parsed_input = get_input(input.as_ref(), path.as_ref());
-            resource_command::set(&mut dsc, resource, version.as_ref(), &parsed_input, output_format.as_ref(), *what_if);
+            if *input_format == InputFormat::JsonLines {
+                let inputs = get_input_lines(input.as_ref(), path.as_ref());
+                resource_command::set_batch(&mut dsc, resource, version.as_ref(), &inputs, output_format.as_ref(), *what_if);
+            }
+            else {
+                let parsed_input = get_input(input.as_ref(), path.as_ref());
+                resource_command::set(&mut dsc, resource, version.as_ref(), &parsed_input, output_format.as_ref(), *what_if);
+            }
         },
-        ResourceSubCommand::Test { resource, required_version: version, input, file: path, output_format } => {
+        ResourceSubCommand::Test { resource, required_version: version, input, file: path, input_format, output_format } => {
             if let Err(err) = dsc.find_resources(&[DiscoveryFilter::new(resource, version.clone(), None)], progress_format) {
                 error!("{}: {err}", t!("subcommand.failedDiscoverResource"));
                 exit(EXIT_DSC_ERROR);
             }
-            let parsed_input = get_input(input.as_ref(), path.as_ref());
-            resource_command::test(&mut dsc, resource, version.as_ref(), &parsed_input, output_format.as_ref());
+            if *input_format == InputFormat::JsonLines {
+                let inputs = get_input_lines(input.as_ref(), path.as_ref());
+                resource_command::test_batch(&mut dsc, resource, version.as_ref(), &inputs, output_format.as_ref());
+            }
+            else {
+                let parsed_input = get_input(input.as_ref(), path.as_ref());
+                resource_command::test(&mut dsc, resource, version.as_ref(), &parsed_input, output_format.as_ref());
+            }
         },
         ResourceSubCommand::Delete { resource, required_version: version, input, file: path, output_format, what_if } => {
             if let Err(err) = dsc.find_resources(&[DiscoveryFilter::new(resource, version.clone(), None)], progress_format) {
@@ -611,9 +796,122 @@ pub fn resource(subcommand: &ResourceSubCommand, progress_format: ProgressFormat
             let parsed_input = get_input(input.as_ref(), path.as_ref());
             resource_command::delete(&mut dsc, resource, version.as_ref(), &parsed_input, output_format.as_ref(), *what_if);
         },
+        ResourceSubCommand::Verify { resource, required_version: version, input, file: path, output_format } => {
+            if let Err(err) = dsc.find_resources(&[DiscoveryFilter::new(resource, version.clone(), None)], progress_format) {
+                error!("{}: {err}", t!("subcommand.failedDiscoverResource"));
+                exit(EXIT_DSC_ERROR);
+            }
+            let parsed_input = get_input(input.as_ref(), path.as_ref());
+            resource_verify::verify(&mut dsc, resource, version.as_ref(), &parsed_input, output_format.as_ref());
+        },
+        ResourceSubCommand::New { resource_type, language, path, output_format } => {
+            scaffold::new_resource(resource_type, language, path.as_ref(), output_format.as_ref());
+        },
+        ResourceSubCommand::ValidateManifest { paths, output_format } => {
+            validate_manifests(paths, output_format.as_ref());
+        },
+        ResourceSubCommand::Install { bundle, force, output_format } => {
+            install(bundle, *force, output_format.as_ref());
+        },
+        ResourceSubCommand::Uninstall { resource, required_version, output_format } => {
+            uninstall(resource, required_version.as_ref(), output_format.as_ref());
+        },
+        ResourceSubCommand::Cache { subcommand } => {
+            resource_cache(subcommand);
+        },
+    }
+}
+
+fn validate_manifests(paths: &[String], format: Option<&OutputFormat>) {
+    let mut has_errors = false;
+    for (index, path) in paths.iter().enumerate() {
+        let result = match validate_manifest_file(Path::new(path)) {
+            Ok(result) => result,
+            Err(err) => {
+                error!("{}: {err}", t!("subcommand.failedValidateManifest", path = path));
+                exit(EXIT_DSC_ERROR);
+            }
+        };
+        has_errors |= !result.valid;
+        let json = match serde_json::to_string(&result) {
+            Ok(json) => json,
+            Err(err) => {
+                error!("JSON Error: {err}");
+                exit(EXIT_JSON_ERROR);
+            }
+        };
+        write_object(&json, format, index > 0);
+    }
+
+    if has_errors {
+        exit(EXIT_VALIDATION_FAILED);
+    }
+}
+
+fn install(bundle: &str, force: bool, format: Option<&OutputFormat>) {
+    let installed = match install_bundle(Path::new(bundle), force) {
+        Ok(installed) => installed,
+        Err(err) => {
+            error!("{}: {err}", t!("subcommand.failedInstallBundle", bundle = bundle));
+            exit(EXIT_DSC_ERROR);
+        }
+    };
+
+    let json = match serde_json::to_string(&installed) {
+        Ok(json) => json,
+        Err(err) => {
+            error!("JSON Error: {err}");
+            exit(EXIT_JSON_ERROR);
+        }
+    };
+    write_object(&json, format, false);
+}
+
+fn uninstall(resource: &FullyQualifiedTypeName, version: Option<&ResourceVersionReq>, format: Option<&OutputFormat>) {
+    let removed = match uninstall_resource(resource, version) {
+        Ok(removed) => removed,
+        Err(err) => {
+            error!("{}: {err}", t!("subcommand.failedUninstallResource", resource = resource));
+            exit(EXIT_DSC_ERROR);
+        }
+    };
+
+    for (index, bundle) in removed.iter().enumerate() {
+        let json = match serde_json::to_string(bundle) {
+            Ok(json) => json,
+            Err(err) => {
+                error!("JSON Error: {err}");
+                exit(EXIT_JSON_ERROR);
+            }
+        };
+        write_object(&json, format, index > 0);
     }
 }
 
+fn resource_cache(subcommand: &ResourceCacheSubCommand) {
+    let (result, output_format) = match subcommand {
+        ResourceCacheSubCommand::Clear { output_format } => (clear_manifest_cache(), output_format),
+        ResourceCacheSubCommand::Rebuild { output_format } => (rebuild_manifest_cache(), output_format),
+    };
+
+    let info = match result {
+        Ok(info) => info,
+        Err(err) => {
+            error!("{}: {err}", t!("subcommand.failedManifestCache"));
+            exit(EXIT_DSC_ERROR);
+        }
+    };
+
+    let json = match serde_json::to_string(&info) {
+        Ok(json) => json,
+        Err(err) => {
+            error!("JSON Error: {err}");
+            exit(EXIT_JSON_ERROR);
+        }
+    };
+    write_object(&json, output_format.as_ref(), false);
+}
+
 /// Indicates whether to emit a table based on the output format and whether stdout is a terminal.
 fn should_write_table(format: Option<&ListOutputFormat>) -> bool {
     if matches!(format, Some(ListOutputFormat::TableNoTruncate)) {
@@ -774,24 +1072,40 @@ fn list_functions(functions: &FunctionDispatcher, function_name: Option<&String>
     }
 }
 
+/// The filters applied when listing resources.
+pub struct ResourceListFilter<'a> {
+    /// Only list resources whose type name matches.
+    pub resource_name: &'a TypeNameFilter,
+    /// Only list the resources of adapters whose type name matches.
+    pub adapter_name: Option<&'a TypeNameFilter>,
+    /// Only list resources whose description contains this text.
+    pub description: Option<&'a String>,
+    /// Only list resources with any of these tags.
+    pub tags: Option<&'a Vec<String>>,
+    /// Also list resources that aren't signed by a trusted publisher.
+    pub show_untrusted: bool,
+}
+
 pub fn list_resources(
     dsc: &mut DscManager,
-    resource_name: &TypeNameFilter,
-    adapter_name: Option<&TypeNameFilter>,
-    description: Option<&String>,
-    tags: Option<&Vec<String>>,
+    filter: &ResourceListFilter,
     format: Option<&ListOutputFormat>,
     progress_format: ProgressFormat
 ) {
+    let ResourceListFilter { resource_name, adapter_name, description, tags, show_untrusted } = *filter;
     let mut write_table = false;
-    let mut table = Table::new(&[
-        t!("subcommand.tableHeader_type").to_string().as_ref(),
-        t!("subcommand.tableHeader_kind").to_string().as_ref(),
-        t!("subcommand.tableHeader_version").to_string().as_ref(),
-        t!("subcommand.tableHeader_capabilities").to_string().as_ref(),
-        t!("subcommand.tableHeader_adapter").to_string().as_ref(),
-        t!("subcommand.tableHeader_description").to_string().as_ref(),
-    ]);
+    let mut headers = vec![
+        t!("subcommand.tableHeader_type").to_string(),
+        t!("subcommand.tableHeader_kind").to_string(),
+        t!("subcommand.tableHeader_version").to_string(),
+        t!("subcommand.tableHeader_capabilities").to_string(),
+        t!("subcommand.tableHeader_adapter").to_string(),
+        t!("subcommand.tableHeader_description").to_string(),
+    ];
+    if show_untrusted {
+        headers.push(t!("subcommand.tableHeader_trust").to_string());
+    }
+    let mut table = Table::new(&headers.iter().map(String::as_str).collect::<Vec<&str>>());
     if format == Some(&ListOutputFormat::TableNoTruncate) || (format.is_none() && io::stdout().is_terminal()) {
         // write as table if format is not specified and interactive
         write_table = true;
@@ -800,6 +1114,11 @@ pub fn list_resources(
 
     for manifest_resource in dsc.list_available(&DiscoveryKind::Resource, resource_name, adapter_name, progress_format) {
         if let ImportedManifest::Resource(resource) = manifest_resource {
+            if resource.trust.is_untrusted() && !show_untrusted {
+                debug!("{}", t!("subcommand.skippingUntrustedResource", resource = resource.type_name));
+                continue;
+            }
+
             let capability_types = [
                 (Capability::Get, "g"),
                 (Capability::Set, "s"),
@@ -810,6 +1129,7 @@ pub fn list_resources(
                 (Capability::DeleteWhatIf, "W"),
                 (Capability::Export, "e"),
                 (Capability::Resolve, "r"),
+                (Capability::Batch, "b"),
             ];
             let mut capabilities = "-".repeat(capability_types.len());
 
@@ -849,14 +1169,22 @@ pub fn list_resources(
             }
 
             if write_table {
-                table.add_row(vec![
+                let mut row = vec![
                     resource.type_name.to_string(),
                     format!("{:?}", resource.kind),
                     resource.version.to_string(),
                     capabilities,
-                    resource.require_adapter.unwrap_or_default().to_string(),
-                    resource.description.unwrap_or_default()
-                ]);
+                    resource.require_adapter.clone().unwrap_or_default().to_string(),
+                    resource.description.clone().unwrap_or_default()
+                ];
+                if show_untrusted {
+                    row.push(match &resource.trust {
+                        ManifestTrust::NotVerified => String::new(),
+                        ManifestTrust::Trusted { publisher } => publisher.clone(),
+                        ManifestTrust::Untrusted { .. } => t!("subcommand.untrusted").to_string(),
+                    });
+                }
+                table.add_row(row);
             }
             else {
                 // convert to json
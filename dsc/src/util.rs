@@ -9,6 +9,8 @@ use dsc_lib::{
     configure::{
         config_doc::{
             Configuration,
+            DataType,
+            Parameter,
             Resource,
             RestartRequired,
         },
@@ -27,6 +29,7 @@ use dsc_lib::{
     dscresources::{
         command_resource::TraceLevel,
         dscresource::DscResource,
+        resource_server::shutdown_resource_servers,
         invoke_result::{
             GetResult,
             SetResult,
@@ -53,9 +56,9 @@ use schemars::{Schema, schema_for};
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::env;
+use std::fmt::Display;
 use std::io::{IsTerminal, Read, stdout, Write};
 use std::path::Path;
-use std::process::exit;
 use syntect::{
     easy::HighlightLines,
     highlighting::ThemeSet,
@@ -79,6 +82,14 @@ pub const EXIT_BICEP_FAILED: i32 = 10;
 pub const DSC_CONFIG_ROOT: &str = "DSC_CONFIG_ROOT";
 pub const DSC_TRACE_LEVEL: &str = "DSC_TRACE_LEVEL";
 
+/// Shut down running resource servers and exit the process with the given code.
+///
+/// Used instead of `std::process::exit` so resource servers don't outlive DSC on any exit path.
+pub fn exit(code: i32) -> ! {
+    shutdown_resource_servers();
+    std::process::exit(code)
+}
+
 #[derive(Deserialize)]
 pub struct TracingSetting {
     /// Trace level to use - see pub enum `TraceLevel` in `dsc_lib\src\dscresources\command_resource.rs`
@@ -426,25 +437,7 @@ pub fn get_input(input: Option<&String>, file: Option<&String>) -> String {
         debug!("{} {path}", t!("util.readingInputFromFile"));
         // check if need to read from STDIN
         if path == "-" {
-            info!("{}", t!("util.readingInputFromStdin"));
-            let mut stdin = Vec::<u8>::new();
-            match std::io::stdin().read_to_end(&mut stdin) {
-                Ok(_) => {
-                    match String::from_utf8(stdin) {
-                        Ok(input) => {
-                            input
-                        },
-                        Err(err) => {
-                            error!("{}: {err}", t!("util.invalidUtf8"));
-                            exit(EXIT_INVALID_INPUT);
-                        }
-                    }
-                },
-                Err(err) => {
-                    error!("{}: {err}", t!("util.failedToReadStdin"));
-                    exit(EXIT_INVALID_INPUT);
-                }
-            }
+            read_stdin()
         } else {
             // see if an extension should handle this file
             let mut discovery = Discovery::new();
@@ -489,6 +482,84 @@ pub fn get_input(input: Option<&String>, file: Option<&String>) -> String {
     }
 }
 
+fn read_stdin() -> String {
+    info!("{}", t!("util.readingInputFromStdin"));
+    let mut stdin = Vec::<u8>::new();
+    match std::io::stdin().read_to_end(&mut stdin) {
+        Ok(_) => {
+            match String::from_utf8(stdin) {
+                Ok(input) => {
+                    input
+                },
+                Err(err) => {
+                    error!("{}: {err}", t!("util.invalidUtf8"));
+                    exit(EXIT_INVALID_INPUT);
+                }
+            }
+        },
+        Err(err) => {
+            error!("{}: {err}", t!("util.failedToReadStdin"));
+            exit(EXIT_INVALID_INPUT);
+        }
+    }
+}
+
+/// Read JSON Lines input with one instance per line.
+///
+/// # Arguments
+///
+/// * `input` - The JSON Lines passed on the command line
+/// * `file` - The file to read the JSON Lines from, or `-` for STDIN
+///
+/// # Returns
+///
+/// The JSON of each instance, empty lines are skipped.
+pub fn get_input_lines(input: Option<&String>, file: Option<&String>) -> Vec<String> {
+    let value = if let Some(input) = input {
+        debug!("{}", t!("util.readingInput"));
+        input.clone()
+    } else if let Some(path) = file {
+        debug!("{} {path}", t!("util.readingInputFromFile"));
+        if path == "-" {
+            read_stdin()
+        } else {
+            match std::fs::read_to_string(path) {
+                Ok(input) => input.trim_start_matches('\u{feff}').to_string(),
+                Err(err) => {
+                    error!("{}: {err}", t!("util.failedToReadFile"));
+                    exit(EXIT_INVALID_INPUT);
+                }
+            }
+        }
+    } else {
+        read_stdin()
+    };
+
+    let mut lines = Vec::new();
+    for (number, line) in value.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<serde_json::Value>(line) {
+            Ok(serde_json::Value::Object(_)) => lines.push(line.to_string()),
+            Ok(_) => {
+                error!("{}", t!("util.inputLineNotObject", line = number + 1));
+                exit(EXIT_INVALID_INPUT);
+            },
+            Err(err) => {
+                error!("{}", t!("util.invalidInputLine", line = number + 1, error = err));
+                exit(EXIT_INVALID_INPUT);
+            }
+        }
+    }
+    if lines.is_empty() {
+        error!("{}", t!("util.emptyInput"));
+        exit(EXIT_INVALID_INPUT);
+    }
+    lines
+}
+
 /// Sets `DSC_CONFIG_ROOT` env var and makes path absolute.
 ///
 /// # Arguments
@@ -666,3 +737,161 @@ pub fn merge_parameters(file_params: &str, inline_params: &str) -> Result<String
     let merged = Value::Object(file_map);
     Ok(serde_json::to_string(&merged)?)
 }
+
+/// Where the effective value of a parameter came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParameterSource {
+    /// The value was read from a parameters file
+    File(String),
+    /// The value was read from an environment variable
+    Environment(String),
+    /// The value was passed inline with `--parameters`
+    Inline,
+    /// The value is the default defined by the configuration document
+    Default,
+}
+
+impl Display for ParameterSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParameterSource::File(path) => write!(f, "{}", t!("util.parameterSourceFile", path = path)),
+            ParameterSource::Environment(name) => write!(f, "{}", t!("util.parameterSourceEnvironment", name = name)),
+            ParameterSource::Inline => write!(f, "{}", t!("util.parameterSourceInline")),
+            ParameterSource::Default => write!(f, "{}", t!("util.parameterSourceDefault")),
+        }
+    }
+}
+
+/// Options controlling how parameters from the command line are layered.
+#[derive(Debug, Default)]
+pub struct ParameterOptions {
+    /// Prefix of environment variables to map to parameters
+    pub env_prefix: Option<String>,
+    /// Print the effective parameters instead of running the operation
+    pub show_parameters: bool,
+    /// The source of each parameter provided by files or inline
+    pub sources: HashMap<String, ParameterSource>,
+}
+
+/// Recursively merge `overlay` into `base`.  Objects are merged key by key, any other value
+/// in `overlay` replaces the value in `base`.
+fn deep_merge(base: &mut serde_json::Value, overlay: serde_json::Value) {
+    match (base, overlay) {
+        (serde_json::Value::Object(base_map), serde_json::Value::Object(overlay_map)) => {
+            for (key, overlay_value) in overlay_map {
+                match base_map.get_mut(&key) {
+                    Some(base_value) => deep_merge(base_value, overlay_value),
+                    None => {
+                        base_map.insert(key, overlay_value);
+                    }
+                }
+            }
+        },
+        (base, overlay) => *base = overlay,
+    }
+}
+
+/// Deep merge a parameters file on top of previously merged parameter files.
+/// Unlike `merge_parameters`, object values of a parameter are merged recursively so a later
+/// file only needs to specify the properties it changes.
+///
+/// # Arguments
+///
+/// * `base_params` - Parameters merged so far (JSON or YAML format)
+/// * `overlay_params` - Parameters from the next file (JSON or YAML format) that take precedence
+///
+/// # Returns
+///
+/// * `Result<String, DscError>` - Merged parameters as JSON string
+///
+/// # Errors
+///
+/// This function will return an error if either parameter set cannot be parsed as a JSON or YAML object
+pub fn deep_merge_parameters(base_params: &str, overlay_params: &str) -> Result<String, DscError> {
+    let mut base = serde_json::Value::Object(params_to_map(base_params, "FileParameters")?);
+    let overlay = serde_json::Value::Object(params_to_map(overlay_params, "FileParameters")?);
+    deep_merge(&mut base, overlay);
+    Ok(serde_json::to_string(&base)?)
+}
+
+/// Record the source of each parameter defined in parameter input.
+///
+/// # Arguments
+///
+/// * `params` - Parameter input (JSON or YAML format)
+/// * `source` - Where the parameter input came from
+/// * `sources` - The map of parameter names to sources to update
+///
+/// # Errors
+///
+/// This function will return an error if the input cannot be parsed as a JSON or YAML object
+pub fn add_parameter_sources(params: &str, source: &ParameterSource, sources: &mut HashMap<String, ParameterSource>) -> Result<(), DscError> {
+    let map = params_to_map(params, "FileParameters")?;
+    if let Some(serde_json::Value::Object(parameters)) = map.get("parameters") {
+        for name in parameters.keys() {
+            sources.insert(name.clone(), source.clone());
+        }
+    }
+    Ok(())
+}
+
+/// Convert the string value of an environment variable to the type of the parameter.
+/// The value is parsed as JSON or YAML, falling back to the string itself, and must then match
+/// the parameter type. Values of string parameters are always used as-is.
+fn convert_env_parameter(name: &str, value: &str, parameter_type: &DataType) -> Result<serde_json::Value, DscError> {
+    if matches!(parameter_type, DataType::String | DataType::SecureString) {
+        return Ok(serde_json::Value::String(value.to_string()));
+    }
+
+    let converted = serde_json::from_str::<serde_json::Value>(value)
+        .or_else(|_| serde_yaml::from_str::<serde_json::Value>(value))
+        .unwrap_or_else(|_| serde_json::Value::String(value.to_string()));
+    let is_valid = match parameter_type {
+        DataType::Int => converted.is_i64(),
+        DataType::Number => converted.is_number(),
+        DataType::Bool => converted.is_boolean(),
+        DataType::Array => converted.is_array(),
+        DataType::Object | DataType::SecureObject => converted.is_object(),
+        DataType::String | DataType::SecureString => true,
+    };
+
+    if is_valid {
+        Ok(converted)
+    } else {
+        Err(DscError::Parser(t!("util.invalidEnvParameter", name = name, parameter_type = parameter_type).to_string()))
+    }
+}
+
+/// Map environment variables starting with a prefix to parameters defined by a configuration.
+/// The remainder of the variable name is matched to a parameter name case-insensitively
+/// ignoring underscores, so `DSC_PARAM_WEB_PORT` maps to the parameter `webPort`.
+///
+/// # Arguments
+///
+/// * `prefix` - The prefix of the environment variables to map
+/// * `parameters` - The parameters defined by the configuration
+///
+/// # Returns
+///
+/// * `Result<Vec<(String, String, serde_json::Value)>, DscError>` - The parameter name, environment variable name, and typed value of each match
+///
+/// # Errors
+///
+/// This function will return an error if a value can't be converted to the type of the parameter
+pub fn get_env_parameters(prefix: &str, parameters: &HashMap<String, Parameter>) -> Result<Vec<(String, String, serde_json::Value)>, DscError> {
+    let normalize = |name: &str| name.replace('_', "").to_lowercase();
+    let mut env_parameters = Vec::new();
+    let mut variables: Vec<(String, String)> = env::vars().filter(|(name, _)| name.starts_with(prefix)).collect();
+    variables.sort();
+    for (variable, value) in variables {
+        let key = normalize(&variable[prefix.len()..]);
+        let Some((name, parameter)) = parameters.iter().find(|(name, _)| normalize(name) == key) else {
+            warn!("{}", t!("util.envParameterNotDefined", variable = variable));
+            continue;
+        };
+        debug!("{}", t!("util.mappingEnvParameter", variable = variable, name = name));
+        let value = convert_env_parameter(&variable, &value, &parameter.parameter_type)?;
+        env_parameters.push((name.clone(), variable, value));
+    }
+    Ok(env_parameters)
+}
@@ -148,6 +148,62 @@ Describe 'dsc config get tests' {
         $InstanceTwoFound | Should -BeTrue
     }
 
+    It 'json progress includes progress reported by the resource' {
+        $config_yaml = @'
+            $schema: https://aka.ms/dsc/schemas/v3/bundled/config/document.json
+            resources:
+            - name: Installer
+              type: Test/Progress
+              properties:
+                steps: 4
+'@
+        $null = dsc --progress-format json config get -i $config_yaml 2> $TestDrive/ErrorStream.txt
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path $TestDrive/ErrorStream.txt)
+        $subProgress = Get-Content $TestDrive/ErrorStream.txt | ForEach-Object { $_ | ConvertFrom-Json } | Where-Object { $null -ne $_.subProgress }
+        $subProgress.Count | Should -Be 4
+        $subProgress[0].resourceName | Should -BeExactly 'Installer'
+        $subProgress[0].resourceType | Should -BeExactly 'Test/Progress'
+        $subProgress.subProgress.percent | Should -Be @(25, 50, 75, 100)
+        $subProgress[3].subProgress.activity | Should -BeExactly 'Step 4'
+    }
+
+    It 'structured error from resource is included in progress and results' {
+        $config_yaml = @'
+            $schema: https://aka.ms/dsc/schemas/v3/bundled/config/document.json
+            resources:
+            - name: Echo 1
+              type: Microsoft.DSC.Debug/Echo
+              properties:
+                output: hello
+            - name: ErrorTest
+              type: Test/ResourceError
+              properties:
+                code: QuotaExceeded
+                message: The disk quota was exceeded
+                target: size
+                retryable: true
+'@
+        $out = dsc --progress-format json config get -i $config_yaml 2> $TestDrive/ErrorStream.txt | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 2 -Because (Get-Content -Raw -Path $TestDrive/ErrorStream.txt)
+        $out.hadErrors | Should -BeTrue
+        $out.results.Count | Should -Be 1
+        $out.results[0].name | Should -BeExactly 'Echo 1'
+        $out.messages.Count | Should -Be 1
+        $out.messages[0].name | Should -BeExactly 'ErrorTest'
+        $out.messages[0].type | Should -BeExactly 'Test/ResourceError'
+        $out.messages[0].level | Should -BeExactly 'error'
+        $out.messages[0].error.code | Should -BeExactly 'QuotaExceeded'
+        $out.messages[0].error.target | Should -BeExactly 'size'
+        $out.messages[0].error.retryable | Should -BeTrue
+        $out.messages[0].message | Should -Not -Match 'Placeholder from manifest'
+        $failure = Get-Content $TestDrive/ErrorStream.txt | Where-Object { $_.StartsWith('{') } | ForEach-Object { $_ | ConvertFrom-Json } | Where-Object { $null -ne $_.failure }
+        $failure.resourceName | Should -BeExactly 'ErrorTest'
+        $failure.failure.exitCode | Should -Be 2
+        $failure.failure.message | Should -BeExactly 'The disk quota was exceeded'
+        $failure.failure.error.code | Should -BeExactly 'QuotaExceeded'
+        (Get-Content -Raw -Path $TestDrive/ErrorStream.txt) | Should -Match 'QuotaExceeded: The disk quota was exceeded'
+    }
+
     It 'contentVersion is ignored' {
         $config_yaml = @"
             `$schema: https://aka.ms/dsc/schemas/v3/bundled/config/document.json
@@ -0,0 +1,129 @@
+# Copyright (c) Microsoft Corporation.
+# Licensed under the MIT License.
+
+Describe 'Configuration lock file' {
+    BeforeAll {
+        $script:configPath = Join-Path $TestDrive 'echo.dsc.yaml'
+        $script:lockPath = Join-Path $TestDrive 'echo.dsc.lock.json'
+        $script:config = @'
+            $schema: https://aka.ms/dsc/schemas/v3/bundled/config/document.json
+            resources:
+            - name: Echo
+              type: Microsoft.DSC.Debug/Echo
+              properties:
+                output: hello
+'@
+    }
+
+    BeforeEach {
+        Set-Content -Path $script:configPath -Value $script:config
+        Remove-Item -Path $script:lockPath -ErrorAction Ignore
+    }
+
+    It 'lock writes the exact resource versions and manifest digests' {
+        $out = dsc config lock -f $script:configPath 2> "$TestDrive/error.log" | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        Test-Path $script:lockPath | Should -BeTrue
+
+        $lock = Get-Content -Raw -Path $script:lockPath | ConvertFrom-Json
+        $lock.lockVersion | Should -Be 1
+        $lock.resources.Count | Should -Be 1
+        $lock.resources[0].type | Should -BeExactly 'Microsoft.DSC.Debug/Echo'
+        $lock.resources[0].version | Should -BeExactly '1.0.0'
+        $lock.resources[0].manifestPath | Should -BeLike '*echo.dsc.resource.json'
+        $lock.resources[0].manifestSha256 | Should -BeExactly (Get-FileHash -Algorithm SHA256 -Path $lock.resources[0].manifestPath).Hash.ToLowerInvariant()
+        $out.resources[0].type | Should -BeExactly 'Microsoft.DSC.Debug/Echo'
+    }
+
+    It 'lock includes the resources nested in a group' {
+        $config = @'
+            $schema: https://aka.ms/dsc/schemas/v3/bundled/config/document.json
+            resources:
+            - name: Group
+              type: Microsoft.DSC/Group
+              properties:
+                $schema: https://aka.ms/dsc/schemas/v3/bundled/config/document.json
+                resources:
+                - name: Nested
+                  type: Microsoft.DSC.Debug/Echo
+                  properties:
+                    output: nested
+'@
+        Set-Content -Path $script:configPath -Value $config
+        $null = dsc config lock -f $script:configPath 2> "$TestDrive/error.log"
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $lock = Get-Content -Raw -Path $script:lockPath | ConvertFrom-Json
+        $lock.resources.type | Should -Contain 'Microsoft.DSC/Group'
+        $lock.resources.type | Should -Contain 'Microsoft.DSC.Debug/Echo'
+
+        $out = dsc config get -f $script:configPath 2> "$TestDrive/error.log" | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $out.results[0].result[0].result.actualState.output | Should -BeExactly 'nested'
+
+        $lock.resources = @($lock.resources | Where-Object { $_.type -ne 'Microsoft.DSC.Debug/Echo' })
+        $lock | ConvertTo-Json -Depth 10 | Set-Content -Path $script:lockPath
+        $null = dsc config get -f $script:configPath 2> "$TestDrive/error.log"
+        $LASTEXITCODE | Should -Be 2
+        Get-Content -Raw -Path "$TestDrive/error.log" | Should -BeLike "*Microsoft.DSC.Debug/Echo*isn't in the lock file*"
+    }
+
+    It 'config <operation> succeeds when the lock is satisfied' -TestCases @(
+        @{ operation = 'get' }
+        @{ operation = 'test' }
+        @{ operation = 'set' }
+    ) {
+        param($operation)
+
+        $null = dsc config lock -f $script:configPath 2> "$TestDrive/error.log"
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+
+        $out = dsc config $operation -f $script:configPath 2> "$TestDrive/error.log" | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $out.results.Count | Should -Be 1
+    }
+
+    It 'config get fails when <name>' -TestCases @(
+        @{ name = 'the manifest digest differs'; property = 'manifestSha256'; value = ('0' * 64); message = '*SHA-256 digest*' }
+        @{ name = 'the locked version is not found'; property = 'version'; value = '9.9.9'; message = '*locked version 9.9.9 not found*' }
+    ) {
+        param($property, $value, $message)
+
+        $null = dsc config lock -f $script:configPath 2> "$TestDrive/error.log"
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $lock = Get-Content -Raw -Path $script:lockPath | ConvertFrom-Json
+        $lock.resources[0].$property = $value
+        $lock | ConvertTo-Json -Depth 10 | Set-Content -Path $script:lockPath
+
+        $null = dsc config get -f $script:configPath 2> "$TestDrive/error.log"
+        $LASTEXITCODE | Should -Be 2
+        $log = Get-Content -Raw -Path "$TestDrive/error.log"
+        $log | Should -BeLike "*can't be satisfied*"
+        $log | Should -BeLike $message
+    }
+
+    It 'config get fails when a resource is not in the lock file' {
+        $null = dsc config lock -f $script:configPath 2> "$TestDrive/error.log"
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $config = $script:config + @'
+
+            - name: Other
+              type: Microsoft.DSC.Debug/Echo
+              properties:
+                output: world
+            - name: Unlocked
+              type: Test/Get
+'@
+        Set-Content -Path $script:configPath -Value $config
+
+        $null = dsc config get -f $script:configPath 2> "$TestDrive/error.log"
+        $LASTEXITCODE | Should -Be 2
+        Get-Content -Raw -Path "$TestDrive/error.log" | Should -BeLike "*Test/Get*isn't in the lock file*"
+    }
+
+    It 'configuration from stdin is not locked' {
+        $out = $script:config | dsc config lock -f - 2> "$TestDrive/error.log" | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $out.resources[0].type | Should -BeExactly 'Microsoft.DSC.Debug/Echo'
+        Get-ChildItem -Path $TestDrive -Filter '*.dsc.lock.json' | Should -BeNullOrEmpty
+    }
+}
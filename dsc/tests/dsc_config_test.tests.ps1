@@ -62,6 +62,69 @@ Describe 'dsc config test tests' {
         }
     }
 
+    It 'Reasons returned by the resource are included in the result' {
+        $configYaml = @'
+  $schema: https://aka.ms/dsc/schemas/v3/bundled/config/document.json
+  resources:
+    - name: Test
+      type: Test/InDesiredState
+      properties:
+        _inDesiredState: false
+        valueOne: 1
+        valueTwo: 2
+'@
+
+        $out = dsc config test -i $configYaml | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0
+        $out.results[0].result.inDesiredState | Should -BeFalse
+        $out.results[0].result.reasons.Count | Should -Be 1
+        $out.results[0].result.reasons[0].code | Should -BeExactly 'Test:InDesiredState:NotCompliant'
+        $out.results[0].result.reasons[0].phrase | Should -BeExactly 'The resource is not in the desired state'
+        $out.results[0].result.reasons[0].property | Should -BeExactly '/_inDesiredState'
+    }
+
+    It 'Reasons are not generated when the resource reports it is in the desired state' {
+        $configYaml = @'
+  $schema: https://aka.ms/dsc/schemas/v3/bundled/config/document.json
+  resources:
+    - name: Test
+      type: Test/InDesiredState
+      properties:
+        _inDesiredState: true
+        valueOne: 3
+        valueTwo: 4
+'@
+
+        $out = dsc config test -i $configYaml | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0
+        $out.results[0].result.inDesiredState | Should -BeTrue
+        $out.results[0].result.differingProperties | Should -Be @('valueOne', 'valueTwo')
+        $out.results[0].result.reasons | Should -BeNullOrEmpty
+    }
+
+    It 'Synthetic test generates reasons for nested properties' {
+        $configYaml = @'
+  $schema: https://aka.ms/dsc/schemas/v3/bundled/config/document.json
+  resources:
+    - name: Test
+      type: Test/Environment
+      properties:
+        names:
+          - DSC_TEST_STATIC
+        variables:
+          DSC_TEST_STATIC: expected
+'@
+
+        $out = dsc config test -i $configYaml | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0
+        $out.results[0].result.inDesiredState | Should -BeFalse
+        $out.results[0].result.differingProperties | Should -Be @('variables')
+        $out.results[0].result.reasons.Count | Should -Be 1
+        $out.results[0].result.reasons[0].code | Should -BeExactly 'valueMismatch'
+        $out.results[0].result.reasons[0].phrase | Should -BeExactly "Expected 'expected', actual 'static'"
+        $out.results[0].result.reasons[0].property | Should -BeExactly '/variables/DSC_TEST_STATIC'
+    }
+
     It 'Duplicate resource names are not allowed' {
         $configYaml = @'
   $schema: https://aka.ms/dsc/schemas/v3/bundled/config/document.json
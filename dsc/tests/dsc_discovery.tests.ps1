@@ -480,4 +480,53 @@ Describe 'tests for resource discovery' {
             $env:DSC_RESTRICTED_PATH = $oldRestrictedPath
         }
     }
+
+    It 'Resource cache rebuild and clear report the cache contents' {
+        $oldRestrictedPath = $env:DSC_RESTRICTED_PATH
+        try {
+            $env:DSC_RESTRICTED_PATH = Split-Path -Path (Get-Command dsc -ErrorAction Stop).Source -Parent
+            $out = dsc resource cache rebuild 2> "$testdrive/error.txt" | ConvertFrom-Json
+            $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$testdrive/error.txt")
+            $out.manifests | Should -BeGreaterThan 0
+            $out.directories | Should -BeGreaterThan 0
+            $out.path | Should -Exist
+            $out = dsc resource cache clear 2> "$testdrive/error.txt" | ConvertFrom-Json
+            $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$testdrive/error.txt")
+            $out.manifests | Should -Be 0
+            $out.directories | Should -Be 0
+            $out.path | Should -Not -Exist
+        }
+        finally {
+            $env:DSC_RESTRICTED_PATH = $oldRestrictedPath
+        }
+    }
+
+    It 'Resource added after the cache is built is discovered' {
+        $resourceJson = @'
+        {
+            "$schema": "https://aka.ms/dsc/schemas/v3/bundled/resource/manifest.json",
+            "type": "DSC/TestCacheResource",
+            "version": "0.1.0",
+            "get": {
+              "executable": "dsc"
+            }
+          }
+'@
+        $oldRestrictedPath = $env:DSC_RESTRICTED_PATH
+        try {
+            $env:DSC_RESTRICTED_PATH = $testdrive
+            $resources = dsc resource list 'DSC/TestCacheResource' | ConvertFrom-Json
+            $resources.Count | Should -Be 0
+            Set-Content -Path "$testdrive/test.dsc.resource.json" -Value $resourceJson
+            $resources = dsc resource list 'DSC/TestCacheResource' | ConvertFrom-Json
+            $resources.Count | Should -Be 1
+            $resources.type | Should -BeExactly 'DSC/TestCacheResource'
+            Set-Content -Path "$testdrive/test.dsc.resource.json" -Value $resourceJson.Replace('0.1.0', '0.2.0')
+            $resources = dsc resource list 'DSC/TestCacheResource' | ConvertFrom-Json
+            $resources.version | Should -BeExactly '0.2.0'
+        }
+        finally {
+            $env:DSC_RESTRICTED_PATH = $oldRestrictedPath
+        }
+    }
 }
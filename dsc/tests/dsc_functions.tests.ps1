@@ -1715,6 +1715,30 @@ Describe 'tests for function expressions' {
     }
   }
 
+  It 'semver functions work for: <expression>' -TestCases @(
+    @{ expression = "[semverCompare('1.2.3', '1.10.0')]"; expected = -1 }
+    @{ expression = "[semverCompare('2.0.0', '2.0.0-rc.1')]"; expected = 1 }
+    @{ expression = "[semverSatisfies('2.10.0', '>=2.1')]"; expected = $true }
+    @{ expression = "[semverSatisfies('2.0.5', '>=2.1')]"; expected = $false }
+    @{ expression = "[semverMajor('3.2.1-rc.1')]"; expected = 3 }
+    @{ expression = "[semverMinor('3.2.1-rc.1')]"; expected = 2 }
+    @{ expression = "[semverPatch('3.2.1-rc.1')]"; expected = 1 }
+  ) {
+    param($expression, $expected)
+
+    $config_yaml = @"
+            `$schema: https://aka.ms/dsc/schemas/v3/bundled/config/document.json
+            resources:
+            - name: Echo
+              type: Microsoft.DSC.Debug/Echo
+              properties:
+                output: "$expression"
+"@
+    $out = dsc config get -i $config_yaml 2>$TestDrive/error.log | ConvertFrom-Json
+    $LASTEXITCODE | Should -Be 0 -Because (Get-Content $TestDrive/error.log -Raw)
+    $out.results[0].result.actualState.output | Should -Be $expected
+  }
+
   It 'dataUri function works for: <expression>' -TestCases @(
     @{ expression = "[dataUri('Hello')]"; expected = 'data:application/json;base64,SGVsbG8=' }
     @{ expression = "[dataUri('')]"; expected = 'data:application/json;base64,' }
@@ -37,6 +37,7 @@ Describe 'Parameters tests' {
     It 'Input is <type>' -TestCases @(
         @{ type = 'string'; value = 'hello' }
         @{ type = 'int'; value = 42}
+        @{ type = 'number'; value = 3.5}
         @{ type = 'bool'; value = $true}
         @{ type = 'array'; value = @('hello', 'world')}
     ) {
@@ -63,6 +64,7 @@ Describe 'Parameters tests' {
     It 'Input is incorrect type <type>' -TestCases @(
         @{ type = 'string'; value = 42 }
         @{ type = 'int'; value = 'hello' }
+        @{ type = 'number'; value = 'hello' }
         @{ type = 'bool'; value = 'hello' }
         @{ type = 'array'; value = 'hello' }
     ) {
@@ -141,6 +143,96 @@ Describe 'Parameters tests' {
         $LASTEXITCODE | Should -Be 4
     }
 
+    It 'Number value <value> with fractional limits <min> and <max> is in range: <inRange>' -TestCases @(
+        @{ value = 0.5; min = 0.25; max = 0.75; inRange = $true }
+        @{ value = 0.2; min = 0.25; max = 0.75; inRange = $false }
+        @{ value = 0.8; min = 0.25; max = 0.75; inRange = $false }
+    ) {
+        param($value, $min, $max, $inRange)
+
+        $config_yaml = @"
+            `$schema: https://aka.ms/dsc/schemas/v3/bundled/config/document.json
+            parameters:
+              param1:
+                type: number
+                minValue: $min
+                maxValue: $max
+            resources:
+            - name: Echo
+              type: Microsoft.DSC.Debug/Echo
+              properties:
+                output: '[parameters(''param1'')]'
+"@
+        $params_json = @{ parameters = @{ param1 = $value }} | ConvertTo-Json
+
+        $out = $config_yaml | dsc config -p $params_json get -f - 2> $TestDrive/error.log | ConvertFrom-Json
+        if ($inRange) {
+            $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw $TestDrive/error.log)
+            $out.results[0].result.actualState.output | Should -Be $value
+        }
+        else {
+            $LASTEXITCODE | Should -Be 4
+        }
+    }
+
+    It 'Input does not match pattern constraint' {
+        $config_yaml = @"
+            `$schema: https://aka.ms/dsc/schemas/v3/bundled/config/document.json
+            parameters:
+              param1:
+                type: string
+                pattern: '^[a-z]+-\d+$'
+            resources:
+            - name: Echo
+              type: Microsoft.DSC.Debug/Echo
+              properties:
+                output: '[parameters(''param1'')]'
+"@
+        $params_json = @{ parameters = @{ param1 = 'web-01' }} | ConvertTo-Json
+        $out = $config_yaml | dsc config -p $params_json get -f - | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0
+        $out.results[0].result.actualState.output | Should -BeExactly 'web-01'
+
+        $params_json = @{ parameters = @{ param1 = 'WEB' }} | ConvertTo-Json
+        $testError = & {$config_yaml | dsc config -p $params_json get -f - 2>&1} | Out-String
+        $testError | Should -BeLike "*does not match pattern*"
+        $LASTEXITCODE | Should -Be 4
+    }
+
+    It 'Object input is validated against the parameter schema' {
+        $config_yaml = @"
+            `$schema: https://aka.ms/dsc/schemas/v3/bundled/config/document.json
+            parameters:
+              param1:
+                type: object
+                schema:
+                  type: object
+                  required: [name, ports]
+                  properties:
+                    name:
+                      type: string
+                    ports:
+                      type: array
+                      items:
+                        type: integer
+                        maximum: 65535
+            resources:
+            - name: Echo
+              type: Microsoft.DSC.Debug/Echo
+              properties:
+                output: '[parameters(''param1'')]'
+"@
+        $params_json = @{ parameters = @{ param1 = @{ name = 'web'; ports = @(80, 443) } }} | ConvertTo-Json -Depth 5
+        $out = $config_yaml | dsc config -p $params_json get -f - | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0
+        $out.results[0].result.actualState.output.ports | Should -Be @(80, 443)
+
+        $params_json = @{ parameters = @{ param1 = @{ name = 'web'; ports = @(80, 70000) } }} | ConvertTo-Json -Depth 5
+        $testError = & {$config_yaml | dsc config -p $params_json get -f - 2>&1} | Out-String
+        $testError | Should -BeLike "*'/ports/1'*"
+        $LASTEXITCODE | Should -Be 4
+    }
+
     It 'Input is not in the allowed value list for <type>' -TestCases @(
         @{ type = 'string'; value = 'hello'; allowed = @('world', 'planet') }
         @{ type = 'int'; value = 42; allowed = @(43, 44) }
@@ -902,4 +994,181 @@ parameters:
         $LASTEXITCODE | Should -Be 4
         $out | Should -Match 'Failed to merge parameters'
     }
-}
\ No newline at end of file
+
+    It 'Multiple parameters files are deep merged in order' {
+        $config_yaml = @"
+            `$schema: https://aka.ms/dsc/schemas/v3/bundled/config/document.json
+            parameters:
+              environment:
+                type: string
+              settings:
+                type: object
+            resources:
+            - name: Echo
+              type: Microsoft.DSC.Debug/Echo
+              properties:
+                output: "[concat(parameters('environment'), '-', parameters('settings').size, '-', string(parameters('settings').replicas))]"
+"@
+        $base_yaml = @"
+            parameters:
+              environment: dev
+              settings:
+                size: small
+                replicas: 1
+"@
+        $prod_yaml = @"
+            parameters:
+              environment: prod
+              settings:
+                replicas: 3
+"@
+        Set-Content -Path "$TestDrive/base.yaml" -Value $base_yaml
+        Set-Content -Path "$TestDrive/prod.yaml" -Value $prod_yaml
+
+        $out = dsc config -f "$TestDrive/base.yaml" -f "$TestDrive/prod.yaml" get -i $config_yaml | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0
+        $out.results[0].result.actualState.output | Should -BeExactly 'prod-small-3'
+    }
+
+    It 'Parameters can be read from environment variables with a prefix' {
+        $config_yaml = @"
+            `$schema: https://aka.ms/dsc/schemas/v3/bundled/config/document.json
+            parameters:
+              webPort:
+                type: int
+              enabled:
+                type: bool
+              message:
+                type: string
+            resources:
+            - name: Echo
+              type: Microsoft.DSC.Debug/Echo
+              properties:
+                output: "[concat(parameters('message'), '-', string(add(parameters('webPort'), 1)), '-', string(parameters('enabled')))]"
+"@
+        $inlineParams = @{ parameters = @{ message = 'inline' } } | ConvertTo-Json
+        try {
+            $env:DSC_PARAM_WEB_PORT = '8080'
+            $env:DSC_PARAM_ENABLED = 'true'
+            $env:DSC_PARAM_MESSAGE = 'env'
+            $out = dsc config --parameters-from-env DSC_PARAM_ --parameters $inlineParams get -i $config_yaml | ConvertFrom-Json
+            $LASTEXITCODE | Should -Be 0
+            $out.results[0].result.actualState.output | Should -BeExactly 'inline-8081-true'
+        }
+        finally {
+            $env:DSC_PARAM_WEB_PORT = $null
+            $env:DSC_PARAM_ENABLED = $null
+            $env:DSC_PARAM_MESSAGE = $null
+        }
+    }
+
+    It 'Environment variables can use YAML values and combine with complex inline parameters' {
+        $config_yaml = @"
+            `$schema: https://aka.ms/dsc/schemas/v3/bundled/config/document.json
+            parameters:
+              settings:
+                type: object
+              message:
+                type: string
+            resources:
+            - name: Echo
+              type: Microsoft.DSC.Debug/Echo
+              properties:
+                output: "[concat(parameters('message'), '-', parameters('settings').name)]"
+"@
+        $inlineParams = @{ parameters = @{ message = @{ value = 'inline' } } } | ConvertTo-Json -Depth 3
+        try {
+            $env:DSC_PARAM_SETTINGS = '{name: yaml}'
+            $out = dsc config --parameters-from-env DSC_PARAM_ --parameters $inlineParams get -i $config_yaml 2> $TestDrive/error.log | ConvertFrom-Json
+            $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw $TestDrive/error.log)
+            $out.results[0].result.actualState.output | Should -BeExactly 'inline-yaml'
+        }
+        finally {
+            $env:DSC_PARAM_SETTINGS = $null
+        }
+    }
+
+    It 'Environment variable that is not the parameter type returns error' {
+        $config_yaml = @"
+            `$schema: https://aka.ms/dsc/schemas/v3/bundled/config/document.json
+            parameters:
+              webPort:
+                type: int
+            resources: []
+"@
+        try {
+            $env:DSC_PARAM_WEB_PORT = 'abc'
+            $out = dsc config --parameters-from-env DSC_PARAM_ get -i $config_yaml 2> $TestDrive/error.log
+            $LASTEXITCODE | Should -Be 4
+            $out | Should -BeNullOrEmpty
+            (Get-Content -Path $TestDrive/error.log -Raw) | Should -BeLike "*DSC_PARAM_WEB_PORT*int*"
+        }
+        finally {
+            $env:DSC_PARAM_WEB_PORT = $null
+        }
+    }
+
+    It 'Show parameters returns effective values with source and secure values redacted' {
+        $config_yaml = @"
+            `$schema: https://aka.ms/dsc/schemas/v3/bundled/config/document.json
+            parameters:
+              environment:
+                type: string
+              password:
+                type: secureString
+              region:
+                type: string
+                defaultValue: westus
+              replicas:
+                type: int
+            resources: []
+"@
+        Set-Content -Path "$TestDrive/base.yaml" -Value "parameters: { environment: dev, password: hidden }"
+        $inlineParams = @{ parameters = @{ environment = 'prod' } } | ConvertTo-Json
+        try {
+            $env:DSC_PARAM_REPLICAS = '2'
+            $out = dsc config -f "$TestDrive/base.yaml" --parameters $inlineParams --parameters-from-env DSC_PARAM_ --show-parameters get -i $config_yaml | ConvertFrom-Json
+            $LASTEXITCODE | Should -Be 0
+        }
+        finally {
+            $env:DSC_PARAM_REPLICAS = $null
+        }
+        $out.Count | Should -Be 4
+        $out[0].name | Should -BeExactly 'environment'
+        $out[0].value | Should -BeExactly 'prod'
+        $out[0].source | Should -BeExactly 'inline'
+        $out[1].name | Should -BeExactly 'password'
+        $out[1].value | Should -BeExactly '<secureValue>'
+        $out[1].source | Should -BeExactly "file '$TestDrive/base.yaml'"
+        $out[2].name | Should -BeExactly 'region'
+        $out[2].value | Should -BeExactly 'westus'
+        $out[2].source | Should -BeExactly 'default'
+        $out[3].name | Should -BeExactly 'replicas'
+        $out[3].value | Should -Be 2
+        $out[3].source | Should -BeExactly "environment variable 'DSC_PARAM_REPLICAS'"
+    }
+
+    It 'Values derived from secure parameters are redacted in output and traces' {
+        $config_yaml = @"
+            `$schema: https://aka.ms/dsc/schemas/v3/bundled/config/document.json
+            parameters:
+              password:
+                type: secureString
+            resources:
+            - name: Echo
+              type: Microsoft.DSC.Debug/Echo
+              properties:
+                output: "[concat('pw=', parameters('password'))]"
+            - name: Formatted
+              type: Microsoft.DSC.Debug/Echo
+              properties:
+                output: "[format('{0}-suffix', parameters('password'))]"
+"@
+        $params = @{ parameters = @{ password = 'mySecretValue' } } | ConvertTo-Json
+        $out = dsc -l trace --progress-format json config -p $params get -i $config_yaml 2> $TestDrive/error.log | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Path $TestDrive/error.log -Raw)
+        $out.results[0].result.actualState.output | Should -BeExactly '<secureValue>'
+        $out.results[1].result.actualState.output | Should -BeExactly '<secureValue>'
+        (Get-Content -Path $TestDrive/error.log -Raw) | Should -Not -Match 'mySecretValue'
+    }
+}
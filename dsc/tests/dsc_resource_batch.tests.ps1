@@ -0,0 +1,123 @@
+# Copyright (c) Microsoft Corporation.
+# Licensed under the MIT License.
+
+Describe 'Resource get, test, and set with json-lines input' {
+    BeforeAll {
+        $batchInput = @'
+{"name":"one","value":"1"}
+{"name":"two","value":"2"}
+
+{"name":"three"}
+'@
+        $echoInput = @'
+{"output":"one"}
+{"output":"two"}
+'@
+    }
+
+    It 'batch resource receives all instances for <operation> in one invocation' -TestCases @(
+        @{ operation = 'get' }
+        @{ operation = 'test' }
+        @{ operation = 'set' }
+    ) {
+        param($operation)
+
+        $out = $batchInput | dsc -l info resource $operation -r Test/Batch --input-format json-lines -f - 2> $TestDrive/tracing.txt
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw $TestDrive/tracing.txt)
+        $out.Count | Should -Be 3
+        $results = $out | ForEach-Object { $_ | ConvertFrom-Json }
+        switch ($operation) {
+            'get' {
+                $results[0].actualState.name | Should -BeExactly 'one'
+                $results[1].actualState.value | Should -BeExactly '2'
+                $results[2].actualState.name | Should -BeExactly 'three'
+            }
+            'test' {
+                $results[1].actualState.name | Should -BeExactly 'two'
+                $results.inDesiredState | Should -Not -Contain $false
+            }
+            'set' {
+                $results[0].afterState.name | Should -BeExactly 'one'
+                $results[2].changedProperties | Should -BeNullOrEmpty
+            }
+        }
+        $tracing = Get-Content -Raw $TestDrive/tracing.txt
+        $tracing | Should -BeLike "*Invoking batch $operation of 3 instances on 'Test/Batch'*"
+    }
+
+    It 'resource without batch capability is invoked once per instance for <operation>' -TestCases @(
+        @{ operation = 'get' }
+        @{ operation = 'test' }
+        @{ operation = 'set' }
+    ) {
+        param($operation)
+
+        $out = $echoInput | dsc -l info resource $operation -r Microsoft.DSC.Debug/Echo --input-format json-lines -f - 2> $TestDrive/tracing.txt
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw $TestDrive/tracing.txt)
+        $out.Count | Should -Be 2
+        $results = $out | ForEach-Object { $_ | ConvertFrom-Json }
+        if ($operation -eq 'set') {
+            $results[1].afterState.output | Should -BeExactly 'two'
+        } else {
+            $results[1].actualState.output | Should -BeExactly 'two'
+        }
+        (Get-Content -Raw $TestDrive/tracing.txt) | Should -Not -BeLike '*Invoking batch*'
+    }
+
+    It 'batch resource returning state and diff for <operation> writes two lines per instance' -TestCases @(
+        @{ operation = 'test' }
+        @{ operation = 'set' }
+    ) {
+        param($operation)
+
+        $out = $batchInput | dsc -l info resource $operation -r Test/BatchStateAndDiff --input-format json-lines -f - 2> $TestDrive/tracing.txt
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw $TestDrive/tracing.txt)
+        $out.Count | Should -Be 3
+        $results = $out | ForEach-Object { $_ | ConvertFrom-Json }
+        if ($operation -eq 'set') {
+            $results[1].afterState.name | Should -BeExactly 'two'
+        } else {
+            $results[1].actualState.name | Should -BeExactly 'two'
+            $results.inDesiredState | Should -Not -Contain $false
+        }
+        (Get-Content -Raw $TestDrive/tracing.txt) | Should -BeLike "*Invoking batch $operation of 3 instances on 'Test/BatchStateAndDiff'*"
+    }
+
+    It 'output format <format> is used for each result' -TestCases @(
+        @{ format = 'yaml' }
+        @{ format = 'pretty-json' }
+    ) {
+        param($format)
+
+        $out = $batchInput | dsc resource get -r Test/Batch --input-format json-lines -o $format -f - 2> $TestDrive/error.txt
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw $TestDrive/error.txt)
+        $text = $out -join "`n"
+        if ($format -eq 'yaml') {
+            ($out | Where-Object { $_ -eq '---' }).Count | Should -Be 2
+            $text | Should -BeLike '*actualState:*'
+        } else {
+            ($out | Where-Object { $_ -eq '{' }).Count | Should -Be 3
+        }
+    }
+
+    It 'json-array output format writes the actual states as one array' {
+        $out = $batchInput | dsc resource get -r Test/Batch --input-format json-lines -o json-array -f - 2> $TestDrive/error.txt
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw $TestDrive/error.txt)
+        $out.Count | Should -Be 1
+        $states = $out | ConvertFrom-Json
+        $states.Count | Should -Be 3
+        $states[2].name | Should -BeExactly 'three'
+    }
+
+    It 'batch resource is listed with the batch capability' {
+        $out = dsc resource list Test/Batch | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0
+        $out.capabilities | Should -Contain 'batch'
+    }
+
+    It 'line that is not a JSON object fails' {
+        $null = "{`"name`":`"one`"}`n[1,2]" | dsc resource get -r Test/Batch --input-format json-lines -f - 2> $TestDrive/error.txt
+        $LASTEXITCODE | Should -Be 4
+        (Get-Content -Raw $TestDrive/error.txt) | Should -BeLike '*line 2*'
+    }
+}
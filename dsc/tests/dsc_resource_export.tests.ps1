@@ -33,6 +33,26 @@ Describe 'Resource export tests' {
         $errorlog | Should -Match "Resource '$resource' does not support export filtering, the engine will filter the exported instances \(experimental feature\)"
     }
 
+    It 'json-lines writes each instance as a line' {
+        $lines = dsc resource export -r Test/Export -i '{"count": 1000}' -o json-lines 2>$TESTDRIVE/error.log
+        $errorlog = Get-Content "$TESTDRIVE/error.log" -Raw
+        $LASTEXITCODE | Should -Be 0 -Because $errorlog
+        $lines.Count | Should -Be 1000
+        $first = $lines[0] | ConvertFrom-Json
+        $first.type | Should -BeExactly 'Test/Export'
+        $first.name | Should -BeExactly 'TestName'
+        $first.properties.name | Should -BeExactly 'Instance0'
+        ($lines[999] | ConvertFrom-Json).properties.name | Should -BeExactly 'Instance999'
+    }
+
+    It 'json-lines applies engine filtering to each instance' {
+        $lines = dsc resource export -r Test/ExportSchemaNoFiltering -i '{ "name": "*e*" }' -o json-lines 2>$TESTDRIVE/error.log
+        $errorlog = Get-Content "$TESTDRIVE/error.log" -Raw
+        $LASTEXITCODE | Should -Be 0 -Because $errorlog
+        $names = $lines | ForEach-Object { ($_ | ConvertFrom-Json).properties.name }
+        $names | Should -Be @('Steve', 'Tess')
+    }
+
     It 'Engine filtering rejects input that is not an object or array of objects' {
         $resource = 'Test/ExportSchemaNoFiltering'
         $json = '5'
@@ -76,4 +76,22 @@ Describe 'resource get tests' {
         $LASTEXITCODE | Should -Be 0
         $out.actualState.version | Should -BeExactly '1.1.2'
     }
+
+    It 'manifest env, cwd, and envPassthrough are applied to the command' {
+        $oldSecret = $env:DSC_TEST_SECRET
+        try {
+            $env:DSC_TEST_SECRET = 'secret'
+            $json = '{"names":["DSC_TEST_STATIC","DSC_TEST_EXPRESSION","DSC_TEST_SECRET","PATH"]}'
+            $out = dsc resource get -r Test/Environment -i $json 2> $TestDrive/error.log | ConvertFrom-Json
+            $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw $TestDrive/error.log)
+        } finally {
+            $env:DSC_TEST_SECRET = $oldSecret
+        }
+        $out.actualState.variables.DSC_TEST_STATIC | Should -BeExactly 'static'
+        $out.actualState.variables.DSC_TEST_EXPRESSION | Should -BeExactly 'fromexpression'
+        $out.actualState.variables.DSC_TEST_SECRET | Should -BeNullOrEmpty
+        $out.actualState.variables.PATH | Should -Not -BeNullOrEmpty
+        $expectedCwd = Split-Path -Parent (Split-Path -Parent (Get-Command dsctest).Source)
+        $out.actualState.cwd | Should -BeExactly $expectedCwd
+    }
 }
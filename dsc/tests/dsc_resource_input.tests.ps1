@@ -119,4 +119,13 @@ Describe 'tests for resource input' {
         $result.$member.StringArray | Should -BeExactly 'foo,bar'
         $result.$member.NumberArray | Should -BeExactly '1,2,3'
     }
+
+    It 'Large input can be passed to the resource in a file' {
+        $value = 'a' * 100000
+        $out = @{ value = $value } | ConvertTo-Json -Compress | dsc resource get -r Test/InputFile -f - 2> $TestDrive/error.log | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw $TestDrive/error.log)
+        $out.actualState.value | Should -BeExactly $value
+        $out.actualState.path | Should -Not -BeNullOrEmpty
+        Test-Path $out.actualState.path | Should -Be $false
+    }
 }
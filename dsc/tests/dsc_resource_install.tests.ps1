@@ -0,0 +1,155 @@
+# Copyright (c) Microsoft Corporation.
+# Licensed under the MIT License.
+
+Describe 'Resource bundle install and uninstall' {
+    BeforeAll {
+        $oldInstallPath = $env:DSC_RESOURCE_INSTALL_PATH
+        $env:DSC_RESOURCE_INSTALL_PATH = Join-Path $TestDrive 'installed'
+
+        function New-BundleContent([string]$version, [string]$path) {
+            $null = New-Item -ItemType Directory -Path $path -Force
+            $manifest = [ordered]@{
+                '$schema' = 'https://aka.ms/dsc/schemas/v3/bundled/resource/manifest.json'
+                type = 'Test/BundledEcho'
+                version = $version
+                get = [ordered]@{
+                    executable = 'dscecho'
+                    args = @(
+                        @{ jsonInputArg = '--input'; mandatory = $true }
+                    )
+                }
+                schema = @{
+                    embedded = @{
+                        type = 'object'
+                        properties = @{ output = @{} }
+                    }
+                }
+            }
+            $manifest | ConvertTo-Json -Depth 10 | Set-Content -Path (Join-Path $path 'bundledecho.dsc.resource.json')
+        }
+
+        function New-ZipBundle([string]$version) {
+            $content = Join-Path $TestDrive "content-$version"
+            New-BundleContent -version $version -path $content
+            $bundle = Join-Path $TestDrive "bundledecho-$version.zip"
+            Compress-Archive -Path "$content/*" -DestinationPath $bundle -Force
+            $bundle
+        }
+    }
+
+    AfterAll {
+        $env:DSC_RESOURCE_INSTALL_PATH = $oldInstallPath
+    }
+
+    AfterEach {
+        Remove-Item -Recurse -Force -Path $env:DSC_RESOURCE_INSTALL_PATH -ErrorAction Ignore
+    }
+
+    It 'installs side-by-side versions and uninstalls them by version' {
+        foreach ($version in '1.0.0', '2.0.0') {
+            $out = dsc resource install (New-ZipBundle $version) 2> "$TestDrive/error.log" | ConvertFrom-Json
+            $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+            $out.resources[0].type | Should -BeExactly 'Test/BundledEcho'
+            $out.resources[0].version | Should -BeExactly $version
+            $out.directory | Should -BeLike "*$version"
+            $out.sha256 | Should -Match '^[0-9a-f]{64}$'
+        }
+
+        $out = dsc resource list Test/BundledEcho -o json 2> "$TestDrive/error.log" | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $out.Count | Should -Be 2
+
+        $out = dsc resource get -r Test/BundledEcho -v 1.0.0 --input '{"output":"hello"}' 2> "$TestDrive/error.log" | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $out.actualState.output | Should -BeExactly 'hello'
+
+        $out = dsc resource uninstall -r Test/BundledEcho -v 1.0.0 2> "$TestDrive/error.log" | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $out.resources[0].version | Should -BeExactly '1.0.0'
+        Test-Path $out.directory | Should -BeFalse
+
+        $out = dsc resource list Test/BundledEcho -o json 2> "$TestDrive/error.log" | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $out.version | Should -BeExactly '2.0.0'
+
+        $index = Get-Content -Raw -Path (Join-Path $env:DSC_RESOURCE_INSTALL_PATH 'installed.json') | ConvertFrom-Json
+        $index.bundles.Count | Should -Be 1
+    }
+
+    It 'refuses to install the same version twice unless forced' {
+        $bundle = New-ZipBundle '1.0.0'
+        $null = dsc resource install $bundle 2> "$TestDrive/error.log"
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+
+        $null = dsc resource install $bundle 2> "$TestDrive/error.log"
+        $LASTEXITCODE | Should -Be 2
+        Get-Content -Raw -Path "$TestDrive/error.log" | Should -BeLike '*already installed*'
+
+        $null = dsc resource install $bundle --force 2> "$TestDrive/error.log"
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+    }
+
+    It 'refuses to install a bundle with an invalid manifest' {
+        $content = Join-Path $TestDrive 'invalid'
+        $null = New-Item -ItemType Directory -Path $content -Force
+        '{"type":"Test/Invalid","version":"1.0.0","get":{}}' | Set-Content -Path (Join-Path $content 'invalid.dsc.resource.json')
+        $bundle = Join-Path $TestDrive 'invalid.zip'
+        Compress-Archive -Path "$content/*" -DestinationPath $bundle -Force
+
+        $null = dsc resource install $bundle 2> "$TestDrive/error.log"
+        $LASTEXITCODE | Should -Be 2
+        Get-Content -Raw -Path "$TestDrive/error.log" | Should -BeLike '*invalid.dsc.resource.json*'
+        Test-Path (Join-Path $env:DSC_RESOURCE_INSTALL_PATH 'installed.json') | Should -BeFalse
+    }
+
+    It 'installs from an OCI image layout' {
+        $content = Join-Path $TestDrive 'oci-content'
+        New-BundleContent -version '3.0.0' -path $content
+        $layout = Join-Path $TestDrive 'oci-layout'
+        $blobs = Join-Path $layout 'blobs' 'sha256'
+        $null = New-Item -ItemType Directory -Path $blobs -Force
+
+        $layer = Join-Path $TestDrive 'layer.tar.gz'
+        tar -czf $layer -C $content .
+        $layerDigest = (Get-FileHash -Algorithm SHA256 -Path $layer).Hash.ToLowerInvariant()
+        Copy-Item -Path $layer -Destination (Join-Path $blobs $layerDigest)
+
+        $config = Join-Path $blobs '44136fa355b3678a1146ad16f7e8649e94fb4fc21fe77e8310c060f61caaff8a'
+        [System.IO.File]::WriteAllText($config, '{}')
+        $manifest = @{
+            schemaVersion = 2
+            mediaType = 'application/vnd.oci.image.manifest.v1+json'
+            config = @{ mediaType = 'application/vnd.oci.empty.v1+json'; digest = 'sha256:44136fa355b3678a1146ad16f7e8649e94fb4fc21fe77e8310c060f61caaff8a'; size = 2 }
+            layers = @(
+                @{ mediaType = 'application/vnd.oci.image.layer.v1.tar+gzip'; digest = "sha256:$layerDigest"; size = (Get-Item $layer).Length }
+            )
+        } | ConvertTo-Json -Depth 10 -Compress
+        $manifestPath = Join-Path $TestDrive 'manifest.json'
+        [System.IO.File]::WriteAllText($manifestPath, $manifest)
+        $manifestDigest = (Get-FileHash -Algorithm SHA256 -Path $manifestPath).Hash.ToLowerInvariant()
+        Copy-Item -Path $manifestPath -Destination (Join-Path $blobs $manifestDigest)
+
+        [System.IO.File]::WriteAllText((Join-Path $layout 'oci-layout'), '{"imageLayoutVersion":"1.0.0"}')
+        @{
+            schemaVersion = 2
+            manifests = @(
+                @{ mediaType = 'application/vnd.oci.image.manifest.v1+json'; digest = "sha256:$manifestDigest"; size = $manifest.Length }
+            )
+        } | ConvertTo-Json -Depth 10 | Set-Content -Path (Join-Path $layout 'index.json')
+
+        $out = dsc resource install $layout 2> "$TestDrive/error.log" | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $out.sha256 | Should -BeExactly $manifestDigest
+        $out.resources[0].version | Should -BeExactly '3.0.0'
+
+        $out = dsc resource get -r Test/BundledEcho --input '{"output":"oci"}' 2> "$TestDrive/error.log" | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $out.actualState.output | Should -BeExactly 'oci'
+    }
+
+    It 'fails to uninstall a resource that is not installed' {
+        $null = dsc resource uninstall -r Test/NotInstalled 2> "$TestDrive/error.log"
+        $LASTEXITCODE | Should -Be 2
+        Get-Content -Raw -Path "$TestDrive/error.log" | Should -BeLike '*No installed bundle provides*'
+    }
+}
@@ -0,0 +1,96 @@
+# Copyright (c) Microsoft Corporation.
+# Licensed under the MIT License.
+
+Describe 'Executable integrity pinning' {
+    BeforeAll {
+        $oldPath = $env:DSC_RESOURCE_PATH
+        $env:DSC_RESOURCE_PATH = $TestDrive
+        $script:digest = (Get-FileHash -Algorithm SHA256 -Path (Get-Command dscecho -CommandType Application | Select-Object -First 1).Source).Hash.ToLowerInvariant()
+
+        $script:dscHome = (Get-Command dsc).Path | Split-Path
+        $script:dscSettingsFilePath = Join-Path $script:dscHome 'dsc.settings.json'
+        $script:dscSettingsFilePath_backup = Join-Path $script:dscHome 'dsc.settings.json.integrity.backup'
+        Copy-Item -Force -Path $script:dscSettingsFilePath -Destination $script:dscSettingsFilePath_backup
+
+        function Set-EchoManifest([object]$sha256) {
+            $manifest = [ordered]@{
+                '$schema' = 'https://aka.ms/dsc/schemas/v3/bundled/resource/manifest.json'
+                type = 'Test/PinnedEcho'
+                version = '1.0.0'
+                get = [ordered]@{
+                    executable = 'dscecho'
+                    args = @(
+                        @{ jsonInputArg = '--input'; mandatory = $true }
+                    )
+                }
+                schema = @{
+                    embedded = @{
+                        type = 'object'
+                        properties = @{ output = @{} }
+                    }
+                }
+            }
+            if ($null -ne $sha256) {
+                $manifest.get.sha256 = $sha256
+            }
+            $manifest | ConvertTo-Json -Depth 10 | Set-Content -Path (Join-Path $TestDrive 'pinnedecho.dsc.resource.json')
+        }
+    }
+
+    AfterAll {
+        $env:DSC_RESOURCE_PATH = $oldPath
+        Move-Item -Force -Path $script:dscSettingsFilePath_backup -Destination $script:dscSettingsFilePath
+    }
+
+    AfterEach {
+        Copy-Item -Force -Path $script:dscSettingsFilePath_backup -Destination $script:dscSettingsFilePath
+    }
+
+    It 'runs the executable when the digest matches: <name>' -TestCases @(
+        @{ name = 'single'; sha256 = { $script:digest } }
+        @{ name = 'list'; sha256 = { @('0' * 64, $script:digest.ToUpperInvariant()) } }
+    ) {
+        param($sha256)
+
+        Set-EchoManifest (& $sha256)
+        $out = '{"output":"hello"}' | dsc resource get -r Test/PinnedEcho -f - 2> "$TestDrive/error.log" | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $out.actualState.output | Should -BeExactly 'hello'
+    }
+
+    It 'refuses to run the executable when the digest does not match' {
+        Set-EchoManifest ('0' * 64)
+        $null = '{"output":"hello"}' | dsc resource get -r Test/PinnedEcho -f - 2> "$TestDrive/error.log"
+        $LASTEXITCODE | Should -Be 2
+        $log = Get-Content -Raw -Path "$TestDrive/error.log"
+        $log | Should -BeLike "*SHA-256 digest doesn't match*"
+        $log | Should -BeLike "*$script:digest*"
+    }
+
+    It 'requires a digest when pinning is required by settings' {
+        $settings = Get-Content -Raw -Path $script:dscSettingsFilePath_backup | ConvertFrom-Json
+        $settings.executableIntegrity.requirePinning = $true
+        $settings | ConvertTo-Json -Depth 10 | Set-Content -Path $script:dscSettingsFilePath
+
+        Set-EchoManifest $null
+        $null = '{"output":"hello"}' | dsc resource get -r Test/PinnedEcho -f - 2> "$TestDrive/error.log"
+        $LASTEXITCODE | Should -Be 2
+        Get-Content -Raw -Path "$TestDrive/error.log" | Should -BeLike '*Policy requires a SHA-256 digest*'
+
+        Set-EchoManifest $script:digest
+        $out = '{"output":"hello"}' | dsc resource get -r Test/PinnedEcho -f - 2> "$TestDrive/error.log" | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $out.actualState.output | Should -BeExactly 'hello'
+    }
+
+    It 'refuses to run the executable when the setting is malformed' {
+        $settings = Get-Content -Raw -Path $script:dscSettingsFilePath_backup | ConvertFrom-Json
+        $settings.executableIntegrity = @{ requirePining = $true }
+        $settings | ConvertTo-Json -Depth 10 | Set-Content -Path $script:dscSettingsFilePath
+
+        Set-EchoManifest $script:digest
+        $null = '{"output":"hello"}' | dsc resource get -r Test/PinnedEcho -f - 2> "$TestDrive/error.log"
+        $LASTEXITCODE | Should -Be 2
+        Get-Content -Raw -Path "$TestDrive/error.log" | Should -BeLike "*Invalid 'executableIntegrity' setting*"
+    }
+}
@@ -0,0 +1,76 @@
+# Copyright (c) Microsoft Corporation.
+# Licensed under the MIT License.
+
+Describe 'resource new tests' {
+    BeforeAll {
+        $oldResourcePath = $env:DSC_RESOURCE_PATH
+    }
+
+    AfterEach {
+        $env:DSC_RESOURCE_PATH = $oldResourcePath
+    }
+
+    It 'powershell project passes its smoke test' {
+        $path = Join-Path $TestDrive 'pwsh'
+        $out = dsc resource new --type Test.Scaffold/PwshSample --language powershell --path $path -o json 2> "$TestDrive/error.log" | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $out.type | Should -BeExactly 'Test.Scaffold/PwshSample'
+        $out.language | Should -BeExactly 'powershell'
+        $out.files.Count | Should -Be 3
+        Join-Path $path 'pwshsample.dsc.resource.json' | Should -Exist
+        Join-Path $path 'pwshsample.ps1' | Should -Exist
+
+        $env:DSC_RESOURCE_PATH = $path
+        $out = dsc config test -f (Join-Path $path 'pwshsample.smoke.dsc.yaml') 2> "$TestDrive/error.log" | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $out.results[0].type | Should -BeExactly 'Test.Scaffold/PwshSample'
+        $out.results[0].result.inDesiredState | Should -BeTrue
+
+        $out = dsc resource export -r Test.Scaffold/PwshSample 2> "$TestDrive/error.log" | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $out.resources[0].properties.name | Should -BeExactly 'example'
+    }
+
+    It 'bash project passes its smoke test' -Skip:($IsWindows) {
+        $path = Join-Path $TestDrive 'bash'
+        $null = dsc resource new --type Test.Scaffold/BashSample --language bash --path $path 2> "$TestDrive/error.log"
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+
+        $env:DSC_RESOURCE_PATH = $path
+        $out = dsc config test -f (Join-Path $path 'bashsample.smoke.dsc.yaml') 2> "$TestDrive/error.log" | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $out.results[0].result.inDesiredState | Should -BeTrue
+
+        $out = dsc resource set -r Test.Scaffold/BashSample -i '{"name":"hello"}' 2> "$TestDrive/error.log" | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $out.afterState.name | Should -BeExactly 'hello'
+    }
+
+    It 'rust project contains a crate and a valid manifest' {
+        $path = Join-Path $TestDrive 'rust'
+        $null = dsc resource new --type Test.Scaffold/RustSample --language rust --path $path 2> "$TestDrive/error.log"
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        Join-Path $path 'Cargo.toml' | Should -Exist
+        Join-Path $path 'src' 'main.rs' | Should -Exist
+        $manifest = Get-Content -Raw -Path (Join-Path $path 'rustsample.dsc.resource.json') | ConvertFrom-Json
+        $manifest.get.executable | Should -BeExactly 'rustsample'
+        $manifest.get.args[1].jsonInputArg | Should -BeExactly '--input'
+        $manifest.test.return | Should -BeExactly 'state'
+        $manifest.schema.embedded.properties._inDesiredState.readOnly | Should -BeTrue
+        Get-Content -Raw -Path (Join-Path $path 'Cargo.toml') | Should -BeLike '*dsc-resource-sdk*'
+        $main = Get-Content -Raw -Path (Join-Path $path 'src' 'main.rs')
+        $main | Should -BeLike '*impl DscResource for Resource*'
+        $main | Should -BeLike '*fn test_reports_in_desired_state()*'
+    }
+
+    It 'existing files are not overwritten' {
+        $path = Join-Path $TestDrive 'existing'
+        $null = New-Item -ItemType Directory -Path $path
+        Set-Content -Path (Join-Path $path 'existing.sh') -Value 'original'
+        $null = dsc resource new --type Test.Scaffold/Existing --language bash --path $path 2> "$TestDrive/error.log"
+        $LASTEXITCODE | Should -Be 2
+        (Get-Content -Raw -Path "$TestDrive/error.log") | Should -BeLike "*existing.sh*"
+        Get-Content -Raw -Path (Join-Path $path 'existing.sh') | Should -BeExactly "original$([Environment]::NewLine)"
+        Join-Path $path 'existing.dsc.resource.json' | Should -Not -Exist
+    }
+}
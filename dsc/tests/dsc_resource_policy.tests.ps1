@@ -0,0 +1,99 @@
+# Copyright (c) Microsoft Corporation.
+# Licensed under the MIT License.
+
+Describe 'Resource policy' {
+    BeforeAll {
+        $script:dscHome = (Get-Command dsc).Path | Split-Path
+        $script:dscSettingsFilePath = Join-Path $script:dscHome 'dsc.settings.json'
+        $script:dscSettingsFilePath_backup = Join-Path $script:dscHome 'dsc.settings.json.policy.backup'
+        Copy-Item -Force -Path $script:dscSettingsFilePath -Destination $script:dscSettingsFilePath_backup
+
+        function Set-ResourcePolicy([object[]]$rules) {
+            $settings = Get-Content -Raw -Path $script:dscSettingsFilePath_backup | ConvertFrom-Json
+            $settings.resourcePolicy.rules = $rules
+            $settings | ConvertTo-Json -Depth 10 | Set-Content -Path $script:dscSettingsFilePath
+        }
+    }
+
+    AfterAll {
+        Move-Item -Force -Path $script:dscSettingsFilePath_backup -Destination $script:dscSettingsFilePath
+    }
+
+    AfterEach {
+        Copy-Item -Force -Path $script:dscSettingsFilePath_backup -Destination $script:dscSettingsFilePath
+    }
+
+    It 'allows only the listed operations for <type>' -TestCases @(
+        @{ type = 'Microsoft.DSC.Debug/Echo' }
+        @{ type = 'microsoft.dsc.debug/*' }
+    ) {
+        param($type)
+
+        Set-ResourcePolicy @(
+            @{ type = $type; allow = @('get', 'test') }
+        )
+        $out = dsc -l info resource get -r Microsoft.DSC.Debug/Echo --input '{"output":"hello"}' 2> "$TestDrive/error.log" | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $out.actualState.output | Should -BeExactly 'hello'
+        Get-Content -Raw -Path "$TestDrive/error.log" | Should -BeLike "*Resource policy audit: allowed 'get'*"
+
+        $null = dsc resource set -r Microsoft.DSC.Debug/Echo --input '{"output":"hello"}' 2> "$TestDrive/error.log"
+        $LASTEXITCODE | Should -Be 2
+        $log = Get-Content -Raw -Path "$TestDrive/error.log"
+        $log | Should -BeLike "*Resource policy audit: denied 'set'*"
+        $log | Should -BeLike "*Resource policy denied operation 'set' for resource 'Microsoft.DSC.Debug/Echo'*"
+    }
+
+    It 'denied operation is refused in a configuration' {
+        Set-ResourcePolicy @(
+            @{ type = 'Microsoft.DSC.Debug/Echo'; deny = @('set') }
+        )
+        $config_yaml = @'
+            $schema: https://aka.ms/dsc/schemas/v3/bundled/config/document.json
+            resources:
+            - name: Echo
+              type: Microsoft.DSC.Debug/Echo
+              properties:
+                output: hello
+'@
+        $out = dsc config test -i $config_yaml 2> "$TestDrive/error.log" | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $out.results[0].result.inDesiredState | Should -BeTrue
+
+        $null = dsc config set -i $config_yaml 2> "$TestDrive/error.log"
+        $LASTEXITCODE | Should -Not -Be 0
+        Get-Content -Raw -Path "$TestDrive/error.log" | Should -BeLike "*Resource policy denied operation 'set'*"
+    }
+
+    It 'requires what-if for set' {
+        Set-ResourcePolicy @(
+            @{ type = 'Test/*'; requireWhatIf = @('set') }
+        )
+        $result = dsc resource set -w -r Test/WhatIf --input '{"executionType":"Actual"}' 2> "$TestDrive/error.log" | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $result.afterState.executionType | Should -BeExactly 'WhatIf'
+
+        $null = dsc resource set -r Test/WhatIf --input '{"executionType":"Actual"}' 2> "$TestDrive/error.log"
+        $LASTEXITCODE | Should -Be 2
+        Get-Content -Raw -Path "$TestDrive/error.log" | Should -BeLike "*only allowed in what-if mode*"
+    }
+
+    It 'first matching rule decides' {
+        Set-ResourcePolicy @(
+            @{ type = 'Microsoft.DSC.Debug/Echo'; allow = @('get', 'set', 'test') }
+            @{ type = '*'; deny = @('set') }
+        )
+        $out = dsc resource set -r Microsoft.DSC.Debug/Echo --input '{"output":"hello"}' 2> "$TestDrive/error.log" | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $out.afterState.output | Should -BeExactly 'hello'
+    }
+
+    It 'invalid policy refuses to invoke resources' {
+        Set-ResourcePolicy @(
+            @{ type = 'Microsoft.DSC.Debug/Echo'; deny = @('remove') }
+        )
+        $null = dsc resource get -r Microsoft.DSC.Debug/Echo --input '{"output":"hello"}' 2> "$TestDrive/error.log"
+        $LASTEXITCODE | Should -Be 2
+        Get-Content -Raw -Path "$TestDrive/error.log" | Should -BeLike "*Invalid 'resourcePolicy' setting*"
+    }
+}
@@ -0,0 +1,252 @@
+# Copyright (c) Microsoft Corporation.
+# Licensed under the MIT License.
+
+Describe 'tests for resources served by a long-lived process' {
+    BeforeAll {
+        $server = @'
+while ($null -ne ($line = [Console]::In.ReadLine())) {
+    $request = $line | ConvertFrom-Json
+    if ($request.method -eq 'shutdown') {
+        exit 0
+    }
+    $result = switch ($request.method) {
+        'get' {
+            ,@(@{ name = $request.params.input.name; processId = $PID })
+        }
+        'set' {
+            ,@(@{ name = $request.params.input.name; processId = $PID; whatIf = $request.params.whatIf })
+        }
+        'export' {
+            ,@(@{ name = 'one'; processId = $PID }, @{ name = 'two'; processId = $PID })
+        }
+    }
+    if ($null -eq $result) {
+        $response = @{ jsonrpc = '2.0'; id = $request.id; error = @{ code = -32601; message = "Method '$($request.method)' not found" } }
+    }
+    else {
+        $response = @{ jsonrpc = '2.0'; id = $request.id; result = $result }
+    }
+    [Console]::Out.WriteLine(($response | ConvertTo-Json -Compress -Depth 10))
+    [Console]::Out.Flush()
+}
+'@
+        $manifest = @'
+{
+    "$schema": "https://aka.ms/dsc/schemas/v3/bundled/resource/manifest.json",
+    "type": "Test/Server",
+    "version": "0.1.0",
+    "server": {
+        "executable": "pwsh",
+        "args": [
+            "-NoLogo",
+            "-NonInteractive",
+            "-NoProfile",
+            "-File",
+            "server.ps1"
+        ],
+        "protocol": "jsonrpc"
+    },
+    "exitCodes": {
+        "0": "Success",
+        "-32601": "Operation not supported by the server"
+    },
+    "get": {
+        "executable": "pwsh"
+    },
+    "set": {
+        "executable": "pwsh",
+        "return": "state"
+    },
+    "delete": {
+        "executable": "pwsh"
+    },
+    "export": {
+        "executable": "pwsh"
+    },
+    "schema": {
+        "embedded": {
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "type": "object",
+            "properties": {
+                "name": {
+                    "type": "string"
+                },
+                "processId": {
+                    "type": "integer"
+                },
+                "whatIf": {
+                    "type": "boolean"
+                }
+            }
+        }
+    }
+}
+'@
+        Set-Content -Path "$TestDrive/server.ps1" -Value $server
+        Set-Content -Path "$TestDrive/server.dsc.resource.json" -Value $manifest
+        $oldResourcePath = $env:DSC_RESOURCE_PATH
+        $env:DSC_RESOURCE_PATH = $TestDrive
+    }
+
+    AfterAll {
+        $env:DSC_RESOURCE_PATH = $oldResourcePath
+    }
+
+    It 'operations in a configuration are sent to the same process' {
+        $config = @'
+$schema: https://aka.ms/dsc/schemas/v3/bundled/config/document.json
+resources:
+- name: first
+  type: Test/Server
+  properties:
+    name: first
+- name: second
+  type: Test/Server
+  properties:
+    name: second
+'@
+        $out = dsc config get -i $config 2> "$TestDrive/error.log" | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $out.results[0].result.actualState.name | Should -BeExactly 'first'
+        $out.results[1].result.actualState.name | Should -BeExactly 'second'
+        $out.results[0].result.actualState.processId | Should -Be $out.results[1].result.actualState.processId
+        $out.results[0].result.actualState.processId | Should -Not -Be $PID
+    }
+
+    It 'test without a server result uses get through the server' {
+        $out = dsc resource test -r Test/Server -i '{"name":"hello"}' 2> "$TestDrive/error.log" | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $out.actualState.name | Should -BeExactly 'hello'
+    }
+
+    It 'export returns every document of the server result' {
+        $out = dsc resource export -r Test/Server 2> "$TestDrive/error.log" | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $out.resources.Count | Should -Be 2
+        $out.resources[0].properties.name | Should -BeExactly 'one'
+        $out.resources[1].properties.name | Should -BeExactly 'two'
+    }
+
+    It 'error responses from the server fail the operation' {
+        $null = dsc resource delete -r Test/Server -i '{"name":"hello"}' 2> "$TestDrive/error.log"
+        $LASTEXITCODE | Should -Not -Be 0
+        (Get-Content -Raw -Path "$TestDrive/error.log") | Should -Match 'Operation not supported by the server'
+    }
+
+    It 'server that does not respond within the timeout fails the operation' {
+        $hangingServer = @'
+while ($null -ne ($line = [Console]::In.ReadLine())) {
+    $request = $line | ConvertFrom-Json
+    if ($request.method -eq 'shutdown') {
+        exit 0
+    }
+    Start-Sleep -Seconds 30
+}
+'@
+        $hangingManifest = @'
+{
+    "$schema": "https://aka.ms/dsc/schemas/v3/bundled/resource/manifest.json",
+    "type": "Test/HangingServer",
+    "version": "0.1.0",
+    "server": {
+        "executable": "pwsh",
+        "args": [
+            "-NoLogo",
+            "-NonInteractive",
+            "-NoProfile",
+            "-File",
+            "hangingServer.ps1"
+        ],
+        "protocol": "jsonrpc",
+        "timeout": 2
+    },
+    "get": {
+        "executable": "pwsh"
+    },
+    "schema": {
+        "embedded": {
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "type": "object"
+        }
+    }
+}
+'@
+        Set-Content -Path "$TestDrive/hangingServer.ps1" -Value $hangingServer
+        Set-Content -Path "$TestDrive/hangingServer.dsc.resource.json" -Value $hangingManifest
+        try {
+            $elapsed = Measure-Command {
+                $null = dsc resource get -r Test/HangingServer -i '{}' 2> "$TestDrive/error.log"
+            }
+            $LASTEXITCODE | Should -Not -Be 0
+            (Get-Content -Raw -Path "$TestDrive/error.log") | Should -Match 'did not respond'
+            $elapsed.TotalSeconds | Should -BeLessThan 25
+        }
+        finally {
+            Remove-Item -Path "$TestDrive/hangingServer.dsc.resource.json"
+        }
+    }
+
+    It 'server is started with the environment and working directory declared by server' {
+        $envServer = @'
+while ($null -ne ($line = [Console]::In.ReadLine())) {
+    $request = $line | ConvertFrom-Json
+    if ($request.method -eq 'shutdown') {
+        exit 0
+    }
+    $result = ,@(@{ name = $env:DSC_SERVER_NAME; cwd = (Split-Path -Leaf (Get-Location).Path); hasPath = $null -ne $env:PATH })
+    $response = @{ jsonrpc = '2.0'; id = $request.id; result = $result }
+    [Console]::Out.WriteLine(($response | ConvertTo-Json -Compress -Depth 10))
+    [Console]::Out.Flush()
+}
+'@
+        $envManifest = @'
+{
+    "$schema": "https://aka.ms/dsc/schemas/v3/bundled/resource/manifest.json",
+    "type": "Test/EnvServer",
+    "version": "0.1.0",
+    "server": {
+        "executable": "pwsh",
+        "args": [
+            "-NoLogo",
+            "-NonInteractive",
+            "-NoProfile",
+            "-File",
+            "../envServer.ps1"
+        ],
+        "protocol": "jsonrpc",
+        "env": {
+            "DSC_SERVER_NAME": "declared"
+        },
+        "cwd": {
+            "path": "work"
+        },
+        "envPassthrough": [
+            "PATH"
+        ]
+    },
+    "get": {
+        "executable": "pwsh"
+    },
+    "schema": {
+        "embedded": {
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "type": "object"
+        }
+    }
+}
+'@
+        $null = New-Item -ItemType Directory -Path "$TestDrive/work" -Force
+        Set-Content -Path "$TestDrive/envServer.ps1" -Value $envServer
+        Set-Content -Path "$TestDrive/envServer.dsc.resource.json" -Value $envManifest
+        try {
+            $out = dsc resource get -r Test/EnvServer -i '{}' 2> "$TestDrive/error.log" | ConvertFrom-Json
+            $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+            $out.actualState.name | Should -BeExactly 'declared'
+            $out.actualState.cwd | Should -BeExactly 'work'
+            $out.actualState.hasPath | Should -BeTrue
+        }
+        finally {
+            Remove-Item -Path "$TestDrive/envServer.dsc.resource.json"
+        }
+    }
+}
@@ -0,0 +1,142 @@
+# Copyright (c) Microsoft Corporation.
+# Licensed under the MIT License.
+
+Describe 'Signed manifests and trusted publishers' {
+    BeforeAll {
+        $oldPath = $env:DSC_RESOURCE_PATH
+        $env:DSC_RESOURCE_PATH = $TestDrive
+
+        # the signature is the base64 encoded ed25519 signature of the exact manifest content
+        $script:manifest = '{"$schema":"https://aka.ms/dsc/schemas/v3/bundled/resource/manifest.json","type":"Test/SignedEcho","version":"1.0.0","get":{"executable":"dscecho","args":[{"jsonInputArg":"--input","mandatory":true}]},"schema":{"embedded":{"type":"object","properties":{"output":{}}}}}'
+        $script:signature = 'i5G7zCu4hyocbqJBEqta5QzoXLCs00Kw+P+9Si/iRyLE7hE00gvaV1a9sENiOjmbCf0fzLhbN5TKP4fnrmkzAQ=='
+        $script:publisherKey = '6kpsY+KcUgq+9VB7Ey7F+ZVHdq6+vnuSQh7qaRRG0iw='
+        $script:otherKey = '/RckOFqgx1tk+3jNYC+h2ZH96/drE8WO1wLqyDXp9hg='
+        $script:manifestPath = Join-Path $TestDrive 'signedecho.dsc.resource.json'
+
+        $script:dscHome = (Get-Command dsc).Path | Split-Path
+        $script:dscSettingsFilePath = Join-Path $script:dscHome 'dsc.settings.json'
+        $script:dscSettingsFilePath_backup = Join-Path $script:dscHome 'dsc.settings.json.trust.backup'
+        Copy-Item -Force -Path $script:dscSettingsFilePath -Destination $script:dscSettingsFilePath_backup
+
+        function Set-TrustedPublishers([string]$publicKey, [bool]$enforce) {
+            $settings = Get-Content -Raw -Path $script:dscSettingsFilePath_backup | ConvertFrom-Json
+            $settings.trustedPublishers.enforce = $enforce
+            $settings.trustedPublishers.publishers = @(
+                [pscustomobject]@{ name = 'Contoso'; publicKey = $publicKey }
+            )
+            $settings | ConvertTo-Json -Depth 10 | Set-Content -Path $script:dscSettingsFilePath
+        }
+    }
+
+    AfterAll {
+        $env:DSC_RESOURCE_PATH = $oldPath
+        Move-Item -Force -Path $script:dscSettingsFilePath_backup -Destination $script:dscSettingsFilePath
+    }
+
+    BeforeEach {
+        [System.IO.File]::WriteAllText($script:manifestPath, $script:manifest)
+        [System.IO.File]::WriteAllText("$script:manifestPath.sig", "untrusted comment: test signature`n$script:signature`n")
+    }
+
+    AfterEach {
+        Copy-Item -Force -Path $script:dscSettingsFilePath_backup -Destination $script:dscSettingsFilePath
+    }
+
+    It 'signatures are not verified without trusted publishers' {
+        $out = dsc resource list Test/SignedEcho -o json 2> "$TestDrive/error.log" | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $out.type | Should -BeExactly 'Test/SignedEcho'
+        $out.PSObject.Properties.Name | Should -Not -Contain 'trust'
+    }
+
+    It 'resource signed by a trusted publisher can be used' {
+        Set-TrustedPublishers -publicKey $script:publisherKey -enforce $true
+        $out = dsc resource list Test/SignedEcho -o json 2> "$TestDrive/error.log" | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $out.trust.status | Should -BeExactly 'trusted'
+        $out.trust.publisher | Should -BeExactly 'Contoso'
+
+        $out = '{"output":"hello"}' | dsc resource get -r Test/SignedEcho -f - 2> "$TestDrive/error.log" | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $out.actualState.output | Should -BeExactly 'hello'
+    }
+
+    It 'resource with <name> is untrusted and refused' -TestCases @(
+        @{ name = 'signature from another publisher'; publicKey = '/RckOFqgx1tk+3jNYC+h2ZH96/drE8WO1wLqyDXp9hg='; tamper = $false; removeSignature = $false }
+        @{ name = 'tampered manifest'; publicKey = '6kpsY+KcUgq+9VB7Ey7F+ZVHdq6+vnuSQh7qaRRG0iw='; tamper = $true; removeSignature = $false }
+        @{ name = 'no signature'; publicKey = '6kpsY+KcUgq+9VB7Ey7F+ZVHdq6+vnuSQh7qaRRG0iw='; tamper = $false; removeSignature = $true }
+    ) {
+        param($publicKey, $tamper, $removeSignature)
+
+        if ($tamper) {
+            [System.IO.File]::WriteAllText($script:manifestPath, $script:manifest.Replace('"1.0.0"', '"1.0.1"'))
+        }
+        if ($removeSignature) {
+            Remove-Item -Path "$script:manifestPath.sig"
+        }
+        Set-TrustedPublishers -publicKey $publicKey -enforce $true
+
+        $out = dsc resource list Test/SignedEcho -o json 2> "$TestDrive/error.log"
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $out | Should -BeNullOrEmpty
+
+        # untrusted manifests are still discovered so they can be reported
+        $out = dsc resource list Test/SignedEcho --show-untrusted -o json 2> "$TestDrive/error.log" | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $out.type | Should -BeExactly 'Test/SignedEcho'
+        $out.trust.status | Should -BeExactly 'untrusted'
+        $out.trust.reason | Should -Not -BeNullOrEmpty
+
+        $null = '{"output":"hello"}' | dsc resource get -r Test/SignedEcho -f - 2> "$TestDrive/error.log"
+        $LASTEXITCODE | Should -Not -Be 0
+        Get-Content -Raw -Path "$TestDrive/error.log" | Should -BeLike "*Resource is not signed by a trusted publisher 'Test/SignedEcho'*"
+    }
+
+    It 'untrusted resource is listed with --show-untrusted when trusted publishers are not enforced' {
+        Set-TrustedPublishers -publicKey $script:otherKey -enforce $false
+        $out = dsc resource list Test/SignedEcho -o json 2> "$TestDrive/error.log"
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $out | Should -BeNullOrEmpty
+
+        $out = dsc resource list Test/SignedEcho --show-untrusted -o json 2> "$TestDrive/error.log" | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $out.type | Should -BeExactly 'Test/SignedEcho'
+        $out.trust.status | Should -BeExactly 'untrusted'
+        $out.trust.reason | Should -Not -BeNullOrEmpty
+    }
+
+    It 'untrusted adapter does not run its list command when trusted publishers are enforced' {
+        $markerPath = Join-Path $TestDrive 'adapterListed.txt'
+        $adapterManifest = @{
+            '$schema' = 'https://aka.ms/dsc/schemas/v3/bundled/resource/manifest.json'
+            type = 'Test/UntrustedAdapter'
+            version = '1.0.0'
+            kind = 'adapter'
+            adapter = @{
+                config = 'full'
+                list = @{
+                    executable = 'pwsh'
+                    args = @('-NoLogo', '-NonInteractive', '-NoProfile', '-Command', "Set-Content -Path '$markerPath' -Value listed")
+                }
+            }
+        } | ConvertTo-Json -Depth 10
+        $adapterPath = Join-Path $TestDrive 'untrustedAdapter.dsc.resource.json'
+        Set-Content -Path $adapterPath -Value $adapterManifest
+        try {
+            Set-TrustedPublishers -publicKey $script:publisherKey -enforce $true
+            $null = dsc resource list --adapter Test/UntrustedAdapter -o json 2> "$TestDrive/error.log"
+            $markerPath | Should -Not -Exist
+            Get-Content -Raw -Path "$TestDrive/error.log" | Should -BeLike "*Skipping the resources of adapter 'Test/UntrustedAdapter'*"
+        }
+        finally {
+            Remove-Item -Path $adapterPath
+        }
+    }
+
+    It 'untrusted resource can be used when the policy does not enforce trusted publishers' {
+        Set-TrustedPublishers -publicKey $script:otherKey -enforce $false
+        $out = '{"output":"hello"}' | dsc resource get -r Test/SignedEcho -f - 2> "$TestDrive/error.log" | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $out.actualState.output | Should -BeExactly 'hello'
+    }
+}
@@ -0,0 +1,106 @@
+# Copyright (c) Microsoft Corporation.
+# Licensed under the MIT License.
+
+Describe 'resource validate-manifest tests' {
+    It 'valid manifest has no errors' {
+        $manifest = @'
+{
+    "$schema": "https://aka.ms/dsc/schemas/v3/bundled/resource/manifest.json",
+    "type": "Test/Valid",
+    "version": "0.1.0",
+    "get": {
+        "executable": "dsc",
+        "args": ["--version"]
+    },
+    "schema": {
+        "embedded": {
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "type": "object"
+        }
+    }
+}
+'@
+        $path = Join-Path $TestDrive 'valid.dsc.resource.json'
+        Set-Content -Path $path -Value $manifest
+        $out = dsc resource validate-manifest $path -o json 2> "$TestDrive/error.log" | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $out.valid | Should -BeTrue
+        $out.diagnostics | Where-Object { $_.severity -eq 'error' } | Should -BeNullOrEmpty
+    }
+
+    It 'semantic errors are reported with pointers' {
+        $manifest = @'
+{
+    "$schema": "https://aka.ms/dsc/schemas/v3/bundled/resource/manifest.json",
+    "type": "Test/Invalid",
+    "version": "0.1.0",
+    "get": {
+        "executable": "dsc",
+        "args": [
+            { "jsonInputArg": "--input" },
+            { "jsonInputArg": "--other" }
+        ]
+    },
+    "set": {
+        "executable": "doesNotExist",
+        "handlesExist": true
+    },
+    "exitCodes": {
+        "1": "Failure"
+    },
+    "schema": {
+        "embedded": {
+            "type": "object",
+            "properties": {
+                "name": { "type": "string" }
+            }
+        }
+    }
+}
+'@
+        $path = Join-Path $TestDrive 'invalid.dsc.resource.json'
+        Set-Content -Path $path -Value $manifest
+        $out = dsc resource validate-manifest $path -o json 2> "$TestDrive/error.log" | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 5 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $out.valid | Should -BeFalse
+        $errors = $out.diagnostics | Where-Object { $_.severity -eq 'error' }
+        ($errors | Where-Object { $_.code -eq 'duplicateJsonInputArg' }).pointer | Should -BeExactly '/get/args/1'
+        ($errors | Where-Object { $_.code -eq 'executableNotFound' }).pointer | Should -BeExactly '/set/executable'
+        ($errors | Where-Object { $_.code -eq 'handlesExistWithoutExist' }).pointer | Should -BeExactly '/set/handlesExist'
+        ($errors | Where-Object { $_.code -eq 'exitCodesMissingZero' }).pointer | Should -BeExactly '/exitCodes'
+    }
+
+    It 'unparsable manifest is reported' {
+        $path = Join-Path $TestDrive 'broken.dsc.resource.json'
+        Set-Content -Path $path -Value '{ "type": '
+        $out = dsc resource validate-manifest $path -o json 2> "$TestDrive/error.log" | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 5 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $out.diagnostics[0].code | Should -BeExactly 'invalidSyntax'
+    }
+
+    It 'warnings do not fail validation of a yaml extension manifest' {
+        $manifest = @'
+$schema: https://aka.ms/dsc/schemas/v3/bundled/extension/manifest.json
+type: Test/Extension
+version: 0.1.0
+import:
+  fileExtensions: []
+  executable: dsc
+  args: []
+'@
+        $path = Join-Path $TestDrive 'test.dsc.extension.yaml'
+        Set-Content -Path $path -Value $manifest
+        $out = dsc resource validate-manifest $path -o json 2> "$TestDrive/error.log" | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $out.valid | Should -BeTrue
+        ($out.diagnostics | Where-Object { $_.code -eq 'importWithoutFileExtensions' }).severity | Should -BeExactly 'warning'
+    }
+
+    It 'multiple files produce a result each' {
+        $path = Join-Path $TestDrive 'multi.dsc.resource.json'
+        Set-Content -Path $path -Value '{ "type": '
+        $out = dsc resource validate-manifest $path $path -o json 2> "$TestDrive/error.log"
+        $LASTEXITCODE | Should -Be 5 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $out.Count | Should -Be 2
+    }
+}
@@ -0,0 +1,58 @@
+# Copyright (c) Microsoft Corporation.
+# Licensed under the MIT License.
+
+Describe 'resource verify tests' {
+    BeforeAll {
+        $oldResourcePath = $env:DSC_RESOURCE_PATH
+        $path = Join-Path $TestDrive 'resources'
+        $null = dsc resource new --type Test.Verify/Conforming --language powershell --path $path
+        $null = dsc resource new --type Test.Verify/Drifting --language powershell --path $path
+        # the drifting resource always reports the same actual state, so set never converges
+        $script = Join-Path $path 'drifting.ps1'
+        (Get-Content -Raw -Path $script) -replace '(?s)function Get-Instance\(\[hashtable\]\$instance\) \{.*?\n\}', "function Get-Instance([hashtable]`$instance) {`n    @{ name = 'actual' }`n}" | Set-Content -Path $script
+        $env:DSC_RESOURCE_PATH = $path
+    }
+
+    AfterAll {
+        $env:DSC_RESOURCE_PATH = $oldResourcePath
+    }
+
+    It 'conforming resource passes every supported check' {
+        $out = dsc resource verify -r Test.Verify/Conforming -i '{"name":"hello"}' -o json 2> "$TestDrive/error.log" | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $out.type | Should -BeExactly 'Test.Verify/Conforming'
+        $out.passed | Should -BeTrue
+        $checks = @{}
+        $out.checks | ForEach-Object { $checks[$_.name] = $_ }
+        foreach ($name in 'schema', 'get', 'getMatchesSchema', 'test', 'set', 'testAfterSet', 'setIdempotent', 'export', 'exportMatchesSchema', 'exportRoundTrip') {
+            $checks[$name].status | Should -BeExactly 'passed' -Because "check '$name': $($checks[$name].message)"
+        }
+        $checks['whatIf'].status | Should -BeExactly 'skipped'
+    }
+
+    It 'resource that does not converge fails the test after set' {
+        $out = dsc resource verify -r Test.Verify/Drifting -i '{"name":"hello"}' -o json 2> "$TestDrive/error.log" | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 5 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $out.passed | Should -BeFalse
+        ($out.checks | Where-Object name -EQ 'get').status | Should -BeExactly 'passed'
+        ($out.checks | Where-Object name -EQ 'testAfterSet').status | Should -BeExactly 'failed'
+        ($out.checks | Where-Object name -EQ 'testAfterSet').message | Should -BeLike '*name*'
+    }
+
+    It 'results can be written as JUnit' {
+        $out = dsc resource verify -r Test.Verify/Drifting -i '{"name":"hello"}' -o junit 2> "$TestDrive/error.log"
+        $LASTEXITCODE | Should -Be 5 -Because (Get-Content -Raw -Path "$TestDrive/error.log")
+        $xml = [xml]($out -join [Environment]::NewLine)
+        $xml.testsuites.testsuite.name | Should -BeExactly 'Test.Verify/Drifting'
+        [int]$xml.testsuites.failures | Should -BeGreaterThan 0
+        $failed = $xml.testsuites.testsuite.testcase | Where-Object name -EQ 'testAfterSet'
+        $failed.failure | Should -Not -BeNullOrEmpty
+        $skipped = $xml.testsuites.testsuite.testcase | Where-Object name -EQ 'whatIf'
+        $skipped.skipped | Should -Not -BeNullOrEmpty
+    }
+
+    It 'input is required' {
+        $null = dsc resource verify -r Test.Verify/Conforming 2> "$TestDrive/error.log"
+        $LASTEXITCODE | Should -Be 1
+    }
+}
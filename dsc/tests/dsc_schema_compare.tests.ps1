@@ -0,0 +1,26 @@
+# Copyright (c) Microsoft Corporation.
+# Licensed under the MIT License.
+
+Describe 'Synthetic test uses x-dsc-compare schema keyword' {
+    It 'Property using <compare> comparison is in desired state: <desired>' -TestCases @(
+        @{ compare = 'set'; desired = '{"name":"test","tags":["a","b"]}'; inDesiredState = $true }
+        @{ compare = 'set'; desired = '{"name":"test","tags":["a","c"]}'; inDesiredState = $false }
+        @{ compare = 'caseInsensitive'; desired = '{"name":"test","state":"running"}'; inDesiredState = $true }
+        @{ compare = 'caseInsensitive'; desired = '{"name":"test","state":"stopped"}'; inDesiredState = $false }
+        @{ compare = 'subset'; desired = '{"name":"test","features":["z","x"]}'; inDesiredState = $true }
+        @{ compare = 'subset'; desired = '{"name":"test","features":["x","w"]}'; inDesiredState = $false }
+        @{ compare = 'ignore'; desired = '{"name":"test","lastModified":"2000-01-01T00:00:00Z"}'; inDesiredState = $true }
+    ) {
+        param($desired, $inDesiredState)
+
+        $out = $desired | dsc resource test -r Test/Compare -f - 2> $TestDrive/error.log | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw $TestDrive/error.log)
+        $out.inDesiredState | Should -Be $inDesiredState
+        if ($inDesiredState) {
+            $out.differingProperties | Should -BeNullOrEmpty
+        }
+        else {
+            $out.differingProperties.Count | Should -Be 1
+        }
+    }
+}
@@ -0,0 +1,29 @@
+# Copyright (c) Microsoft Corporation.
+# Licensed under the MIT License.
+
+Describe 'Resource manifest normalize field' {
+    It 'Set receives desired state with schema defaults and enum casing applied' {
+        $out = '{"name":"test","state":"stopped"}' | dsc resource set -r Test/Normalize -f - 2> $TestDrive/error.log | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw $TestDrive/error.log)
+        $out.afterState.name | Should -BeExactly 'test'
+        $out.afterState.state | Should -BeExactly 'Stopped'
+        $out.afterState.count | Should -Be 5
+    }
+
+    It 'Test compares the normalized desired state' {
+        $out = '{"name":"test","state":"RUNNING","count":2}' | dsc resource test -r Test/Normalize -f - 2> $TestDrive/error.log | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content -Raw $TestDrive/error.log)
+        $out.desiredState.state | Should -BeExactly 'Running'
+        $out.desiredState.count | Should -Be 2
+        $out.actualState.state | Should -BeExactly 'Running'
+        $out.inDesiredState | Should -BeTrue
+    }
+
+    It 'Invalid desired state is reported against the resource schema' {
+        '{"name":"test","count":0}' | dsc resource set -r Test/Normalize -f - 2> $TestDrive/error.log | Out-Null
+        $LASTEXITCODE | Should -Not -Be 0
+        $errorLog = Get-Content -Raw $TestDrive/error.log
+        $errorLog | Should -BeLike "*Test/Normalize*not valid against the resource schema*" -Because $errorLog
+        $errorLog | Should -BeLike "*'/count'*" -Because $errorLog
+    }
+}
@@ -151,4 +151,71 @@ resources:
         $LASTEXITCODE | Should -Be 2 -Because (Get-Content $testdrive/error.log | Out-String)
         (Get-Content $testdrive/error.log -Raw) | Should -BeLike "*Output of user function 'MyFunction.BadFunction' did not return expected type 'int'*" -Because (Get-Content $testdrive/error.log | Out-String)
     }
+
+    It 'user functions can be imported from a function library' {
+        $libraryYaml = @"
+version: 1.2.0
+functions:
+- namespace: Contoso
+  members:
+    Greet:
+      parameters:
+      - name: Who
+        type: string
+      output:
+        type: string
+        value: "[concat('Hello ', parameters('Who'))]"
+"@
+        Set-Content -Path $testdrive/contoso.dsc.functions.yaml -Value $libraryYaml
+        $configYaml = @"
+`$schema: https://aka.ms/dsc/schemas/v3/bundled/config/document.json
+imports:
+- path: contoso.dsc.functions.yaml
+  version: '^1.1'
+resources:
+- name: test
+  type: Microsoft.DSC.Debug/Echo
+  properties:
+    output: "[Contoso.Greet('world')]"
+"@
+        Set-Content -Path $testdrive/config.dsc.yaml -Value $configYaml
+        $out = dsc -l trace config get -f $testdrive/config.dsc.yaml 2>$testdrive/error.log | ConvertFrom-Json
+        $LASTEXITCODE | Should -Be 0 -Because (Get-Content $testdrive/error.log | Out-String)
+        $out.results[0].result.actualState.output | Should -BeExactly 'Hello world'
+    }
+
+    It 'importing a function library with a conflicting namespace fails' {
+        $libraryYaml = @"
+version: 1.0.0
+functions:
+- namespace: Contoso
+  members:
+    Greet:
+      output:
+        type: string
+        value: "hello"
+"@
+        Set-Content -Path $testdrive/conflict.dsc.functions.yaml -Value $libraryYaml
+        $configYaml = @"
+`$schema: https://aka.ms/dsc/schemas/v3/bundled/config/document.json
+imports:
+- path: conflict.dsc.functions.yaml
+functions:
+- namespace: Contoso
+  members:
+    Other:
+      output:
+        type: string
+        value: "other"
+resources:
+- name: test
+  type: Microsoft.DSC.Debug/Echo
+  properties:
+    output: "[Contoso.Other()]"
+"@
+        Set-Content -Path $testdrive/conflict.dsc.yaml -Value $configYaml
+        dsc config get -f $testdrive/conflict.dsc.yaml 2>$testdrive/error.log | Out-Null
+        $LASTEXITCODE | Should -Be 2 -Because (Get-Content $testdrive/error.log | Out-String)
+        (Get-Content $testdrive/error.log -Raw) | Should -BeLike "*User function namespace 'Contoso'*" -Because (Get-Content $testdrive/error.log | Out-String)
+    }
 }
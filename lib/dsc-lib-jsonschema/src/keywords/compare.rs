@@ -0,0 +1,136 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use std::borrow::Cow;
+
+use jsonschema::{Keyword, ValidationError, paths::Location};
+use referencing::Resource;
+use rust_i18n::t;
+use schemars::{Schema, JsonSchema, SchemaGenerator, generate::SchemaSettings, json_schema};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Defines how DSC compares the desired and actual values of a property during a synthetic test.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CompareKind {
+    /// Compares arrays as sets, ignoring the order and duplicate items.
+    Set,
+    /// Compares strings, including the string items of arrays, ignoring case.
+    CaseInsensitive,
+    /// Compares arrays by checking that every desired item is in the actual array.
+    Subset,
+    /// Skips the property when comparing the states.
+    Ignore,
+}
+
+impl CompareKind {
+    /// Returns the comparison a (sub)schema declares with the `x-dsc-compare` keyword, if any.
+    ///
+    /// An unknown comparison is ignored here. Validators built with
+    /// [`with_dsc_keywords()`] reject schemas that declare one.
+    ///
+    /// [`with_dsc_keywords()`]: crate::keywords::DscValidationOptionsExtensions::with_dsc_keywords
+    #[must_use]
+    pub fn from_schema(schema: &Value) -> Option<Self> {
+        let value = schema.get(CompareKeyword::KEYWORD_NAME)?;
+        Self::deserialize(value).ok()
+    }
+}
+
+/// Defines the `x-dsc-compare` keyword.
+///
+/// This keyword declares how DSC compares the desired and actual values of a property when it
+/// tests an instance of a resource that doesn't implement the `test` operation itself. Without
+/// the keyword, DSC compares arrays independent of order but including duplicates and compares
+/// strings case-sensitively.
+///
+/// For example, in the following schema, DSC ignores the casing of the `state` property and
+/// the order and duplicates of the `tags` property:
+///
+/// ```json
+/// {
+///     "type": "object",
+///     "properties": {
+///         "state": { "type": "string", "x-dsc-compare": "caseInsensitive" },
+///         "tags": { "type": "array", "x-dsc-compare": "set" }
+///     }
+/// }
+/// ```
+#[derive(Serialize, Deserialize)]
+pub struct CompareKeyword(CompareKind);
+
+impl CompareKeyword {
+    /// Defines the property name for the keyword.
+    pub const KEYWORD_NAME: &str = "x-dsc-compare";
+    /// Defines the canonical `$id` URI for the keyword.
+    pub const KEYWORD_ID: &str = "https://raw.githubusercontent.com/PowerShell/DSC/main/schemas/keywords/x-dsc-compare.json";
+    /// Defines the meta schema used to validate the keyword's own schema definition.
+    pub const META_SCHEMA: &str = "https://json-schema.org/draft/2020-12/schema";
+
+    /// Defines the factory function [`jsonschema`] requires for registering a custom keyword.
+    ///
+    /// # Errors
+    ///
+    /// The function returns a [`ValidationError`] when the value of the keyword isn't one of the
+    /// supported comparisons.
+    #[allow(clippy::result_large_err)]
+    pub fn keyword_factory<'a>(
+        _parent: &'a Map<String, Value>,
+        value: &'a Value,
+        _path: Location,
+    ) -> Result<Box<dyn for<'instance> Keyword<'instance>>, ValidationError<'a>> {
+        match CompareKind::deserialize(value) {
+            Ok(kind) => Ok(Box::new(Self(kind))),
+            Err(_) => Err(ValidationError::custom(
+                t!("keywords.compare.factory_error_invalid_value"),
+            )),
+        }
+    }
+
+    /// Returns the default representation of the JSON Schema for the keyword.
+    #[must_use]
+    pub fn default_schema() -> Schema {
+        let generator = &mut SchemaGenerator::new(
+            SchemaSettings::draft2020_12()
+        );
+
+        Self::json_schema(generator)
+    }
+
+    /// Returns the default schema for the keyword as a [`Resource`] to register with a
+    /// [`jsonschema::Validator`].
+    #[must_use]
+    pub fn default_schema_resource() -> Resource {
+        Resource::from_contents(Self::default_schema().to_value())
+    }
+}
+
+impl JsonSchema for CompareKeyword {
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "$schema": Self::META_SCHEMA,
+            "$id": Self::KEYWORD_ID,
+            "title": t!("keywords.compare.title"),
+            "description": t!("keywords.compare.description"),
+            "type": "string",
+            "enum": ["set", "caseInsensitive", "subset", "ignore"]
+        })
+    }
+
+    fn schema_name() -> Cow<'static, str> {
+        Self::KEYWORD_ID.into()
+    }
+}
+
+impl<'i> Keyword<'i> for CompareKeyword {
+    fn validate(
+            &self,
+            _: &'i Value,
+        ) -> Result<(), ValidationError<'i>> {
+        Ok(())
+    }
+    fn is_valid(&self, _: &'i Value) -> bool {
+        true
+    }
+}
@@ -0,0 +1,12 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+//! Defines the custom JSON Schema keywords DSC recognizes in resource instance schemas.
+//!
+//! The keywords use the `x-dsc-` prefix so that JSON Schema implementations that don't
+//! understand them can safely ignore them as unknown annotations.
+
+mod compare;
+pub use compare::{CompareKeyword, CompareKind};
+mod validation_options_extensions;
+pub use validation_options_extensions::{DscValidationOptionsExtensions, DSC_KEYWORDS_REGISTRY};
@@ -0,0 +1,43 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use std::sync::LazyLock;
+
+use jsonschema::ValidationOptions;
+use referencing::Registry;
+
+use crate::keywords::CompareKeyword;
+
+/// A static registry containing the schema resources for the DSC keywords.
+///
+/// This registry is lazily initialized on first use and lives for the duration of the program.
+/// It is used by the [`DscValidationOptionsExtensions`] trait methods to register schema
+/// resources with `with_registry()`.
+pub static DSC_KEYWORDS_REGISTRY: LazyLock<Registry<'static>> = LazyLock::new(|| {
+    Registry::new()
+        .add(CompareKeyword::KEYWORD_ID, CompareKeyword::default_schema_resource())
+        .unwrap_or_else(|e| panic!("invalid URI for {}: {e}", CompareKeyword::KEYWORD_ID))
+        .prepare()
+        .unwrap_or_else(|e| panic!("failed to prepare DSC_KEYWORDS_REGISTRY: {e}"))
+});
+
+/// Defines extension methods to the [`jsonschema::ValidationOptions`] to simplify registering the
+/// DSC keywords.
+pub trait DscValidationOptionsExtensions<'i> {
+    /// Registers every DSC keyword for use with a [`jsonschema::Validator`].
+    ///
+    /// This function registers each of the DSC keywords with the [`with_keyword()`] builder
+    /// method and adds the [`DSC_KEYWORDS_REGISTRY`] containing all schema resources. Building
+    /// a validator for a schema that uses a keyword with an invalid value fails.
+    ///
+    /// [`with_keyword()`]: ValidationOptions::with_keyword
+    fn with_dsc_keywords(self) -> ValidationOptions<'i>;
+}
+
+impl<'i> DscValidationOptionsExtensions<'i> for ValidationOptions<'i> {
+    fn with_dsc_keywords(self) -> ValidationOptions<'i> {
+        self
+            .with_keyword(CompareKeyword::KEYWORD_NAME, CompareKeyword::keyword_factory)
+            .with_registry(&DSC_KEYWORDS_REGISTRY)
+    }
+}
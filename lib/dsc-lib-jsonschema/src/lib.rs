@@ -9,6 +9,7 @@ use rust_i18n::i18n;
 pub mod macros;
 
 pub mod dsc_repo;
+pub mod keywords;
 pub mod schema_utility_extensions;
 pub mod transforms;
 pub mod vscode;
@@ -0,0 +1,79 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use pretty_assertions::assert_eq;
+use rust_i18n::t;
+use serde_json::json;
+
+use crate::keywords::{CompareKeyword, CompareKind, DscValidationOptionsExtensions};
+
+#[test] fn meta_schema_is_valid() {
+    let schema = CompareKeyword::default_schema();
+    let result = jsonschema::meta::validate(
+        schema.as_value()
+    );
+    assert!(result.is_ok(), "Unexpected error: {}", result.unwrap_err());
+}
+
+#[test] fn unknown_value_is_invalid() {
+    let validation_error = jsonschema::options().with_keyword(
+        CompareKeyword::KEYWORD_NAME,
+        CompareKeyword::keyword_factory
+    ).build(&json!({
+        "x-dsc-compare": "fuzzy"
+    })).unwrap_err().to_owned();
+
+    assert_eq!(
+        validation_error.schema_path().as_str(),
+        "/x-dsc-compare"
+    );
+
+    assert_eq!(
+        format!("{validation_error}"),
+        t!("keywords.compare.factory_error_invalid_value")
+    );
+}
+
+#[test] fn known_value_is_valid() {
+    let validator = jsonschema::options().with_keyword(
+        CompareKeyword::KEYWORD_NAME,
+        CompareKeyword::keyword_factory
+    ).build(&json!({
+        "x-dsc-compare": "caseInsensitive"
+    }));
+
+    assert!(validator.is_ok());
+}
+
+#[test] fn extension_registers_keyword() {
+    let result = jsonschema::options().with_dsc_keywords().build(&json!({
+        "type": "object",
+        "properties": {
+            "state": { "type": "string", "x-dsc-compare": "fuzzy" }
+        }
+    }));
+    assert!(result.is_err());
+
+    let validator = jsonschema::options().with_dsc_keywords().build(&json!({
+        "type": "object",
+        "properties": {
+            "state": { "type": "string", "x-dsc-compare": "caseInsensitive" }
+        }
+    })).unwrap();
+    assert!(validator.is_valid(&json!({"state": "Running"})));
+}
+
+#[test] fn keyword_schema_resolves_from_registry() {
+    let validator = jsonschema::options().with_dsc_keywords().build(&json!({
+        "$ref": CompareKeyword::KEYWORD_ID
+    })).unwrap();
+    assert!(validator.is_valid(&json!("set")));
+    assert!(!validator.is_valid(&json!("fuzzy")));
+}
+
+#[test] fn kind_from_schema() {
+    assert_eq!(CompareKind::from_schema(&json!({"x-dsc-compare": "set"})), Some(CompareKind::Set));
+    assert_eq!(CompareKind::from_schema(&json!({"x-dsc-compare": "subset"})), Some(CompareKind::Subset));
+    assert_eq!(CompareKind::from_schema(&json!({"x-dsc-compare": "ignore"})), Some(CompareKind::Ignore));
+    assert_eq!(CompareKind::from_schema(&json!({"type": "string"})), None);
+}
@@ -0,0 +1,6 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+//! Unit tests for [`dsc-lib-jsonschema::keywords`]
+
+#[cfg(test)] mod compare;
@@ -13,5 +13,6 @@
 //! of the modules from the rest of the source tree.
 
 #[cfg(test)] mod dsc_repo;
+#[cfg(test)] mod keywords;
 #[cfg(test)] mod schema_utility_extensions;
 #[cfg(test)] mod vscode;
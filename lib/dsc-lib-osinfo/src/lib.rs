@@ -1,41 +1,96 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 use std::string::ToString;
 use version_compare::Cmp;
 
 /// Returns information about the operating system.
-#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
+#[schemars(
+    title = "OsInfo",
+    description = "Returns information about the operating system.\n\nhttps://learn.microsoft.com/powershell/dsc/reference/microsoft/osinfo/resource",
+    extend(
+        "$id" = "https://raw.githubusercontent.com/PowerShell/DSC/main/schemas/resources/Microsoft/OSInfo/v0.1.0/schema.json",
+        "markdownDescription" = "The `Microsoft/OSInfo` resource enables you to assert whether a machine meets criteria related to\nthe operating system. The resource is only capable of assertions. It doesn't implement the set\noperation and can't configure the operating system.\n\n[Online documentation][01]\n\n[01]: https://learn.microsoft.com/powershell/dsc/reference/microsoft/osinfo/resource"
+    )
+)]
 pub struct OsInfo {
+    /// Defines whether the operating system is Linux, macOS, or Windows.
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "Operating system family",
+        description = "Defines whether the operating system is Linux, macOS, or Windows.\n\nhttps://learn.microsoft.com/powershell/dsc/reference/microsoft/osinfo/resource#family",
+        extend("markdownDescription" = "Defines whether the operating system is Linux, macOS, or Windows.\n\n[Online documentation][01]\n\n[01]: https://learn.microsoft.com/powershell/dsc/reference/microsoft/osinfo/resource#family")
+    )]
     family: Option<Family>,
     /// Defines the version of the operating system as a string.
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "Operating system version",
+        description = "Defines the version of the operating system as a string.\n\nWhen used with the test operation, the version string may include a comparison operator prefix to compare the actual version against a constraint. Supported operators are: `>`, `<`, `=`, `>=`, and `<=`. The operator and version may be separated by optional whitespace, for example `>= 10.0`. Without an operator, the comparison is an exact string match.\n\nhttps://learn.microsoft.com/powershell/dsc/reference/microsoft/osinfo/resource#version",
+        extend("markdownDescription" = "Defines the version of the operating system as a string.\n\nWhen used with the **test** operation, the version string may include a comparison operator prefix to compare the actual version against a constraint. Supported operators are: `>`, `<`, `=`, `>=`, and `<=`. The operator and version may be separated by optional whitespace, for example `>= 10.0`. Without an operator, the comparison is an exact string match.\n\n[Online documentation][01]\n\n[01]: https://learn.microsoft.com/powershell/dsc/reference/microsoft/osinfo/resource#version")
+    )]
     version: Option<String>,
     /// Defines the Windows operating system edition, like `Windows 11` or `Windows Server 2016`.
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "Windows edition",
+        description = "Defines the operating system edition, like 'Windows 11' or 'Windows Server 2016'.\n\nhttps://learn.microsoft.com/powershell/dsc/reference/microsoft/osinfo/resource#edition",
+        extend("markdownDescription" = "Defines the operating system edition, like `Windows 11` or `Windows Server 2016`.\n\n[Online documentation][01]\n\n[01]: https://learn.microsoft.com/powershell/dsc/reference/microsoft/osinfo/resource#edition")
+    )]
     edition: Option<String>,
     /// Defines the codename for the operating system as returned from `lsb_release --codename`.
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "Linux codename",
+        description = "Defines the codename for the operating system as returned from 'lsb_release --codename'.\n\nhttps://learn.microsoft.com/powershell/dsc/reference/microsoft/osinfo/resource#codename",
+        extend("markdownDescription" = "Defines the codename for the operating system as returned from `lsb_release --codename`.\n\n[Online documentation][01]\n\n[01]: https://learn.microsoft.com/powershell/dsc/reference/microsoft/osinfo/resource#codename")
+    )]
     codename: Option<String>,
+    /// Defines whether the operating system is a 32-bit or 64-bit operating system.
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "Operating system bitness",
+        description = "Defines whether the operating system is a 32-bit or 64-bit operating system.\n\nhttps://learn.microsoft.com/powershell/dsc/reference/microsoft/osinfo/resource#bitness",
+        extend("markdownDescription" = "Defines whether the operating system is a 32-bit or 64-bit operating system.\n\n[Online documentation][01]\n\n[01]: https://learn.microsoft.com/powershell/dsc/reference/microsoft/osinfo/resource#bitness")
+    )]
     bitness: Option<i32>,
     /// Defines the processor architecture as reported by `uname -m` on the operating system.
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "Processor architecture",
+        description = "Defines the processor architecture as reported by 'uname -m' on the operating system.\n\nhttps://learn.microsoft.com/powershell/dsc/reference/microsoft/osinfo/resource#architecture",
+        extend("markdownDescription" = "Defines the processor architecture as reported by `uname -m` on the operating system.\n\n[Online documentation][01]\n\n[01]: https://learn.microsoft.com/powershell/dsc/reference/microsoft/osinfo/resource#architecture")
+    )]
     architecture: Option<String>,
     /// Support returning generated name for the OSInfo instance for export.
     #[serde(rename = "_name", skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "Instance name",
+        description = "Returns the name of the OSInfo instance.\n\nhttps://learn.microsoft.com/powershell/dsc/reference/microsoft/osinfo/resource#name",
+        extend(
+            "readOnly" = true,
+            "markdownDescription" = "Returns the name of the OSInfo instance.\n\n[Online documentation][01]\n\n[01]: https://learn.microsoft.com/powershell/dsc/reference/microsoft/osinfo/resource#name"
+        )
+    )]
     name: Option<String>,
     /// Indicates whether the resource is in the desired state. Only emitted by the test operation.
     #[serde(rename = "_inDesiredState", skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        title = "In desired state",
+        description = "Indicates whether the resource instance is in the desired state. Only present in the output of the test operation.",
+        extend("readOnly" = true)
+    )]
     in_desired_state: Option<bool>,
 }
 
 /// Defines whether the operating system is Linux, macOS, or Windows.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[schemars(inline)]
 pub enum Family {
     Linux,
     #[serde(rename = "macOS")]
@@ -91,6 +146,12 @@ impl OsInfo {
             in_desired_state: None,
         }
     }
+
+    /// Whether the OS info is in the desired state, only set by the test operation.
+    #[must_use]
+    pub fn in_desired_state(&self) -> Option<bool> {
+        self.in_desired_state
+    }
 }
 
 /// Parse the optional comparison operator prefix from a version constraint string.
@@ -180,7 +241,14 @@ fn version_matches(constraint: &str, actual: &str) -> bool {
 pub fn perform_test(input_json: &str) -> Result<OsInfo, String> {
     let desired: OsInfo = serde_json::from_str(input_json)
         .map_err(|e| format!("Failed to parse test input as JSON: {e}"))?;
+    Ok(test_os_info(&desired))
+}
 
+/// Compare the desired OS info against the actual OS info.
+///
+/// Returns the actual OS info with `_inDesiredState` set.
+#[must_use]
+pub fn test_os_info(desired: &OsInfo) -> OsInfo {
     // name is ignored for test since it's only generated for export and not a property of the actual OS state.
     let actual = OsInfo::new(false);
 
@@ -222,5 +290,5 @@ pub fn perform_test(input_json: &str) -> Result<OsInfo, String> {
             in_desired_state = false;
         }
 
-    Ok(OsInfo { in_desired_state: Some(in_desired_state), ..actual })
+    OsInfo { in_desired_state: Some(in_desired_state), ..actual }
 }
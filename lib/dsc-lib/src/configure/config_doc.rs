@@ -5,10 +5,11 @@ use chrono::{DateTime, Local};
 use rust_i18n::t;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use serde_json::{Map, Value};
+use serde_json::{Map, Number, Value};
 use std::{collections::HashMap, fmt::Display};
 
 use crate::{
+    configure::function_library::FunctionImport,
     schemas::{
         dsc_repo::DscRepoSchema,
         transforms::{idiomaticize_externally_tagged_enum, idiomaticize_string_enum}
@@ -321,6 +322,9 @@ pub struct Configuration {
     pub execution_information: Option<ExecutionInformation>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub functions: Option<Vec<UserFunction>>,
+    /// Function libraries whose user functions are available to the document
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub imports: Option<Vec<FunctionImport>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<Metadata>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -343,13 +347,19 @@ pub struct Parameter {
     #[serde(rename = "allowedValues", skip_serializing_if = "Option::is_none")]
     pub allowed_values: Option<Vec<Value>>,
     #[serde(rename = "minValue", skip_serializing_if = "Option::is_none")]
-    pub min_value: Option<i64>,
+    pub min_value: Option<Number>,
     #[serde(rename = "maxValue", skip_serializing_if = "Option::is_none")]
-    pub max_value: Option<i64>,
+    pub max_value: Option<Number>,
     #[serde(rename = "minLength", skip_serializing_if = "Option::is_none")]
     pub min_length: Option<i64>,
     #[serde(rename = "maxLength", skip_serializing_if = "Option::is_none")]
     pub max_length: Option<i64>,
+    /// Regular expression that string values must match
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    /// JSON Schema that the value must be valid against
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<Map<String, Value>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -366,6 +376,8 @@ pub enum DataType {
     SecureString,
     #[serde(rename = "int")]
     Int,
+    #[serde(rename = "number")]
+    Number,
     #[serde(rename = "bool")]
     Bool,
     #[serde(rename = "object")]
@@ -382,6 +394,7 @@ impl Display for DataType {
             DataType::String => "string",
             DataType::SecureString => "secureString",
             DataType::Int => "int",
+            DataType::Number => "number",
             DataType::Bool => "bool",
             DataType::Object => "object",
             DataType::SecureObject => "secureObject",
@@ -476,6 +489,7 @@ impl Configuration {
             parameters: None,
             resources: Vec::new(),
             functions: None,
+            imports: None,
             variables: None,
             outputs: None,
         }
@@ -1,11 +1,13 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
+use rust_i18n::t;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
 
-use crate::dscresources::invoke_result::{GetResult, SetResult, TestResult};
+use crate::dscerror::DscError;
+use crate::dscresources::invoke_result::{GetResult, ResourceError, SetResult, TestResult};
 use crate::configure::config_doc::{Configuration, ExecutionInformation, Metadata};
 use crate::schemas::{dsc_repo::DscRepoSchema, transforms::idiomaticize_string_enum};
 use crate::types::FullyQualifiedTypeName;
@@ -19,6 +21,25 @@ pub enum MessageLevel {
     Information,
 }
 
+/// Returns an error with the error messages of a configuration operation that had errors.
+///
+/// A resource that fails during a configuration operation stops the operation and is reported in
+/// the messages of the result instead of as an error, so callers must check the result.
+///
+/// # Errors
+///
+/// Error returned if `had_errors` is `true`.
+pub fn check_resource_errors(had_errors: bool, messages: &[ResourceMessage]) -> Result<(), DscError> {
+    if !had_errors {
+        return Ok(());
+    }
+    let errors: Vec<&str> = messages.iter()
+        .filter(|message| message.level == MessageLevel::Error)
+        .map(|message| message.message.as_str())
+        .collect();
+    Err(DscError::Operation(t!("configure.config_result.resourceErrors", errors = errors.join("; ")).to_string()))
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, DscRepoSchema)]
 #[serde(deny_unknown_fields)]
 #[dsc_repo_schema(base_name = "message", folder_path = "definitions")]
@@ -28,6 +49,9 @@ pub struct ResourceMessage {
     pub resource_type: FullyQualifiedTypeName,
     pub message: String,
     pub level: MessageLevel,
+    /// The structured error reported by the resource.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ResourceError>,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, DscRepoSchema)]
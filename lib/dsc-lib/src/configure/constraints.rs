@@ -1,10 +1,13 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
-use crate::configure::config_doc::Parameter;
+use crate::configure::config_doc::{DataType, Parameter};
 use crate::DscError;
+use jsonschema::Validator;
+use regex::Regex;
 use rust_i18n::t;
-use serde_json::Value;
+use serde_json::{Number, Value};
+use std::cmp::Ordering;
 
 /// Checks that the given value matches the given parameter length constraints.
 ///
@@ -91,39 +94,42 @@ pub fn check_length(name: &str, value: &Value, constraint: &Parameter) -> Result
 ///
 /// * `DscError::Validation` if the value does not match the constraints.
 pub fn check_number_limits(name: &str, value: &Value, constraint: &Parameter) -> Result<(), DscError> {
-    if let Some(min_value) = constraint.min_value {
-        if value.is_i64() && value.as_i64().is_some() {
-            let Some(value) = value.as_i64() else {
-                return Err(DscError::Validation(t!("configure.constraints.minValueIsNull", name = name).to_string()));
-            };
-
-            if value < min_value {
+    if let Some(min_value) = &constraint.min_value {
+        match compare_number(value, min_value) {
+            Some(Ordering::Less) => {
                 return Err(DscError::Validation(t!("configure.constraints.notMinValue", name = name, min_value = min_value, value = value).to_string()));
-            }
-        }
-        else {
-            return Err(DscError::Validation(t!("configure.constraints.minValueNotInteger", name = name).to_string()));
+            },
+            None => {
+                return Err(DscError::Validation(t!("configure.constraints.minValueNotNumber", name = name).to_string()));
+            },
+            _ => {},
         }
     }
 
-    if let Some(max_value) = constraint.max_value {
-        if value.is_i64() && value.as_i64().is_some() {
-            let Some(value) = value.as_i64() else {
-                return Err(DscError::Validation(t!("configure.constraints.maxValueIsNull", name = name).to_string()));
-            };
-
-            if value > max_value {
+    if let Some(max_value) = &constraint.max_value {
+        match compare_number(value, max_value) {
+            Some(Ordering::Greater) => {
                 return Err(DscError::Validation(t!("configure.constraints.notMaxValue", name = name, max_value = max_value, value = value).to_string()));
-            }
-        }
-        else {
-            return Err(DscError::Validation(t!("configure.constraints.maxValueNotInteger", name = name).to_string()));
+            },
+            None => {
+                return Err(DscError::Validation(t!("configure.constraints.maxValueNotNumber", name = name).to_string()));
+            },
+            _ => {},
         }
     }
 
     Ok(())
 }
 
+/// Compares a parameter value to a numeric limit, as integers when both are integers so large
+/// values don't lose precision, and otherwise as floating point numbers.
+fn compare_number(value: &Value, limit: &Number) -> Option<Ordering> {
+    if let Some(value) = value.as_i64() && let Some(limit) = limit.as_i64() {
+        return Some(value.cmp(&limit));
+    }
+    value.as_f64()?.partial_cmp(&limit.as_f64()?)
+}
+
 /// Checks that the given value matches the given allowed values constraints.
 ///
 /// # Arguments
@@ -168,4 +174,90 @@ pub fn check_allowed_values(name: &str, value: &Value, constraint: &Parameter) -
     Ok(())
 }
 
+/// Checks that the given value matches the given parameter pattern constraint.
+///
+/// # Arguments
+///
+/// * `name` - The name of the parameter.
+/// * `value` - The value of the parameter.
+/// * `constraint` - The constraints on the parameter.
+///
+/// # Returns
+///
+/// * `Ok(())` if the value matches the constraints.
+/// * `Err(DscError::Validation)` if the value does not match the constraints.
+///
+/// # Errors
+///
+/// * `DscError::Validation` if the pattern is invalid or the value does not match it.
+pub fn check_pattern(name: &str, value: &Value, constraint: &Parameter) -> Result<(), DscError> {
+    let Some(pattern) = &constraint.pattern else {
+        return Ok(());
+    };
+
+    let regex = match Regex::new(pattern) {
+        Ok(regex) => regex,
+        Err(err) => {
+            return Err(DscError::Validation(t!("configure.constraints.invalidPattern", name = name, pattern = pattern, error = err).to_string()));
+        }
+    };
+
+    let Some(value) = value.as_str() else {
+        return Err(DscError::Validation(t!("configure.constraints.patternNotString", name = name).to_string()));
+    };
+
+    if !regex.is_match(value) {
+        return Err(DscError::Validation(t!("configure.constraints.notMatchPattern", name = name, pattern = pattern).to_string()));
+    }
+
+    Ok(())
+}
+
+/// Checks that the given value is valid against the parameter JSON Schema constraint.
+///
+/// Every validation error is reported with the JSON pointer of the offending field. Values of
+/// secure parameters are masked in the error messages.
+///
+/// # Arguments
+///
+/// * `name` - The name of the parameter.
+/// * `value` - The value of the parameter.
+/// * `constraint` - The constraints on the parameter.
+///
+/// # Returns
+///
+/// * `Ok(())` if the value matches the constraints.
+/// * `Err(DscError::Validation)` if the value does not match the constraints.
+///
+/// # Errors
+///
+/// * `DscError::Validation` if the schema is invalid or the value isn't valid against it.
+pub fn check_schema(name: &str, value: &Value, constraint: &Parameter) -> Result<(), DscError> {
+    let Some(schema) = &constraint.schema else {
+        return Ok(());
+    };
+
+    let schema = Value::Object(schema.clone());
+    let validator = match Validator::new(&schema) {
+        Ok(validator) => validator,
+        Err(err) => {
+            return Err(DscError::Validation(t!("configure.constraints.invalidSchema", name = name, error = err).to_string()));
+        }
+    };
+
+    let is_secure = matches!(constraint.parameter_type, DataType::SecureString | DataType::SecureObject);
+    let errors: Vec<String> = validator.iter_errors(value).map(|err| {
+        let message = if is_secure { err.masked().to_string() } else { err.to_string() };
+        let pointer = err.instance_path().to_string();
+        let pointer = if pointer.is_empty() { "/".to_string() } else { pointer };
+        t!("configure.constraints.schemaViolation", pointer = pointer, message = message).to_string()
+    }).collect();
+
+    if !errors.is_empty() {
+        return Err(DscError::Validation(t!("configure.constraints.notValidAgainstSchema", name = name, errors = errors.join("; ")).to_string()));
+    }
+
+    Ok(())
+}
+
 // TODO: check nullable
@@ -24,19 +24,38 @@ pub(super) fn apply_export_filter(instances: &mut Vec<Value>, filters: &[Map<Str
     }
 
     let original_count = instances.len();
-    instances.retain_mut(|instance| {
-        if !instance_matches_filters(instance, filters) {
-            return false;
-        }
-        let matching: Vec<&Map<String, Value>> = instance.as_object()
-            .map(|instance_obj| filters.iter().filter(|filter| instance_matches_filter(instance_obj, filter)).collect())
-            .unwrap_or_default();
-        filter_array_elements(instance, &matching);
-        true
-    });
+    instances.retain_mut(|instance| apply_export_filter_to_instance(instance, filters));
     debug!("{}", t!("configure.export_filter.filteredInstances", original = original_count, retained = instances.len()));
 }
 
+/// Apply an export filter to a single exported instance, so instances can be filtered as they're
+/// exported.
+///
+/// Array properties targeted by element filters are rewritten the same way as
+/// [`apply_export_filter`].
+///
+/// # Arguments
+///
+/// * `instance` - The exported instance to filter.
+/// * `filters` - The filter objects derived from the export input.
+///
+/// # Returns
+///
+/// `true` if the instance should be retained.
+pub(super) fn apply_export_filter_to_instance(instance: &mut Value, filters: &[Map<String, Value>]) -> bool {
+    if filters.is_empty() {
+        return true;
+    }
+    if !instance_matches_filters(instance, filters) {
+        return false;
+    }
+    let matching: Vec<&Map<String, Value>> = instance.as_object()
+        .map(|instance_obj| filters.iter().filter(|filter| instance_matches_filter(instance_obj, filter)).collect())
+        .unwrap_or_default();
+    filter_array_elements(instance, &matching);
+    true
+}
+
 /// Rewrite the instance's array properties targeted by element filters, retaining only the
 /// elements that match any of the element filter objects (logical OR).
 fn filter_array_elements(instance: &mut Value, filters: &[&Map<String, Value>]) {
@@ -284,6 +303,16 @@ mod tests {
         assert!(instance_matches_filters(&json!({"name": "anything"}), &filters));
     }
 
+    #[test]
+    fn apply_export_filter_to_instance_matches_apply_export_filter() {
+        let filters = to_filters(json!([{ "name": "*ssh*", "features": [{ "featureName": "a*" }] }]));
+        let mut instance = json!({"name": "sshd", "features": [{"featureName": "alpha"}, {"featureName": "beta"}]});
+        assert!(apply_export_filter_to_instance(&mut instance, &filters));
+        assert_eq!(instance["features"], json!([{"featureName": "alpha"}]));
+        assert!(!apply_export_filter_to_instance(&mut json!({"name": "spooler"}), &filters));
+        assert!(apply_export_filter_to_instance(&mut json!({"name": "spooler"}), &[]));
+    }
+
     #[test]
     fn apply_export_filter_retains_matching() {
         let mut instances = vec![
@@ -0,0 +1,266 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use crate::configure::config_doc::{UserFunction, UserFunctionDefinition};
+use crate::dscerror::DscError;
+use crate::schemas::dsc_repo::DscRepoSchema;
+use crate::types::{SemanticVersion, SemanticVersionReq};
+use crate::util::{get_setting, parse_input_to_json};
+use rust_i18n::t;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{debug, info};
+
+/// Environment variable set by the CLI to the directory of the configuration document.
+pub(crate) const DSC_CONFIG_ROOT: &str = "DSC_CONFIG_ROOT";
+
+/// A file containing user function namespaces that can be shared across configuration documents.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, DscRepoSchema)]
+#[serde(deny_unknown_fields)]
+#[dsc_repo_schema(base_name = "library", folder_path = "definitions/functions/user")]
+pub struct FunctionLibrary {
+    #[serde(rename = "$schema", skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+    /// The version of the library, used to satisfy the `version` requirement of an import
+    pub version: SemanticVersion,
+    /// The user function namespaces defined by the library
+    pub functions: Vec<UserFunction>,
+}
+
+/// A reference to a function library from a configuration document.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, DscRepoSchema)]
+#[serde(deny_unknown_fields)]
+#[dsc_repo_schema(base_name = "document.import", folder_path = "config")]
+pub struct FunctionImport {
+    /// Path to the library file, relative paths are resolved against the configuration root
+    /// and then the directories of the `functionLibraryPath` setting
+    pub path: String,
+    /// Optional requirement the version of the library must satisfy
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<SemanticVersionReq>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct FunctionLibraryPathSetting {
+    /// array of directories that DSC should search for function library files
+    directories: Vec<String>,
+}
+
+/// Tracks which source defined each user function namespace so conflicts can be reported.
+#[derive(Debug, Default)]
+pub struct UserFunctionRegistry {
+    namespaces: HashMap<String, String>,
+    loaded_libraries: Vec<PathBuf>,
+}
+
+impl UserFunctionRegistry {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the user function namespaces from a source to the map of user functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `functions` - The user function namespaces to add.
+    /// * `source` - A description of where the functions were defined, used in error messages.
+    /// * `user_functions` - The map of user functions to add to.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a namespace is already defined by a different source
+    /// or if a function is already defined.
+    pub fn add(&mut self, functions: &[UserFunction], source: &str, user_functions: &mut HashMap<String, UserFunctionDefinition>) -> Result<(), DscError> {
+        for user_function in functions {
+            if let Some(existing_source) = self.namespaces.get(&user_function.namespace)
+                && existing_source != source {
+                return Err(DscError::Validation(t!("configure.function_library.namespaceConflict", namespace = user_function.namespace, source = source, existing = existing_source).to_string()));
+            }
+            self.namespaces.insert(user_function.namespace.clone(), source.to_string());
+
+            for (function_name, function_definition) in &user_function.members {
+                let full_name = format!("{}.{}", user_function.namespace, function_name);
+                if user_functions.contains_key(&full_name) {
+                    return Err(DscError::Validation(t!("configure.mod.userFunctionAlreadyDefined", name = function_name, namespace = user_function.namespace).to_string()));
+                }
+                debug!("{}", t!("configure.mod.addingUserFunction", name = full_name));
+                user_functions.insert(full_name, function_definition.clone());
+            }
+        }
+        Ok(())
+    }
+
+    /// Load the function libraries referenced by imports and add their functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `imports` - The libraries to import.
+    /// * `user_functions` - The map of user functions to add to.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a library can't be found or read, doesn't satisfy the
+    /// version requirement, or defines conflicting functions.
+    pub fn import(&mut self, imports: &[FunctionImport], user_functions: &mut HashMap<String, UserFunctionDefinition>) -> Result<(), DscError> {
+        if imports.is_empty() {
+            return Ok(());
+        }
+
+        let search_directories = get_library_search_directories();
+        for import in imports {
+            let path = resolve_library_path(&import.path, &search_directories)?;
+            let library = load_function_library(&path)?;
+            if let Some(version_req) = &import.version
+                && !version_req.matches(&library.version) {
+                return Err(DscError::Validation(t!("configure.function_library.versionNotSatisfied", path = path.to_string_lossy(), version = library.version, required_version = version_req).to_string()));
+            }
+
+            if self.loaded_libraries.contains(&path) {
+                debug!("{}", t!("configure.function_library.alreadyImported", path = path.to_string_lossy()));
+                continue;
+            }
+
+            info!("{}", t!("configure.function_library.importing", path = path.to_string_lossy(), version = library.version));
+            self.add(&library.functions, &path.to_string_lossy(), user_functions)?;
+            self.loaded_libraries.push(path);
+        }
+        Ok(())
+    }
+}
+
+/// Read and parse a function library file, which can be JSON or YAML.
+///
+/// # Arguments
+///
+/// * `path` - The path to the library file.
+///
+/// # Errors
+///
+/// This function will return an error if the file can't be read or isn't a valid library.
+pub fn load_function_library(path: &Path) -> Result<FunctionLibrary, DscError> {
+    let content = fs::read_to_string(path).map_err(|err| DscError::Validation(t!("configure.function_library.failedToRead", path = path.to_string_lossy(), error = err).to_string()))?;
+    let json = parse_input_to_json(&content)?;
+    serde_json::from_str::<FunctionLibrary>(&json).map_err(|err| DscError::Validation(t!("configure.function_library.invalidLibrary", path = path.to_string_lossy(), error = err).to_string()))
+}
+
+fn resolve_library_path(path: &str, search_directories: &[PathBuf]) -> Result<PathBuf, DscError> {
+    let library_path = Path::new(path);
+    let candidates: Vec<PathBuf> = if library_path.is_absolute() {
+        vec![library_path.to_path_buf()]
+    } else {
+        search_directories.iter().map(|directory| directory.join(library_path)).collect()
+    };
+
+    for candidate in candidates {
+        if candidate.is_file() {
+            return Ok(fs::canonicalize(&candidate).unwrap_or(candidate));
+        }
+    }
+
+    Err(DscError::Validation(t!("configure.function_library.notFound", path = path).to_string()))
+}
+
+/// Returns the directories searched for relative library paths, in order: the configuration
+/// root, the `functionLibraryPath` setting (policy takes precedence), and the current directory.
+fn get_library_search_directories() -> Vec<PathBuf> {
+    let mut directories: Vec<PathBuf> = vec![];
+    if let Ok(config_root) = env::var(DSC_CONFIG_ROOT) {
+        directories.push(PathBuf::from(config_root));
+    }
+
+    if let Ok(value) = get_setting("functionLibraryPath") {
+        let setting = if value.policy.is_null() { value.setting } else { value.policy };
+        match serde_json::from_value::<FunctionLibraryPathSetting>(setting) {
+            Ok(setting) => directories.extend(setting.directories.iter().map(PathBuf::from)),
+            Err(err) => debug!("{}", t!("configure.function_library.invalidSetting", error = err)),
+        }
+    }
+
+    if let Ok(current_directory) = env::current_dir() {
+        directories.push(current_directory);
+    }
+    directories
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configure::config_doc::Configuration;
+
+    fn write_library(dir: &Path, name: &str, content: &str) -> PathBuf {
+        let path = dir.join(name);
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    const LIBRARY: &str = r#"
+version: 1.2.0
+functions:
+- namespace: Contoso
+  members:
+    greet:
+      parameters:
+      - name: who
+        type: string
+      output:
+        type: string
+        value: "[concat('Hello ', parameters('who'))]"
+"#;
+
+    #[test]
+    fn import_library() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_library(dir.path(), "contoso.dsc.functions.yaml", LIBRARY);
+        let mut user_functions = HashMap::new();
+        let mut registry = UserFunctionRegistry::new();
+        let imports = vec![FunctionImport { path: path.to_string_lossy().to_string(), version: Some(SemanticVersionReq::parse("^1.1").unwrap()) }];
+        registry.import(&imports, &mut user_functions).unwrap();
+        assert!(user_functions.contains_key("Contoso.greet"));
+    }
+
+    #[test]
+    fn import_library_version_not_satisfied() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_library(dir.path(), "contoso.dsc.functions.yaml", LIBRARY);
+        let mut user_functions = HashMap::new();
+        let mut registry = UserFunctionRegistry::new();
+        let imports = vec![FunctionImport { path: path.to_string_lossy().to_string(), version: Some(SemanticVersionReq::parse(">=2.0").unwrap()) }];
+        assert!(registry.import(&imports, &mut user_functions).is_err());
+    }
+
+    #[test]
+    fn namespace_conflict_between_document_and_library() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_library(dir.path(), "contoso.dsc.functions.yaml", LIBRARY);
+        let config: Configuration = serde_yaml::from_str(r#"
+$schema: https://aka.ms/dsc/schemas/v3/bundled/config/document.json
+functions:
+- namespace: Contoso
+  members:
+    other:
+      output:
+        type: string
+        value: "other"
+resources: []
+"#).unwrap();
+        let mut user_functions = HashMap::new();
+        let mut registry = UserFunctionRegistry::new();
+        let imports = vec![FunctionImport { path: path.to_string_lossy().to_string(), version: None }];
+        registry.import(&imports, &mut user_functions).unwrap();
+        let result = registry.add(config.functions.as_ref().unwrap(), "configuration", &mut user_functions);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn missing_library() {
+        let mut user_functions = HashMap::new();
+        let mut registry = UserFunctionRegistry::new();
+        let imports = vec![FunctionImport { path: "does_not_exist.dsc.functions.yaml".to_string(), version: None }];
+        assert!(registry.import(&imports, &mut user_functions).is_err());
+    }
+}
@@ -0,0 +1,299 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use crate::dscerror::DscError;
+use crate::dscresources::dscresource::DscResource;
+use crate::types::{FullyQualifiedTypeName, ResourceVersion, ResourceVersionReq};
+use crate::util::get_file_sha256;
+use rust_i18n::t;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs::{read, write};
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+/// The extension of the lock file written next to a configuration document.
+pub const LOCK_FILE_EXTENSION: &str = ".dsc.lock.json";
+
+/// The version of the lock file format.
+const LOCK_VERSION: u32 = 1;
+
+/// The extensions of configuration documents replaced by the lock file extension.
+const CONFIG_EXTENSIONS: [&str; 6] = [".dsc.yaml", ".dsc.yml", ".dsc.json", ".yaml", ".yml", ".json"];
+
+/// The exact resources a configuration document resolved to, like `Cargo.lock` for a crate.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct ConfigurationLock {
+    /// The version of the lock file format.
+    pub lock_version: u32,
+    /// The resources and adapters used by the configuration.
+    pub resources: Vec<LockedResource>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct LockedResource {
+    #[serde(rename = "type")]
+    pub type_name: FullyQualifiedTypeName,
+    pub version: ResourceVersion,
+    /// The adapter the resource requires, which is also locked.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub require_adapter: Option<FullyQualifiedTypeName>,
+    /// The version of the adapter the resource was resolved with.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub require_adapter_version: Option<ResourceVersion>,
+    /// The path of the manifest the resource was discovered from.
+    pub manifest_path: String,
+    /// The SHA-256 digest of the manifest, if the resource was discovered from a file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manifest_sha256: Option<String>,
+}
+
+impl LockedResource {
+    /// Lock a discovered resource and, for an adapted resource, the version of its adapter.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the manifest exists but can't be read.
+    pub fn from_resource(resource: &DscResource, adapter: Option<&DscResource>) -> Result<Self, DscError> {
+        Ok(LockedResource {
+            type_name: resource.type_name.clone(),
+            version: resource.version.clone(),
+            require_adapter: resource.require_adapter.clone(),
+            require_adapter_version: adapter.map(|adapter| adapter.version.clone()),
+            manifest_path: resource.path.to_string_lossy().to_string(),
+            manifest_sha256: get_manifest_sha256(&resource.path)?,
+        })
+    }
+
+    /// Get the requirement that only matches the locked version.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the locked version can't be used as a requirement.
+    pub fn get_version_req(&self) -> Result<ResourceVersionReq, DscError> {
+        get_exact_version_req(&self.version)
+    }
+
+    /// Get the requirement that only matches the locked version of the adapter.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the resource is adapted but the adapter version isn't
+    /// locked, or the locked version can't be used as a requirement.
+    pub fn get_adapter_version_req(&self) -> Result<Option<ResourceVersionReq>, DscError> {
+        if self.require_adapter.is_none() {
+            return Ok(None);
+        }
+        let Some(version) = &self.require_adapter_version else {
+            return Err(DscError::LockMismatch(self.type_name.to_string(), t!("configure.lock.adapterVersionNotLocked").to_string()));
+        };
+        Ok(Some(get_exact_version_req(version)?))
+    }
+}
+
+impl Default for ConfigurationLock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConfigurationLock {
+    #[must_use]
+    pub fn new() -> Self {
+        ConfigurationLock {
+            lock_version: LOCK_VERSION,
+            resources: Vec::new(),
+        }
+    }
+
+    /// Get the path of the lock file for a configuration document, for example `web.dsc.lock.json`
+    /// for `web.dsc.yaml`.
+    #[must_use]
+    pub fn get_lock_path(config_path: &Path) -> PathBuf {
+        let file_name = config_path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+        let lowercase = file_name.to_lowercase();
+        let stem = CONFIG_EXTENSIONS.iter()
+            .find(|ext| lowercase.ends_with(*ext))
+            .map_or(file_name.as_str(), |ext| &file_name[..file_name.len() - ext.len()]);
+        config_path.with_file_name(format!("{stem}{LOCK_FILE_EXTENSION}"))
+    }
+
+    /// Read a lock file.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file can't be read, is invalid, or uses an
+    /// unsupported version of the lock file format.
+    pub fn load(path: &Path) -> Result<Self, DscError> {
+        let lock = serde_json::from_slice::<ConfigurationLock>(&read(path)?).map_err(|err| {
+            DscError::Validation(t!("configure.lock.invalidLockFile", path = path.to_string_lossy(), error = err).to_string())
+        })?;
+        if lock.lock_version != LOCK_VERSION {
+            return Err(DscError::Validation(t!("configure.lock.unsupportedVersion", path = path.to_string_lossy(), version = lock.lock_version).to_string()));
+        }
+        debug!("{}", t!("configure.lock.loaded", count = lock.resources.len(), path = path.to_string_lossy()));
+        Ok(lock)
+    }
+
+    /// Write the lock file.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file can't be written.
+    pub fn save(&self, path: &Path) -> Result<(), DscError> {
+        let mut json = serde_json::to_string_pretty(self)?;
+        json.push('\n');
+        write(path, json)?;
+        debug!("{}", t!("configure.lock.saved", count = self.resources.len(), path = path.to_string_lossy()));
+        Ok(())
+    }
+
+    /// Add a resource to the lock, unless the same resource is already locked.
+    ///
+    /// # Arguments
+    ///
+    /// * `resource` - The discovered resource.
+    /// * `adapter` - The discovered adapter of the resource, if it's an adapted resource.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the manifest can't be read.
+    pub fn add(&mut self, resource: &DscResource, adapter: Option<&DscResource>) -> Result<(), DscError> {
+        let locked = LockedResource::from_resource(resource, adapter)?;
+        if !self.resources.contains(&locked) {
+            self.resources.push(locked);
+        }
+        Ok(())
+    }
+
+    /// Find the locked resource with the type and, if specified, the required adapter.
+    #[must_use]
+    pub fn find(&self, type_name: &FullyQualifiedTypeName, require_adapter: Option<&FullyQualifiedTypeName>) -> Option<&LockedResource> {
+        self.resources.iter().find(|locked| {
+            locked.type_name == *type_name && require_adapter.is_none_or(|adapter| locked.require_adapter.as_ref() == Some(adapter))
+        })
+    }
+
+    /// Verify a discovered resource is the locked version with the locked manifest.
+    ///
+    /// The manifest path is only traced if it differs, so a lock can be shared by hosts that
+    /// install resources to different directories.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the resource isn't locked, or the version or manifest
+    /// digest differs from the lock.
+    pub fn verify(&self, resource: &DscResource) -> Result<(), DscError> {
+        let type_name = resource.type_name.to_string();
+        let Some(locked) = self.find(&resource.type_name, resource.require_adapter.as_ref()) else {
+            return Err(DscError::LockMismatch(type_name, t!("configure.lock.notLocked").to_string()));
+        };
+        if locked.version != resource.version {
+            return Err(DscError::LockMismatch(type_name, t!("configure.lock.versionMismatch", locked = locked.version, actual = resource.version).to_string()));
+        }
+        let manifest_sha256 = get_manifest_sha256(&resource.path)?;
+        if !digest_matches(locked.manifest_sha256.as_deref(), manifest_sha256.as_deref()) {
+            return Err(DscError::LockMismatch(type_name, t!("configure.lock.digestMismatch", path = resource.path.to_string_lossy(), locked = locked.manifest_sha256.clone().unwrap_or_default(), actual = manifest_sha256.unwrap_or_default()).to_string()));
+        }
+        let manifest_path = resource.path.to_string_lossy();
+        if locked.manifest_path != manifest_path {
+            warn!("{}", t!("configure.lock.pathDiffers", resource = type_name, locked = locked.manifest_path, actual = manifest_path));
+        }
+        debug!("{}", t!("configure.lock.verified", resource = type_name, version = resource.version));
+        Ok(())
+    }
+}
+
+fn get_exact_version_req(version: &ResourceVersion) -> Result<ResourceVersionReq, DscError> {
+    let requirement = if version.is_semver() {
+        format!("={version}")
+    } else {
+        version.to_string()
+    };
+    Ok(ResourceVersionReq::parse(&requirement)?)
+}
+
+fn digest_matches(locked: Option<&str>, actual: Option<&str>) -> bool {
+    match (locked, actual) {
+        (Some(locked), Some(actual)) => locked.eq_ignore_ascii_case(actual),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+/// Adapted resources listed by an adapter don't necessarily have a manifest file.
+fn get_manifest_sha256(path: &Path) -> Result<Option<String>, DscError> {
+    if path.is_file() {
+        Ok(Some(get_file_sha256(path)?))
+    } else {
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn resource(dir: &Path, name: &str, version: &str, manifest: &str) -> DscResource {
+        let path = dir.join(format!("{name}.{version}.dsc.resource.json"));
+        fs::write(&path, manifest).unwrap();
+        let mut resource = DscResource::new();
+        resource.type_name = FullyQualifiedTypeName::parse(&format!("Test/{name}")).unwrap();
+        resource.version = ResourceVersion::parse(version).unwrap();
+        resource.path = path;
+        resource
+    }
+
+    #[test]
+    fn lock_path_replaces_config_extension() {
+        assert_eq!(ConfigurationLock::get_lock_path(Path::new("dir/web.dsc.yaml")), Path::new("dir/web.dsc.lock.json"));
+        assert_eq!(ConfigurationLock::get_lock_path(Path::new("web.JSON")), Path::new("web.dsc.lock.json"));
+        assert_eq!(ConfigurationLock::get_lock_path(Path::new("web")), Path::new("web.dsc.lock.json"));
+    }
+
+    #[test]
+    fn locked_resource_is_verified() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut lock = ConfigurationLock::new();
+        let locked = resource(dir.path(), "Locked", "1.0.0", "{}");
+        lock.add(&locked, None).unwrap();
+        lock.add(&locked, None).unwrap();
+        assert_eq!(lock.resources.len(), 1);
+        assert!(lock.verify(&locked).is_ok());
+        assert!(lock.resources[0].get_version_req().unwrap().matches(&locked.version));
+        assert!(!lock.resources[0].get_version_req().unwrap().matches(&ResourceVersion::parse("1.0.1").unwrap()));
+
+        let newer = resource(dir.path(), "Locked", "1.1.0", "{}");
+        assert!(matches!(lock.verify(&newer), Err(DscError::LockMismatch(..))));
+        let changed_dir = tempfile::tempdir().unwrap();
+        let changed = resource(changed_dir.path(), "Locked", "1.0.0", r#"{"changed":true}"#);
+        assert!(matches!(lock.verify(&changed), Err(DscError::LockMismatch(..))));
+        let other = resource(dir.path(), "Other", "1.0.0", "{}");
+        assert!(matches!(lock.verify(&other), Err(DscError::LockMismatch(..))));
+    }
+
+    #[test]
+    fn adapted_resource_locks_adapter_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let adapter = resource(dir.path(), "Adapter", "2.1.0", "{}");
+        let mut adapted = resource(dir.path(), "Adapted", "1.0.0", "{}");
+        adapted.require_adapter = Some(adapter.type_name.clone());
+        let mut lock = ConfigurationLock::new();
+        lock.add(&adapted, Some(&adapter)).unwrap();
+        lock.add(&adapter, None).unwrap();
+
+        let locked = lock.find(&adapted.type_name, Some(&adapter.type_name)).unwrap();
+        assert_eq!(locked.require_adapter_version.as_ref(), Some(&adapter.version));
+        let adapter_req = locked.get_adapter_version_req().unwrap().unwrap();
+        assert!(adapter_req.matches(&adapter.version));
+        assert!(!adapter_req.matches(&ResourceVersion::parse("2.2.0").unwrap()));
+        assert!(lock.resources[1].get_adapter_version_req().unwrap().is_none());
+
+        let mut unpinned = locked.clone();
+        unpinned.require_adapter_version = None;
+        assert!(matches!(unpinned.get_adapter_version_req(), Err(DscError::LockMismatch(..))));
+    }
+}
@@ -8,7 +8,7 @@ use crate::configure::{config_doc::{ExecutionKind, IntOrExpression, Metadata, Pa
 use crate::discovery::discovery_trait::DiscoveryFilter;
 use crate::dscerror::DscError;
 use crate::dscresources::{
-    {dscresource::{Capability, Invoke, get_diff, validate_properties, get_adapter_input_kind},
+    {dscresource::{Capability, Invoke, get_diff, redact_json_string, validate_properties, get_adapter_input_kind},
     invoke_result::{DeleteResult, DeleteResultKind, GetResult, SetResult, TestResult, ExportResult, ResourceSetResponse}},
     resource_manifest::{AdapterInputKind, ExportSchemaOrFiltering, Kind},
 };
@@ -20,8 +20,10 @@ use crate::types::{FullyQualifiedTypeName, SemanticVersion};
 use crate::util::resource_id;
 use self::config_doc::{Configuration, DataType, MicrosoftDscMetadata, Operation, SecurityContextKind};
 use self::depends_on::get_resource_invocation_order;
-use self::config_result::{ConfigurationExportResult, ConfigurationGetResult, ConfigurationSetResult, ConfigurationTestResult};
-use self::constraints::{check_length, check_number_limits, check_allowed_values};
+use self::function_library::UserFunctionRegistry;
+use self::lock::ConfigurationLock;
+use self::config_result::{ConfigurationExportResult, ConfigurationGetResult, ConfigurationSetResult, ConfigurationTestResult, MessageLevel, ResourceMessage};
+use self::constraints::{check_length, check_number_limits, check_allowed_values, check_pattern, check_schema};
 use dsc_lib_security_context::{SecurityContext, get_security_context};
 use rust_i18n::t;
 use serde_json::{Map, Value};
@@ -34,6 +36,8 @@ pub mod config_doc;
 pub mod config_result;
 pub mod constraints;
 pub mod depends_on;
+pub mod function_library;
+pub mod lock;
 pub mod parameters;
 mod export_filter;
 pub(crate) mod schema_cache;
@@ -137,60 +141,111 @@ pub fn add_resource_export_results_to_configuration(
         export_filter::apply_export_filter(&mut export_result.actual_state, filters);
     }
 
-    if resource.kind == Kind::Exporter {
-        for instance in &export_result.actual_state {
-            let mut resource = serde_json::from_value::<Resource>(instance.clone())?;
-            let execution_information = ExecutionInformation::new_with_duration(&start_datetime, &end_datetime);
-            resource.execution_information = Some(execution_information);
-            conf.resources.push(resource);
-        }
-    } else {
-        for (i, instance) in export_result.actual_state.iter().enumerate() {
-            let mut r: Resource = config_doc::Resource::new();
-            r.resource_type.clone_from(&resource.type_name);
-            let mut props: Map<String, Value> = serde_json::from_value(instance.clone())?;
-            r.name = if let Some(name) = props.remove("_name") {
-                name.as_str()
-                    .map(std::string::ToString::to_string)
-                    .ok_or_else(|| DscError::Parser(t!("configure.mod.propertyNotString", name = "_name", value = name).to_string()))?
-            } else {
-                let resource_type_short = if let Some(pos) = resource.type_name.find('/') {
-                    &resource.type_name[pos + 1..]
-                } else {
-                    &resource.type_name
-                };
-                format!("{resource_type_short}-{i}")
-            };
-            let mut metadata = Metadata {
-                microsoft: None,
-                other: Map::new(),
-            };
-            if let Some(security_context) = props.remove("_securityContext") {
-                let security_context: SecurityContextKind = serde_json::from_value(security_context)?;
-                metadata.microsoft = Some(
-                        MicrosoftDscMetadata {
-                            security_context: Some(security_context),
-                            ..Default::default()
-                        }
-                );
-            }
-            r.properties = escape_property_values(&props)?;
-            let mut properties = serde_json::to_value(&r.properties)?;
-            let mut execution_information = ExecutionInformation::new_with_duration(&start_datetime, &end_datetime);
-            get_metadata_from_result(None, &mut properties, &mut metadata, &mut execution_information)?;
-            r.properties = Some(properties.as_object().cloned().unwrap_or_default());
-            r.metadata = if metadata.microsoft.is_some() || !metadata.other.is_empty() {
-                Some(metadata)
-            } else {
-                None
-            };
-            r.execution_information = Some(execution_information);
+    for (i, instance) in export_result.actual_state.iter().enumerate() {
+        let execution_information = ExecutionInformation::new_with_duration(&start_datetime, &end_datetime);
+        conf.resources.push(export_instance_to_resource(resource, i, instance.clone(), execution_information)?);
+    }
 
-            conf.resources.push(r);
+    Ok(export_result)
+}
+
+/// Export a resource, passing each exported instance to `on_resource` as a configuration resource
+/// as soon as the resource writes it.
+///
+/// Unlike [`add_resource_export_results_to_configuration`], the instances aren't collected, so a
+/// resource can export any number of instances in bounded memory. When the engine filters the
+/// export, each instance is filtered as it arrives.
+///
+/// # Arguments
+///
+/// * `resource` - The resource to export.
+/// * `input` - The input to the export operation.
+/// * `on_resource` - Called with each exported instance.
+///
+/// # Returns
+///
+/// The number of instances passed to `on_resource`.
+///
+/// # Errors
+///
+/// This function will return an error if the underlying resource or `on_resource` fails.
+pub fn stream_resource_export_results(
+    resource: &DscResource,
+    input: &str,
+    on_resource: &mut dyn FnMut(Resource) -> Result<(), DscError>,
+) -> Result<usize, DscError> {
+    let mut resource_input = input;
+    let mut input_filters: Option<Vec<Map<String, Value>>> = None;
+    if !input.is_empty() && !resource_supports_export_filtering(resource) {
+        input_filters = Some(parse_export_filter_input(input)?);
+        resource_input = "";
+        info!("{}", t!("configure.mod.engineExportFiltering", resource = resource.type_name));
+    }
+
+    let start_datetime = chrono::Local::now();
+    let mut count = 0;
+    resource.export_stream(resource_input, &mut |mut instance| {
+        if let Some(filters) = input_filters.as_deref()
+            && !export_filter::apply_export_filter_to_instance(&mut instance, filters) {
+            return Ok(());
         }
+        let execution_information = ExecutionInformation::new_with_duration(&start_datetime, &chrono::Local::now());
+        on_resource(export_instance_to_resource(resource, count, instance, execution_information)?)?;
+        count += 1;
+        Ok(())
+    })?;
+    debug!("{}", t!("configure.mod.streamedExportInstances", count = count, resource = resource.type_name));
+    Ok(count)
+}
+
+/// Convert an exported instance to a configuration resource.
+fn export_instance_to_resource(resource: &DscResource, index: usize, instance: Value, execution_information: ExecutionInformation) -> Result<Resource, DscError> {
+    if resource.kind == Kind::Exporter {
+        let mut resource = serde_json::from_value::<Resource>(instance)?;
+        resource.execution_information = Some(execution_information);
+        return Ok(resource);
     }
 
-    Ok(export_result)
+    let mut r: Resource = config_doc::Resource::new();
+    r.resource_type.clone_from(&resource.type_name);
+    let mut props: Map<String, Value> = serde_json::from_value(instance)?;
+    r.name = if let Some(name) = props.remove("_name") {
+        name.as_str()
+            .map(std::string::ToString::to_string)
+            .ok_or_else(|| DscError::Parser(t!("configure.mod.propertyNotString", name = "_name", value = name).to_string()))?
+    } else {
+        let resource_type_short = if let Some(pos) = resource.type_name.find('/') {
+            &resource.type_name[pos + 1..]
+        } else {
+            &resource.type_name
+        };
+        format!("{resource_type_short}-{index}")
+    };
+    let mut metadata = Metadata {
+        microsoft: None,
+        other: Map::new(),
+    };
+    if let Some(security_context) = props.remove("_securityContext") {
+        let security_context: SecurityContextKind = serde_json::from_value(security_context)?;
+        metadata.microsoft = Some(
+                MicrosoftDscMetadata {
+                    security_context: Some(security_context),
+                    ..Default::default()
+                }
+        );
+    }
+    r.properties = escape_property_values(&props)?;
+    let mut properties = serde_json::to_value(&r.properties)?;
+    let mut execution_information = execution_information;
+    get_metadata_from_result(None, &mut properties, &mut metadata, &mut execution_information)?;
+    r.properties = Some(properties.as_object().cloned().unwrap_or_default());
+    r.metadata = if metadata.microsoft.is_some() || !metadata.other.is_empty() {
+        Some(metadata)
+    } else {
+        None
+    };
+    r.execution_information = Some(execution_information);
+    Ok(r)
 }
 
 /// A resource supports native export filtering unless its manifest export method explicitly
@@ -340,6 +395,17 @@ fn get_require_adapter_from_directive(resource_directives: &Option<ResourceDirec
     None
 }
 
+/// Get the resources nested in the properties of a group or an adapter.
+fn get_nested_resources(dsc_resource: &DscResource, properties: Option<&Map<String, Value>>) -> Result<Option<Vec<Resource>>, DscError> {
+    if !matches!(dsc_resource.kind, Kind::Group | Kind::Adapter) {
+        return Ok(None);
+    }
+    let Some(resources) = properties.and_then(|properties| properties.get("resources")) else {
+        return Ok(None);
+    };
+    Ok(Some(serde_json::from_value(resources.clone())?))
+}
+
 fn check_security_context(metadata: Option<&Metadata>, directive_security_context: Option<&SecurityContextKind>) -> Result<(), DscError> {
     if metadata.is_none() && directive_security_context.is_none() {
         return Ok(());
@@ -534,6 +600,115 @@ impl Configurator {
         &mut self.discovery
     }
 
+    /// Resolve the resources of the configuration, including the resources nested in groups and
+    /// adapters, and their adapters to the exact versions and manifests found by discovery.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a resource or adapter can't be found or its manifest
+    /// can't be read.
+    pub fn lock(&mut self) -> Result<ConfigurationLock, DscError> {
+        let mut lock = ConfigurationLock::new();
+        let resources = self.config.resources.clone();
+        self.lock_resources(&mut lock, &resources, None)?;
+        Ok(lock)
+    }
+
+    fn lock_resources(&mut self, lock: &mut ConfigurationLock, resources: &[Resource], parent_adapter: Option<&FullyQualifiedTypeName>) -> Result<(), DscError> {
+        for resource in resources {
+            let adapter = get_require_adapter_from_directive(&resource.directives).or_else(|| parent_adapter.cloned());
+            let filter = DiscoveryFilter::new(&resource.resource_type, resource.require_version.clone(), adapter);
+            let Some(dsc_resource) = self.find_resource_for_lock(&filter)? else {
+                return Err(DscError::ResourceNotFound(
+                    resource.resource_type.to_string(),
+                    resource.require_version.as_ref().map(|r| r.to_string()).unwrap_or("".to_string())
+                ));
+            };
+            let dsc_adapter = match &dsc_resource.require_adapter {
+                Some(adapter) => {
+                    let Some(dsc_adapter) = self.find_resource_for_lock(&DiscoveryFilter::new(adapter, None, None))? else {
+                        return Err(DscError::AdapterNotFound(adapter.to_string()));
+                    };
+                    Some(dsc_adapter)
+                },
+                None => None,
+            };
+            lock.add(&dsc_resource, dsc_adapter.as_ref())?;
+            if let Some(dsc_adapter) = &dsc_adapter {
+                lock.add(dsc_adapter, None)?;
+            }
+            debug!("{}", t!("configure.mod.lockedResource", resource = dsc_resource.type_name, version = dsc_resource.version));
+            if let Some(nested) = get_nested_resources(&dsc_resource, resource.properties.as_ref())? {
+                let nested_adapter = (dsc_resource.kind == Kind::Adapter).then_some(&dsc_resource.type_name);
+                self.lock_resources(lock, &nested, nested_adapter)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Require the resources of the configuration, including the resources nested in groups and
+    /// adapters, and their adapters to match a lock.
+    ///
+    /// The resources and adapters are pinned to the locked versions, so a newer version discovered
+    /// later isn't used instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `lock` - The lock read from the lock file of the configuration.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a resource or its adapter isn't locked, the locked
+    /// version can't be found, or the manifest differs from the lock.
+    pub fn set_lock(&mut self, lock: &ConfigurationLock) -> Result<(), DscError> {
+        let mut resources = self.config.resources.clone();
+        self.set_lock_resources(lock, &mut resources, None)?;
+        self.config.resources = resources;
+        Ok(())
+    }
+
+    fn set_lock_resources(&mut self, lock: &ConfigurationLock, resources: &mut [Resource], parent_adapter: Option<&FullyQualifiedTypeName>) -> Result<(), DscError> {
+        for resource in resources.iter_mut() {
+            let adapter = get_require_adapter_from_directive(&resource.directives).or_else(|| parent_adapter.cloned());
+            let Some(locked) = lock.find(&resource.resource_type, adapter.as_ref()) else {
+                return Err(DscError::LockMismatch(resource.resource_type.to_string(), t!("configure.lock.notLocked").to_string()));
+            };
+            resource.require_version = Some(locked.get_version_req()?);
+            let adapter_version = locked.get_adapter_version_req()?;
+            let filter = DiscoveryFilter::new(&resource.resource_type, resource.require_version.clone(), adapter);
+            let Some(dsc_resource) = self.find_resource_for_lock(&filter)? else {
+                return Err(DscError::LockMismatch(resource.resource_type.to_string(), t!("configure.lock.lockedVersionNotFound", version = locked.version).to_string()));
+            };
+            lock.verify(&dsc_resource)?;
+            if let Some(adapter) = &dsc_resource.require_adapter {
+                let Some(dsc_adapter) = self.find_resource_for_lock(&DiscoveryFilter::new(adapter, adapter_version, None))? else {
+                    let version = locked.require_adapter_version.as_ref().map(ToString::to_string).unwrap_or_default();
+                    return Err(DscError::LockMismatch(adapter.to_string(), t!("configure.lock.lockedVersionNotFound", version = version).to_string()));
+                };
+                lock.verify(&dsc_adapter)?;
+            }
+            if let Some(mut nested) = get_nested_resources(&dsc_resource, resource.properties.as_ref())? {
+                let nested_adapter = (dsc_resource.kind == Kind::Adapter).then_some(&dsc_resource.type_name);
+                self.set_lock_resources(lock, &mut nested, nested_adapter)?;
+                // groups invoke their resources in a child process, so the pinned versions are passed in the properties
+                if dsc_resource.kind == Kind::Group
+                    && let Some(properties) = resource.properties.as_mut() {
+                    properties.insert("resources".to_string(), serde_json::to_value(&nested)?);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn find_resource_for_lock(&mut self, filter: &DiscoveryFilter) -> Result<Option<DscResource>, DscError> {
+        if let Some(resource) = self.discovery.find_resource(filter)? {
+            return Ok(Some(resource.clone()));
+        }
+        // resources aren't discovered up front when discovery happens during deployment
+        self.discovery.find_resources(std::slice::from_ref(filter), self.progress_format)?;
+        Ok(self.discovery.find_resource(filter)?.cloned())
+    }
+
     fn get_properties(&mut self, resource: &Resource, resource_kind: &Kind) -> Result<Option<Map<String, Value>>, DscError> {
         // Restore copy loop context from resource metadata under Microsoft.DSC/copyLoops if present
         if let Some(metadata) = &resource.metadata
@@ -600,7 +775,9 @@ impl Configurator {
                 Err(e) => {
                     progress.set_failure(get_failure_from_error(&e));
                     progress.write_increment(1);
-                    return Err(e);
+                    result.messages.push(get_message_from_error(e, &evaluated_name, &resource.resource_type)?);
+                    result.had_errors = true;
+                    return Ok(result);
                 },
             };
             let end_datetime = chrono::Local::now();
@@ -700,7 +877,7 @@ impl Configurator {
             };
 
             let desired = add_metadata(dsc_resource, properties, resource.metadata.clone())?;
-            trace!("{}", t!("configure.mod.desired", state = desired));
+            trace!("{}", t!("configure.mod.desired", state = redact_json_string(&desired)));
 
             let start_datetime;
             let end_datetime;
@@ -714,7 +891,9 @@ impl Configurator {
                     Err(e) => {
                         progress.set_failure(get_failure_from_error(&e));
                         progress.write_increment(1);
-                        return Err(e);
+                        result.messages.push(get_message_from_error(e, &evaluated_name, &resource.resource_type)?);
+                        result.had_errors = true;
+                        return Ok(result);
                     },
                 };
                 end_datetime = chrono::Local::now();
@@ -726,7 +905,9 @@ impl Configurator {
                     Err(e) => {
                         progress.set_failure(get_failure_from_error(&e));
                         progress.write_increment(1);
-                        return Err(e);
+                        result.messages.push(get_message_from_error(e, &evaluated_name, &resource.resource_type)?);
+                        result.had_errors = true;
+                        return Ok(result);
                     },
                 };
 
@@ -736,7 +917,9 @@ impl Configurator {
                     Err(e) => {
                         progress.set_failure(get_failure_from_error(&e));
                         progress.write_increment(1);
-                        return Err(e);
+                        result.messages.push(get_message_from_error(e, &evaluated_name, &resource.resource_type)?);
+                        result.had_errors = true;
+                        return Ok(result);
                     },
                 };
 
@@ -755,7 +938,9 @@ impl Configurator {
                             Err(e) => {
                                 progress.set_failure(get_failure_from_error(&e));
                                 progress.write_increment(1);
-                                return Err(e);
+                                result.messages.push(get_message_from_error(e, &evaluated_name, &resource.resource_type)?);
+                                result.had_errors = true;
+                                return Ok(result);
                             },
                         };
                         end_datetime = chrono::Local::now();
@@ -882,7 +1067,9 @@ impl Configurator {
                 Err(e) => {
                     progress.set_failure(get_failure_from_error(&e));
                     progress.write_increment(1);
-                    return Err(e);
+                    result.messages.push(get_message_from_error(e, &evaluated_name, &resource.resource_type)?);
+                    result.had_errors = true;
+                    return Ok(result);
                 },
             };
             let end_datetime = chrono::Local::now();
@@ -965,7 +1152,7 @@ impl Configurator {
             let properties = self.get_properties(resource, &dsc_resource.kind)?;
             debug!("resource_type {}", &resource.resource_type);
             let input = add_metadata(dsc_resource, properties, resource.metadata.clone())?;
-            trace!("{}", t!("configure.mod.exportInput", input = input));
+            trace!("{}", t!("configure.mod.exportInput", input = redact_json_string(&input)));
             let export_result = match add_resource_export_results_to_configuration(
                 dsc_resource,
                 &mut conf,
@@ -1043,7 +1230,8 @@ impl Configurator {
                     }
                     // TODO: handle nullable when supported
                     if value_result.is_string() && output.r#type != DataType::String ||
-                        value_result.is_i64() && output.r#type != DataType::Int ||
+                        value_result.is_i64() && output.r#type != DataType::Int && output.r#type != DataType::Number ||
+                        value_result.is_f64() && output.r#type != DataType::Number ||
                         value_result.is_boolean() && output.r#type != DataType::Bool ||
                         value_result.is_array() && output.r#type != DataType::Array ||
                         value_result.is_object() && output.r#type != DataType::Object {
@@ -1104,8 +1292,8 @@ impl Configurator {
                     check_length(&name, &value, constraint)?;
                     check_allowed_values(&name, &value, constraint)?;
                     check_number_limits(&name, &value, constraint)?;
-                    // TODO: additional array constraints
-                    // TODO: object constraints
+                    check_pattern(&name, &value, constraint)?;
+                    check_schema(&name, &value, constraint)?;
 
                     let value = match &constraint.parameter_type {
                         DataType::SecureString => {
@@ -1176,6 +1364,8 @@ impl Configurator {
                         default_value.clone()
                     };
 
+                    check_pattern(name, &value, parameter)?;
+                    check_schema(name, &value, parameter)?;
                     if parameter.parameter_type == DataType::SecureString && value.is_string() {
                         let secure_string = SecureString {
                             secure_string: value.as_str().unwrap().to_string(),
@@ -1232,20 +1422,16 @@ impl Configurator {
     }
 
     fn set_user_functions(&mut self, config: &Configuration) -> Result<(), DscError> {
+        let mut registry = UserFunctionRegistry::new();
+        if let Some(imports) = &config.imports {
+            registry.import(imports, &mut self.context.user_functions)?;
+        }
+
         let Some(functions) = &config.functions else {
             return Ok(());
         };
 
-        for user_function in functions {
-            for (function_name, function_definition) in &user_function.members {
-                if self.context.user_functions.contains_key(&format!("{}.{}", user_function.namespace, function_name)) {
-                    return Err(DscError::Validation(t!("configure.mod.userFunctionAlreadyDefined", name = function_name, namespace = user_function.namespace).to_string()));
-                }
-                debug!("{}", t!("configure.mod.addingUserFunction", name = format!("{}.{}", user_function.namespace, function_name)));
-                self.context.user_functions.insert(format!("{}.{}", user_function.namespace, function_name), function_definition.clone());
-            }
-        }
-        Ok(())
+        registry.add(functions, "configuration", &mut self.context.user_functions)
     }
 
     fn get_result_metadata(&self, operation: Operation) -> Metadata {
@@ -1482,6 +1668,11 @@ pub fn validate_parameter_type(name: &str, value: &Value, parameter_type: &DataT
                 return Err(DscError::Validation(t!("configure.mod.parameterNotInteger", name = name).to_string()));
             }
         },
+        DataType::Number => {
+            if !value.is_number() {
+                return Err(DscError::Validation(t!("configure.mod.parameterNotNumber", name = name).to_string()));
+            }
+        },
         DataType::Bool => {
             if !value.is_boolean() {
                 return Err(DscError::Validation(t!("configure.mod.parameterNotBoolean", name = name).to_string()));
@@ -1507,18 +1698,43 @@ pub fn validate_parameter_type(name: &str, value: &Value, parameter_type: &DataT
     Ok(())
 }
 
+/// Convert a structured error reported by a resource into a message of the configuration result.
+///
+/// Errors without a structured error from the resource are returned unchanged.
+fn get_message_from_error(err: DscError, name: &str, resource_type: &FullyQualifiedTypeName) -> Result<ResourceMessage, DscError> {
+    let DscError::CommandResourceError(_, _, resource_error) = &err else {
+        return Err(err);
+    };
+    Ok(ResourceMessage {
+        name: name.to_string(),
+        resource_type: resource_type.clone(),
+        message: err.to_string(),
+        level: MessageLevel::Error,
+        error: Some(resource_error.as_ref().clone()),
+    })
+}
+
 fn get_failure_from_error(err: &DscError) -> Option<Failure> {
     match err {
         DscError::CommandExit(_resource, exit_code, reason) => {
             Some(Failure {
                 message: reason.to_string(),
                 exit_code: *exit_code,
+                error: None,
             })
         },
         DscError::CommandExitFromManifest(_resource, exit_code, reason) => {
             Some(Failure {
                 message: reason.to_string(),
                 exit_code: *exit_code,
+                error: None,
+            })
+        },
+        DscError::CommandResourceError(_resource, exit_code, resource_error) => {
+            Some(Failure {
+                message: resource_error.message.clone(),
+                exit_code: *exit_code,
+                error: Some(resource_error.as_ref().clone()),
             })
         },
         _ => None,
@@ -1528,6 +1744,8 @@ fn get_failure_from_error(err: &DscError) -> Option<Failure> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::configure::config_result::check_resource_errors;
+    use crate::dscresources::invoke_result::ResourceError;
     use serde_json::json;
 
     fn resource_with_export(export: Value) -> DscResource {
@@ -1605,4 +1823,43 @@ mod tests {
     fn filter_input_invalid_json_is_error() {
         assert!(parse_export_filter_input("not json").is_err());
     }
+
+    #[test]
+    fn structured_error_becomes_message() {
+        let resource_error: ResourceError = serde_json::from_value(json!({
+            "code": "QuotaExceeded",
+            "message": "The disk quota was exceeded",
+            "target": "size"
+        })).unwrap();
+        let resource_type = FullyQualifiedTypeName::parse("Test/Error").unwrap();
+        let err = DscError::CommandResourceError("dsctest".to_string(), 2, Box::new(resource_error.clone()));
+        let failure = get_failure_from_error(&err).unwrap();
+        assert_eq!(failure.exit_code, 2);
+        assert_eq!(failure.error, Some(resource_error.clone()));
+        let message = get_message_from_error(err, "Error", &resource_type).unwrap();
+        assert_eq!(message.name, "Error");
+        assert_eq!(message.level, MessageLevel::Error);
+        assert_eq!(message.error, Some(resource_error));
+    }
+
+    #[test]
+    fn unstructured_error_is_not_message() {
+        let resource_type = FullyQualifiedTypeName::parse("Test/Error").unwrap();
+        let err = DscError::Command("dsctest".to_string(), 2, "failed".to_string());
+        assert!(get_message_from_error(err, "Error", &resource_type).is_err());
+    }
+
+    #[test]
+    fn failed_resource_is_error_for_callers() {
+        let resource_error: ResourceError = serde_json::from_value(json!({
+            "code": "QuotaExceeded",
+            "message": "The disk quota was exceeded"
+        })).unwrap();
+        let resource_type = FullyQualifiedTypeName::parse("Test/Error").unwrap();
+        let err = DscError::CommandResourceError("dsctest".to_string(), 2, Box::new(resource_error));
+        let message = get_message_from_error(err, "Error", &resource_type).unwrap();
+        assert!(check_resource_errors(false, &[]).is_ok());
+        let err = check_resource_errors(true, std::slice::from_ref(&message)).unwrap_err();
+        assert!(err.to_string().contains(&message.message));
+    }
 }
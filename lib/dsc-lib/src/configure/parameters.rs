@@ -70,6 +70,91 @@ pub fn is_secure_value(value: &Value) -> bool {
     false
 }
 
+/// Check if a JSON value is a secure value or contains one in an object or array.
+///
+/// # Arguments
+///
+/// * `value` - The JSON value to check.
+///
+/// # Returns
+///
+/// `true` if the value is or contains a secure value, `false` otherwise.
+#[must_use]
+pub fn contains_secure_value(value: &Value) -> bool {
+    if is_secure_value(value) {
+        return true;
+    }
+
+    match value {
+        Value::Object(map) => map.values().any(contains_secure_value),
+        Value::Array(array) => array.iter().any(contains_secure_value),
+        _ => false,
+    }
+}
+
+/// Replace secure values with the value they wrap so functions can operate on them.
+///
+/// # Arguments
+///
+/// * `value` - The JSON value to unwrap.
+///
+/// # Returns
+///
+/// The JSON value without any `secureString` or `secureObject` wrappers.
+#[must_use]
+pub fn unwrap_secure_value(value: &Value) -> Value {
+    if is_secure_value(value) {
+        if let Some(string) = value.get("secureString") {
+            return string.clone();
+        }
+        if let Some(object) = value.get("secureObject") {
+            return unwrap_secure_value(object);
+        }
+    }
+
+    match value {
+        Value::Object(map) => Value::Object(map.iter().map(|(key, val)| (key.clone(), unwrap_secure_value(val))).collect()),
+        Value::Array(array) => Value::Array(array.iter().map(unwrap_secure_value).collect()),
+        _ => value.clone(),
+    }
+}
+
+/// Convert a JSON value to a secure value if it is a string, an object, or an array of those.
+///
+/// # Arguments
+///
+/// * `value` - The JSON value to convert.
+///
+/// # Returns
+///
+/// The converted JSON value.
+#[must_use]
+pub fn to_secure_value(value: &Value) -> Value {
+    if is_secure_value(value) {
+        return value.clone();
+    }
+
+    if let Some(string) = value.as_str() {
+        let secure_string = SecureString {
+            secure_string: string.to_string(),
+        };
+        return serde_json::to_value(secure_string).unwrap_or(value.clone());
+    }
+
+    if value.as_object().is_some() {
+        let secure_object = SecureObject {
+            secure_object: value.clone(),
+        };
+        return serde_json::to_value(secure_object).unwrap_or(value.clone());
+    }
+
+    if let Some(array) = value.as_array() {
+        let new_array: Vec<Value> = array.iter().map(to_secure_value).collect();
+        return Value::Array(new_array);
+    }
+    value.clone()
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
 #[serde(deny_unknown_fields, untagged)]
 pub enum SecureKind {
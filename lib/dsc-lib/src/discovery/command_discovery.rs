@@ -1,7 +1,7 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
-use crate::{discovery::{DiscoveryExtensionCache, DiscoveryManifestCache, DiscoveryResourceCache, discovery_trait::{DiscoveryFilter, DiscoveryKind, ResourceDiscovery}, matches_adapter_requirement}, dscresources::{adapted_resource_manifest::AdaptedDscResourceManifest, resource_manifest::SetDeleteArgKind}, parser::Statement, types::{FullyQualifiedTypeName, TypeNameFilter}};
+use crate::{discovery::{DiscoveryExtensionCache, DiscoveryManifestCache, DiscoveryResourceCache, discovery_trait::{DiscoveryFilter, DiscoveryKind, ResourceDiscovery}, manifest_cache::get_manifests, resource_bundle::get_installed_directories, manifest_signature::{get_trusted_publishers_setting, verify_manifest_signature, ManifestTrust, TrustedPublishersSetting}, matches_adapter_requirement}, dscresources::{adapted_resource_manifest::AdaptedDscResourceManifest, resource_manifest::SetDeleteArgKind}, parser::Statement, types::{FullyQualifiedTypeName, TypeNameFilter}};
 use crate::{locked_clear, locked_is_empty, locked_extend, locked_clone, locked_get};
 use crate::configure::{config_doc::ResourceDiscoveryMode, context::Context};
 use crate::dscresources::adapted_resource_manifest::AdaptedPathOrContent;
@@ -15,7 +15,7 @@ use crate::progress::{ProgressBar, ProgressFormat};
 use crate::schemas::transforms::idiomaticize_externally_tagged_enum;
 use rust_i18n::t;
 use schemars::JsonSchema;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::{collections::{HashMap, HashSet}, sync::{LazyLock, RwLock}};
 use std::env;
@@ -25,7 +25,7 @@ use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use tracing::{debug, info, trace, warn};
 
-use crate::util::get_setting;
+use crate::util::{get_setting, load_executable_integrity_setting};
 use crate::util::{canonicalize_which, get_exe_path};
 
 // NOTE: if new types of file extensions are added, ensure they are added to `process_discover_args` in `lib/dsc-lib/src/extensions/discover.rs`
@@ -48,7 +48,7 @@ pub struct ManifestList {
 }
 
 #[allow(clippy::large_enum_variant)]
-#[derive(Clone, Deserialize, JsonSchema)]
+#[derive(Clone, Deserialize, Serialize, JsonSchema)]
 #[schemars(transform = idiomaticize_externally_tagged_enum)]
 pub enum ImportedManifest {
     Resource(DscResource),
@@ -120,7 +120,7 @@ impl CommandDiscovery {
         Err(DscError::Setting(t!("discovery.commandDiscovery.couldNotReadSetting").to_string()))
     }
 
-    fn get_resource_paths() -> Result<Vec<PathBuf>, DscError>
+    pub(crate) fn get_resource_paths() -> Result<Vec<PathBuf>, DscError>
     {
         let mut resource_path_setting = ResourcePathSetting::default();
 
@@ -180,6 +180,13 @@ impl CommandDiscovery {
             paths = add_exe_home_to_path(paths)?;
         }
 
+        // installed bundles are managed by DSC, so only the restricted path excludes them
+        if !(resource_path_setting.allow_env_override && dsc_restricted_path.is_some()) {
+            let installed = get_installed_directories();
+            trace!("{}", t!("discovery.commandDiscovery.addingInstalledDirectories", count = installed.len()));
+            paths.extend(installed);
+        }
+
         // remove duplicate entries
         let mut uniques: HashSet<PathBuf> = HashSet::new();
         paths.retain(|e|uniques.insert((*e).clone()));
@@ -236,6 +243,7 @@ impl ResourceDiscovery for CommandDiscovery {
         }
 
         info!("{}", t!("discovery.commandDiscovery.discoverResources", kind = kind : {:?}, filter = filter.to_string()));
+        load_executable_integrity_setting();
 
         debug!("Using type name filter '{filter}' for adapter name");
         let mut progress = ProgressBar::new(1, self.progress_format)?;
@@ -252,73 +260,46 @@ impl ResourceDiscovery for CommandDiscovery {
         let mut resources = DiscoveryResourceCache::new();
         let mut extensions = DiscoveryExtensionCache::new();
 
+        let trusted_publishers = get_trusted_publishers_setting();
         if let Ok(paths) = CommandDiscovery::get_resource_paths() {
-            for path in paths {
-                if path.exists() && path.is_dir() {
-                    for entry in path.read_dir().unwrap() {
-                        let entry = entry.unwrap();
-                        let path = entry.path();
-                        if path.is_file() {
-                            let Some(os_file_name) = path.file_name() else {
-                                // skip if not a file
-                                continue;
-                            };
-                            let Some(file_name) = os_file_name.to_str() else {
-                                // skip if not a valid file name
-                                continue;
-                            };
-                            let file_name_lowercase = file_name.to_lowercase();
-                            if DSC_MANIFEST_LIST_EXTENSIONS.iter().any(|ext| file_name_lowercase.ends_with(ext)) ||
-                                (kind == &DiscoveryKind::Resource && (DSC_RESOURCE_EXTENSIONS.iter().any(|ext| file_name_lowercase.ends_with(ext))) || DSC_ADAPTED_RESOURCE_EXTENSIONS.iter().any(|ext| file_name_lowercase.ends_with(ext))) ||
-                                (kind == &DiscoveryKind::Extension && DSC_EXTENSION_EXTENSIONS.iter().any(|ext| file_name_lowercase.ends_with(ext))) {
-                                trace!("{}", t!("discovery.commandDiscovery.foundManifest", path = path.to_string_lossy()));
-                                let imported_manifests = match load_manifest(&path)
-                                {
-                                    Ok(r) => r,
-                                    Err(e) => {
-                                        // At this point we can't determine whether or not the bad manifest contains
-                                        // resource that is requested by resource/config operation
-                                        // if it is, then "ResouceNotFound" error will be issued later
-                                        // and here we just write as info
-                                        info!("{}", t!("discovery.commandDiscovery.failedLoadManifest", err = e));
-                                        continue;
-                                    },
-                                };
-
-                                for imported_manifest in imported_manifests {
-                                    match imported_manifest {
-                                        ImportedManifest::Extension(extension) => {
-                                            if filter.is_match(&extension.type_name) {
-                                                trace!("{}", t!("discovery.commandDiscovery.extensionFound", extension = extension.type_name, version = extension.version));
-                                                // we only keep newest version of the extension so compare the version and only keep the newest
-                                                if let Some(existing_extension) = extensions.get_mut(&extension.type_name) {
-                                                    if extension.version > existing_extension.version {
-                                                        extensions.insert(extension.type_name.clone(), extension.clone());
-                                                    }
-                                                } else {
-                                                    extensions.insert(extension.type_name.clone(), extension.clone());
-                                                }
-                                            }
-                                        },
-                                        ImportedManifest::Resource(resource) => {
-                                            if filter.is_match(&resource.type_name) {
-                                                if let Some(manifest) = &resource.manifest {
-                                                    if manifest.kind == Some(Kind::Adapter) {
-                                                        trace!("{}", t!("discovery.commandDiscovery.adapterFound", adapter = resource.type_name, version = resource.version));
-                                                        insert_resource(&mut adapters, &resource);
-                                                    }
-                                                    // also make sure to add adapters as a resource as well
-                                                    trace!("{}", t!("discovery.commandDiscovery.resourceFound", resource = resource.type_name, version = resource.version));
-                                                    insert_resource(&mut resources, &resource);
-                                                }
-                                                if let Some(_adapter) = &resource.require_adapter {
-                                                    trace!("{}", t!("discovery.commandDiscovery.adaptedResourceFound", resource = resource.type_name, version = resource.version));
-                                                    insert_resource(&mut resources, &resource);
-                                                }
-                                            }
-                                        }
-                                    }
+            for imported_manifest in get_manifests(&paths, Some(kind)) {
+                match imported_manifest {
+                    ImportedManifest::Extension(extension) => {
+                        if filter.is_match(&extension.type_name) {
+                            if trusted_publishers.is_enabled()
+                                && let ManifestTrust::Untrusted { reason } = verify_manifest_signature(&extension.path, &trusted_publishers.publishers) {
+                                if trusted_publishers.enforce {
+                                    warn!("{}", t!("discovery.commandDiscovery.untrustedExtensionSkipped", extension = extension.type_name, reason = reason));
+                                    continue;
                                 }
+                                warn!("{}", t!("discovery.commandDiscovery.untrustedExtension", extension = extension.type_name, reason = reason));
+                            }
+                            trace!("{}", t!("discovery.commandDiscovery.extensionFound", extension = extension.type_name, version = extension.version));
+                            // we only keep newest version of the extension so compare the version and only keep the newest
+                            if let Some(existing_extension) = extensions.get_mut(&extension.type_name) {
+                                if extension.version > existing_extension.version {
+                                    extensions.insert(extension.type_name.clone(), extension.clone());
+                                }
+                            } else {
+                                extensions.insert(extension.type_name.clone(), extension.clone());
+                            }
+                        }
+                    },
+                    ImportedManifest::Resource(mut resource) => {
+                        if filter.is_match(&resource.type_name) {
+                            verify_resource_trust(&mut resource, &trusted_publishers);
+                            if let Some(manifest) = &resource.manifest {
+                                if manifest.kind == Some(Kind::Adapter) {
+                                    trace!("{}", t!("discovery.commandDiscovery.adapterFound", adapter = resource.type_name, version = resource.version));
+                                    insert_resource(&mut adapters, &resource);
+                                }
+                                // also make sure to add adapters as a resource as well
+                                trace!("{}", t!("discovery.commandDiscovery.resourceFound", resource = resource.type_name, version = resource.version));
+                                insert_resource(&mut resources, &resource);
+                            }
+                            if let Some(_adapter) = &resource.require_adapter {
+                                trace!("{}", t!("discovery.commandDiscovery.adaptedResourceFound", resource = resource.type_name, version = resource.version));
+                                insert_resource(&mut resources, &resource);
                             }
                         }
                     }
@@ -342,8 +323,9 @@ impl ResourceDiscovery for CommandDiscovery {
                             }
                         };
                         debug!("{}", t!("discovery.commandDiscovery.extensionFoundResources", extension = extension.type_name, count = discovered_resources.len()));
-                        for resource in discovered_resources {
+                        for mut resource in discovered_resources {
                             if filter.is_match(&resource.type_name) {
+                                verify_resource_trust(&mut resource, &trusted_publishers);
                                 trace!("{}", t!("discovery.commandDiscovery.extensionResourceFound", resource = resource.type_name));
                                 insert_resource(&mut resources, &resource);
                             }
@@ -383,6 +365,7 @@ impl ResourceDiscovery for CommandDiscovery {
         progress.write_activity("Searching for adapted resources");
 
         let mut adapted_resources = DiscoveryResourceCache::new();
+        let trusted_publishers = get_trusted_publishers_setting();
 
         let mut found_adapter: bool = false;
         for (adapter_name, adapters) in &adapters {
@@ -393,6 +376,14 @@ impl ResourceDiscovery for CommandDiscovery {
                     continue;
                 }
 
+                // the list command of an adapter only runs if the adapter itself is trusted
+                let mut adapter = adapter.clone();
+                verify_resource_trust(&mut adapter, &trusted_publishers);
+                if let ManifestTrust::Untrusted { reason } = &adapter.trust
+                    && trusted_publishers.enforce {
+                    warn!("{}", t!("discovery.commandDiscovery.untrustedAdapterSkipped", adapter = adapter_name, reason = reason));
+                    continue;
+                }
                 found_adapter = true;
                 let mut adapter_progress = ProgressBar::new(1, self.progress_format)?;
                 adapter_progress.write_activity(format!("Enumerating resources for adapter '{adapter_name}'").as_str());
@@ -403,7 +394,7 @@ impl ResourceDiscovery for CommandDiscovery {
                 let mut adapter_resources_count = 0;
                 // invoke the list command
                 if let Some(list_command) = &manifest.adapter.clone().unwrap().list {
-                    let (exit_code, stdout, stderr) = match invoke_command(&list_command.executable, list_command.args.clone(), None, Some(&adapter.directory), None, manifest.exit_codes.as_ref())
+                    let (exit_code, stdout, stderr) = match invoke_command(&list_command.executable, list_command.args.clone(), None, Some(&adapter.directory), None, manifest.exit_codes.as_ref(), list_command.sha256.as_ref())
                     {
                         Ok((exit_code, stdout, stderr)) => (exit_code, stdout, stderr),
                         Err(e) => {
@@ -421,11 +412,13 @@ impl ResourceDiscovery for CommandDiscovery {
 
                     for line in stdout.lines() {
                         match serde_json::from_str::<DscResource>(line){
-                            Result::Ok(resource) => {
+                            Result::Ok(mut resource) => {
                                 if resource.require_adapter.is_none() {
                                     warn!("{}", DscError::MissingRequires(adapter_name.to_string(), resource.type_name.to_string()).to_string());
                                     continue;
                                 }
+                                // adapted resources are as trusted as the adapter listing them
+                                resource.trust = adapter.trust.clone();
 
                                 if name_filter.is_match(&resource.type_name) {
                                     insert_resource(&mut adapted_resources, &resource);
@@ -617,6 +610,20 @@ fn filter_resources(found_resources: &mut DiscoveryResourceCache, required_resou
     }
 }
 
+/// Verify the signature of the manifest of a resource if trusted publishers are configured.
+///
+/// Untrusted resources are still discovered so they can be listed, invoking them is refused when
+/// trusted publishers are enforced.
+fn verify_resource_trust(resource: &mut DscResource, trusted_publishers: &TrustedPublishersSetting) {
+    if !trusted_publishers.is_enabled() {
+        return;
+    }
+    resource.trust = verify_manifest_signature(&resource.path, &trusted_publishers.publishers);
+    if let ManifestTrust::Untrusted { reason } = &resource.trust {
+        info!("{}", t!("discovery.commandDiscovery.untrustedResource", resource = resource.type_name, reason = reason));
+    }
+}
+
 /// Inserts a resource into tree adding to vector if already exists
 fn insert_resource(resources: &mut DiscoveryResourceCache, resource: &DscResource) {
     if let Some(resource_versions) = resources.get_mut(&resource.type_name) {
@@ -731,6 +738,30 @@ pub fn load_manifest_content(content: &Value) -> Result<Vec<ImportedManifest>, D
 ///
 /// * Returns a `DscError` if the manifest could not be loaded or parsed.
 pub fn load_manifest(path: &Path) -> Result<Vec<ImportedManifest>, DscError> {
+    let parsed_manifests = parse_manifest_file(path)?;
+    import_parsed_manifests(path, &parsed_manifests)
+}
+
+/// A manifest parsed from a file before its condition is evaluated.
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) enum ParsedManifest {
+    AdaptedResource(AdaptedDscResourceManifest),
+    Resource(ResourceManifest),
+    Extension(ExtensionManifest),
+}
+
+/// Reads and parses the manifests in a file without evaluating their conditions, so the result
+/// doesn't depend on the environment and can be cached.
+///
+/// # Arguments
+///
+/// * `path` - The path to the manifest file.
+///
+/// # Errors
+///
+/// * Returns a `DscError` if the manifest file could not be read or parsed.
+pub(crate) fn parse_manifest_file(path: &Path) -> Result<Vec<ParsedManifest>, DscError> {
     let contents = read_to_string(path)?;
     let Some(file_name_lowercase) = path.file_name().and_then(OsStr::to_str).map(|s| s.to_lowercase()) else {
         return Err(DscError::InvalidManifest(t!("discovery.commandDiscovery.invalidManifestFileName", path = path.to_string_lossy()).to_string()));
@@ -752,12 +783,7 @@ pub fn load_manifest(path: &Path) -> Result<Vec<ImportedManifest>, DscError> {
                 }
             }
         };
-        if !evaluate_condition(resource.condition.as_deref())? {
-            debug!("{}", t!("discovery.commandDiscovery.conditionNotMet", path = path.to_string_lossy(), condition = resource.condition.unwrap_or_default(), resource = resource.type_name));
-            return Ok(vec![]);
-        }
-        let resource = load_adapted_resource_manifest(path, &resource)?;
-        return Ok(vec![ImportedManifest::Resource(resource)]);
+        return Ok(vec![ParsedManifest::AdaptedResource(resource)]);
     }
     if DSC_RESOURCE_EXTENSIONS.iter().any(|ext| file_name_lowercase.ends_with(ext)) {
         let manifest = if extension_is_json {
@@ -775,12 +801,7 @@ pub fn load_manifest(path: &Path) -> Result<Vec<ImportedManifest>, DscError> {
                 }
             }
         };
-        if !evaluate_condition(manifest.condition.as_deref())? {
-            debug!("{}", t!("discovery.commandDiscovery.conditionNotMet", path = path.to_string_lossy(), condition = manifest.condition.unwrap_or_default(), resource = manifest.resource_type));
-            return Ok(vec![]);
-        }
-        let resource = load_resource_manifest(path, &manifest)?;
-        return Ok(vec![ImportedManifest::Resource(resource)]);
+        return Ok(vec![ParsedManifest::Resource(manifest)]);
     }
     if DSC_EXTENSION_EXTENSIONS.iter().any(|ext| file_name_lowercase.ends_with(ext)) {
         let manifest = if extension_is_json {
@@ -798,15 +819,9 @@ pub fn load_manifest(path: &Path) -> Result<Vec<ImportedManifest>, DscError> {
                 }
             }
         };
-        if !evaluate_condition(manifest.condition.as_deref())? {
-                debug!("{}", t!("discovery.commandDiscovery.conditionNotMet", path = path.to_string_lossy(), condition = manifest.condition.unwrap_or_default(), resource = manifest.r#type));
-                return Ok(vec![]);
-        }
-        let extension = load_extension_manifest(path, &manifest)?;
-        return Ok(vec![ImportedManifest::Extension(extension)]);
+        return Ok(vec![ParsedManifest::Extension(manifest)]);
     }
     if DSC_MANIFEST_LIST_EXTENSIONS.iter().any(|ext| file_name_lowercase.ends_with(ext)) {
-        let mut resources: Vec<ImportedManifest> = vec![];
         let manifest_list = if extension_is_json {
             match serde_json::from_str::<ManifestList>(&contents) {
                 Ok(manifest) => manifest,
@@ -822,39 +837,57 @@ pub fn load_manifest(path: &Path) -> Result<Vec<ImportedManifest>, DscError> {
                 }
             }
         };
-        if let Some(adapted_resources) = &manifest_list.adapted_resources {
-            for resource in adapted_resources {
+        let mut manifests: Vec<ParsedManifest> = vec![];
+        manifests.extend(manifest_list.adapted_resources.unwrap_or_default().into_iter().map(ParsedManifest::AdaptedResource));
+        manifests.extend(manifest_list.resources.unwrap_or_default().into_iter().map(ParsedManifest::Resource));
+        manifests.extend(manifest_list.extensions.unwrap_or_default().into_iter().map(ParsedManifest::Extension));
+        return Ok(manifests);
+    }
+    Err(DscError::InvalidManifest(t!("discovery.commandDiscovery.invalidManifestFile", resource = path.to_string_lossy()).to_string()))
+}
+
+/// Evaluates the conditions of parsed manifests and imports the ones whose condition is met.
+/// The executables of the imported manifests are checked every time.
+///
+/// # Arguments
+///
+/// * `path` - The path to the manifest file the manifests were parsed from.
+/// * `parsed_manifests` - The manifests parsed from the file.
+///
+/// # Errors
+///
+/// * Returns a `DscError` if a condition can't be evaluated or a manifest can't be imported.
+pub(crate) fn import_parsed_manifests(path: &Path, parsed_manifests: &[ParsedManifest]) -> Result<Vec<ImportedManifest>, DscError> {
+    let mut manifests: Vec<ImportedManifest> = vec![];
+    for parsed_manifest in parsed_manifests {
+        match parsed_manifest {
+            ParsedManifest::AdaptedResource(resource) => {
                 if !evaluate_condition(resource.condition.as_deref())? {
                     debug!("{}", t!("discovery.commandDiscovery.conditionNotMet", path = path.to_string_lossy(), condition = resource.condition.as_ref() : {:?}, resource = resource.type_name));
                     continue;
                 }
                 let resource = load_adapted_resource_manifest(path, resource)?;
-                resources.push(ImportedManifest::Resource(resource));
-            }
-        }
-        if let Some(resource_manifests) = &manifest_list.resources {
-            for res_manifest in resource_manifests {
-                if !evaluate_condition(res_manifest.condition.as_deref())? {
-                    debug!("{}", t!("discovery.commandDiscovery.conditionNotMet", path = path.to_string_lossy(), condition = res_manifest.condition.as_ref() : {:?}, resource = res_manifest.resource_type));
+                manifests.push(ImportedManifest::Resource(resource));
+            },
+            ParsedManifest::Resource(manifest) => {
+                if !evaluate_condition(manifest.condition.as_deref())? {
+                    debug!("{}", t!("discovery.commandDiscovery.conditionNotMet", path = path.to_string_lossy(), condition = manifest.condition.as_ref() : {:?}, resource = manifest.resource_type));
                     continue;
                 }
-                let resource = load_resource_manifest(path, res_manifest)?;
-                resources.push(ImportedManifest::Resource(resource));
-            }
-        }
-        if let Some(extension_manifests) = &manifest_list.extensions {
-            for ext_manifest in extension_manifests {
-                if !evaluate_condition(ext_manifest.condition.as_deref())? {
-                    debug!("{}", t!("discovery.commandDiscovery.conditionNotMet", path = path.to_string_lossy(), condition = ext_manifest.condition.as_ref() : {:?}, resource = ext_manifest.r#type));
+                let resource = load_resource_manifest(path, manifest)?;
+                manifests.push(ImportedManifest::Resource(resource));
+            },
+            ParsedManifest::Extension(manifest) => {
+                if !evaluate_condition(manifest.condition.as_deref())? {
+                    debug!("{}", t!("discovery.commandDiscovery.conditionNotMet", path = path.to_string_lossy(), condition = manifest.condition.as_ref() : {:?}, resource = manifest.r#type));
                     continue;
                 }
-                let extension = load_extension_manifest(path, ext_manifest)?;
-                resources.push(ImportedManifest::Extension(extension));
-            }
+                let extension = load_extension_manifest(path, manifest)?;
+                manifests.push(ImportedManifest::Extension(extension));
+            },
         }
-        return Ok(resources);
     }
-    Err(DscError::InvalidManifest(t!("discovery.commandDiscovery.invalidManifestFile", resource = path.to_string_lossy()).to_string()))
+    Ok(manifests)
 }
 
 pub fn load_adapted_resource_manifest(path: &Path, manifest: &AdaptedDscResourceManifest) -> Result<DscResource, DscError> {
@@ -955,6 +988,12 @@ fn load_resource_manifest(path: &Path, manifest: &ResourceManifest) -> Result<Ds
         verify_executable(&manifest.resource_type, "what-if", &what_if.executable, path.parent().unwrap());
         capabilities.insert(Capability::SetWhatIf);
     }
+    if let Some(server) = &manifest.server {
+        verify_executable(&manifest.resource_type, "server", &server.executable, path.parent().unwrap());
+    }
+    if manifest.batch == Some(true) {
+        capabilities.insert(Capability::Batch);
+    }
 
     let mut resource = DscResource::new();
     let mut capabilities: Vec<Capability> = capabilities.into_iter().collect();
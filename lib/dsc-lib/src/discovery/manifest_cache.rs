@@ -0,0 +1,406 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use crate::discovery::command_discovery::{
+    import_parsed_manifests, parse_manifest_file, CommandDiscovery, ImportedManifest, ParsedManifest,
+    DSC_ADAPTED_RESOURCE_EXTENSIONS, DSC_EXTENSION_EXTENSIONS, DSC_MANIFEST_LIST_EXTENSIONS, DSC_RESOURCE_EXTENSIONS,
+};
+use crate::discovery::discovery_trait::DiscoveryKind;
+use crate::dscerror::DscError;
+use rust_i18n::t;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{create_dir_all, metadata, read, remove_file, rename, write};
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+use std::thread;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::{debug, info, trace, warn};
+
+static MANIFEST_CACHE: LazyLock<Mutex<ManifestCache>> = LazyLock::new(|| Mutex::new(ManifestCache::load()));
+
+/// Information about the manifest cache returned by the cache commands.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestCacheInfo {
+    /// The path to the cache file.
+    pub path: String,
+    /// The number of directories in the cache.
+    pub directories: usize,
+    /// The number of manifests in the cache.
+    pub manifests: usize,
+}
+
+/// Parsed manifests of the resource directories persisted between invocations. Manifests are
+/// cached before their conditions are evaluated since conditions depend on the environment.
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct ManifestCache {
+    /// The version of DSC that wrote the cache, a different version discards the cache.
+    version: String,
+    directories: HashMap<PathBuf, CachedDirectory>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+struct CachedDirectory {
+    /// The last modified time of the directory, which changes when files are added or removed.
+    modified: Option<u64>,
+    /// The manifest files in the directory by file name.
+    files: HashMap<String, CachedFile>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct CachedFile {
+    modified: Option<u64>,
+    size: u64,
+    /// The manifests parsed from the file, `None` if the file failed to parse.
+    manifests: Option<Vec<ParsedManifest>>,
+}
+
+impl ManifestCache {
+    fn load() -> Self {
+        let file_path = get_manifest_cache_file_path();
+        let cache = match read(&file_path) {
+            Ok(data) => serde_json::from_slice::<ManifestCache>(&data).unwrap_or_else(|err| {
+                info!("{}", t!("discovery.manifestCache.invalidCache", path = file_path.to_string_lossy(), error = err));
+                ManifestCache::default()
+            }),
+            Err(_) => ManifestCache::default(),
+        };
+
+        if cache.version != env!("CARGO_PKG_VERSION") {
+            debug!("{}", t!("discovery.manifestCache.versionMismatch", version = cache.version));
+            return ManifestCache::new();
+        }
+
+        debug!("{}", t!("discovery.manifestCache.loaded", count = cache.directories.len(), path = file_path.to_string_lossy()));
+        cache
+    }
+
+    fn new() -> Self {
+        ManifestCache {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            directories: HashMap::new(),
+        }
+    }
+
+    fn save(&self) {
+        let file_path = get_manifest_cache_file_path();
+        let Some(parent) = file_path.parent() else {
+            info!("{}", t!("discovery.manifestCache.failedToSave", path = file_path.to_string_lossy(), error = "no parent directory"));
+            return;
+        };
+        let json = match serde_json::to_vec(self) {
+            Ok(json) => json,
+            Err(err) => {
+                info!("{}", t!("discovery.manifestCache.failedToSave", path = file_path.to_string_lossy(), error = err));
+                return;
+            }
+        };
+
+        // write to a temporary file and rename so concurrent invocations never read a partial cache
+        let temp_path = file_path.with_extension(format!("{}.tmp", std::process::id()));
+        let result = create_dir_all(parent)
+            .and_then(|()| write(&temp_path, json))
+            .and_then(|()| rename(&temp_path, &file_path));
+        match result {
+            Ok(()) => debug!("{}", t!("discovery.manifestCache.saved", count = self.directories.len(), path = file_path.to_string_lossy())),
+            Err(err) => {
+                let _ = remove_file(&temp_path);
+                info!("{}", t!("discovery.manifestCache.failedToSave", path = file_path.to_string_lossy(), error = err));
+            }
+        }
+    }
+
+    fn info(&self) -> ManifestCacheInfo {
+        ManifestCacheInfo {
+            path: get_manifest_cache_file_path().to_string_lossy().to_string(),
+            directories: self.directories.len(),
+            manifests: self.directories.values()
+                .flat_map(|directory| directory.files.values())
+                .filter_map(|file| file.manifests.as_ref())
+                .map(Vec::len)
+                .sum(),
+        }
+    }
+}
+
+/// Get the manifests in the directories, using the cache for directories and files that haven't changed.
+///
+/// # Arguments
+///
+/// * `paths` - The directories to search for manifests.
+/// * `kind` - The kind of manifests to return, `None` returns all manifests.
+///
+/// # Returns
+///
+/// The manifests found in the directories in the order of the directories.
+pub(crate) fn get_manifests(paths: &[PathBuf], kind: Option<&DiscoveryKind>) -> Vec<ImportedManifest> {
+    let directories: Vec<&PathBuf> = paths.iter().filter(|path| path.is_dir()).collect();
+    let mut cache = MANIFEST_CACHE.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+    let previous: Vec<Option<CachedDirectory>> = directories.iter().map(|directory| cache.directories.get(*directory).cloned()).collect();
+
+    let scanned = scan_directories(&directories, previous);
+    let mut changed = false;
+    let mut manifests = Vec::new();
+    for (directory, (cached_directory, directory_changed)) in directories.into_iter().zip(scanned) {
+        changed |= directory_changed;
+        let mut file_names: Vec<&String> = cached_directory.files.keys().collect();
+        file_names.sort();
+        for file_name in file_names {
+            if kind.is_some_and(|kind| !is_manifest_of_kind(file_name, kind)) {
+                continue;
+            }
+            let Some(parsed_manifests) = &cached_directory.files[file_name].manifests else {
+                continue;
+            };
+            // conditions and executables are checked on every load as they depend on the environment
+            match import_parsed_manifests(&directory.join(file_name), parsed_manifests) {
+                Ok(file_manifests) => manifests.extend(file_manifests),
+                Err(e) => info!("{}", t!("discovery.commandDiscovery.failedLoadManifest", err = e)),
+            }
+        }
+        cache.directories.insert(directory.clone(), cached_directory);
+    }
+
+    if changed {
+        cache.save();
+    }
+    manifests
+}
+
+/// Delete the manifest cache.
+///
+/// # Errors
+///
+/// This function will return an error if the cache file exists and can't be deleted.
+pub fn clear_manifest_cache() -> Result<ManifestCacheInfo, DscError> {
+    let mut cache = MANIFEST_CACHE.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+    *cache = ManifestCache::new();
+    let file_path = get_manifest_cache_file_path();
+    if file_path.exists() {
+        info!("{}", t!("discovery.manifestCache.clearing", path = file_path.to_string_lossy()));
+        remove_file(&file_path)?;
+    }
+    Ok(cache.info())
+}
+
+/// Delete the manifest cache and rebuild it from the resource directories.
+///
+/// # Errors
+///
+/// This function will return an error if the cache can't be deleted or the resource directories can't be determined.
+pub fn rebuild_manifest_cache() -> Result<ManifestCacheInfo, DscError> {
+    clear_manifest_cache()?;
+    let paths = CommandDiscovery::get_resource_paths()?;
+    get_manifests(&paths, None);
+    let cache = MANIFEST_CACHE.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+    Ok(cache.info())
+}
+
+/// Scan the directories in parallel, returning the updated cache entry of each directory and whether it changed.
+fn scan_directories(directories: &[&PathBuf], previous: Vec<Option<CachedDirectory>>) -> Vec<(CachedDirectory, bool)> {
+    if directories.is_empty() {
+        return vec![];
+    }
+
+    let workers = thread::available_parallelism().map_or(1, std::num::NonZero::get);
+    let chunk_size = directories.len().div_ceil(workers);
+    let mut work: Vec<(&PathBuf, Option<CachedDirectory>)> = directories.iter().copied().zip(previous).collect();
+    let mut chunks = Vec::new();
+    while !work.is_empty() {
+        let rest = work.split_off(chunk_size.min(work.len()));
+        chunks.push(work);
+        work = rest;
+    }
+
+    thread::scope(|scope| {
+        let handles: Vec<_> = chunks.into_iter().map(|chunk| {
+            scope.spawn(move || {
+                chunk.into_iter().map(|(directory, cached)| scan_directory(directory, cached)).collect::<Vec<_>>()
+            })
+        }).collect();
+
+        handles.into_iter().flat_map(|handle| handle.join().unwrap_or_else(|_| {
+            warn!("{}", t!("discovery.manifestCache.scanFailed"));
+            vec![]
+        })).collect()
+    })
+}
+
+/// Scan a directory for manifests, reusing cached manifests of files that haven't changed.
+/// The file listing is only read again if the directory was modified.
+fn scan_directory(directory: &Path, cached: Option<CachedDirectory>) -> (CachedDirectory, bool) {
+    let modified = metadata(directory).ok().and_then(|m| get_modified_time(m.modified().ok()));
+    let (mut previous_files, file_names, mut changed) = match cached {
+        Some(cached) if modified.is_some() && cached.modified == modified => {
+            trace!("{}", t!("discovery.manifestCache.directoryUnchanged", path = directory.to_string_lossy()));
+            let file_names: Vec<String> = cached.files.keys().cloned().collect();
+            (cached.files, file_names, false)
+        },
+        cached => {
+            trace!("{}", t!("discovery.manifestCache.scanningDirectory", path = directory.to_string_lossy()));
+            (cached.map(|c| c.files).unwrap_or_default(), list_manifest_files(directory), true)
+        }
+    };
+
+    let mut files = HashMap::new();
+    for file_name in file_names {
+        let path = directory.join(&file_name);
+        let Ok(file_metadata) = metadata(&path) else {
+            changed = true;
+            continue;
+        };
+        if !file_metadata.is_file() {
+            continue;
+        }
+        let file_modified = get_modified_time(file_metadata.modified().ok());
+        let size = file_metadata.len();
+        if let Some(cached_file) = previous_files.remove(&file_name)
+            && cached_file.manifests.is_some()
+            && file_modified.is_some()
+            && cached_file.modified == file_modified
+            && cached_file.size == size {
+            files.insert(file_name, cached_file);
+            continue;
+        }
+
+        changed = true;
+        trace!("{}", t!("discovery.commandDiscovery.foundManifest", path = path.to_string_lossy()));
+        let manifests = match parse_manifest_file(&path) {
+            Ok(manifests) => Some(manifests),
+            Err(e) => {
+                // At this point we can't determine whether or not the bad manifest contains
+                // resource that is requested by resource/config operation
+                // if it is, then "ResouceNotFound" error will be issued later
+                // and here we just write as info
+                info!("{}", t!("discovery.commandDiscovery.failedLoadManifest", err = e));
+                None
+            }
+        };
+        files.insert(file_name, CachedFile { modified: file_modified, size, manifests });
+    }
+
+    (CachedDirectory { modified, files }, changed)
+}
+
+fn list_manifest_files(directory: &Path) -> Vec<String> {
+    let Ok(entries) = directory.read_dir() else {
+        return vec![];
+    };
+
+    entries.filter_map(Result::ok)
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .filter(|file_name| is_manifest(file_name))
+        .collect()
+}
+
+fn is_manifest(file_name: &str) -> bool {
+    let file_name_lowercase = file_name.to_lowercase();
+    DSC_MANIFEST_LIST_EXTENSIONS.iter()
+        .chain(DSC_RESOURCE_EXTENSIONS.iter())
+        .chain(DSC_ADAPTED_RESOURCE_EXTENSIONS.iter())
+        .chain(DSC_EXTENSION_EXTENSIONS.iter())
+        .any(|ext| file_name_lowercase.ends_with(ext))
+}
+
+fn is_manifest_of_kind(file_name: &str, kind: &DiscoveryKind) -> bool {
+    let file_name_lowercase = file_name.to_lowercase();
+    DSC_MANIFEST_LIST_EXTENSIONS.iter().any(|ext| file_name_lowercase.ends_with(ext)) ||
+        (kind == &DiscoveryKind::Resource && (DSC_RESOURCE_EXTENSIONS.iter().any(|ext| file_name_lowercase.ends_with(ext)) || DSC_ADAPTED_RESOURCE_EXTENSIONS.iter().any(|ext| file_name_lowercase.ends_with(ext)))) ||
+        (kind == &DiscoveryKind::Extension && DSC_EXTENSION_EXTENSIONS.iter().any(|ext| file_name_lowercase.ends_with(ext)))
+}
+
+fn get_modified_time(modified: Option<SystemTime>) -> Option<u64> {
+    modified
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .and_then(|duration| u64::try_from(duration.as_nanos()).ok())
+}
+
+#[cfg(target_os = "windows")]
+fn get_manifest_cache_file_path() -> PathBuf
+{
+    // $env:LocalAppData+"dsc\ManifestCache.json"
+    let Ok(local_app_data_path) = std::env::var("LocalAppData") else { return PathBuf::new(); };
+    Path::new(&local_app_data_path).join("dsc").join("ManifestCache.json")
+}
+
+#[cfg(not(target_os = "windows"))]
+fn get_manifest_cache_file_path() -> PathBuf
+{
+    // $env:HOME+".dsc/ManifestCache.json"
+    let Ok(home_path) = std::env::var("HOME") else { return PathBuf::new(); };
+    Path::new(&home_path).join(".dsc").join("ManifestCache.json")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    const MANIFEST: &str = r#"{
+        "$schema": "https://aka.ms/dsc/schemas/v3/bundled/resource/manifest.json",
+        "type": "Test/CachedResource",
+        "version": "1.0.0",
+        "get": {
+            "executable": "dsc"
+        }
+    }"#;
+
+    #[test]
+    fn unchanged_directory_uses_cache() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+        fs::write(dir.join("cached.dsc.resource.json"), MANIFEST).unwrap();
+        let (cached, changed) = scan_directory(dir, None);
+        assert!(changed);
+        assert_eq!(cached.files.len(), 1);
+
+        let (cached, changed) = scan_directory(dir, Some(cached));
+        assert!(!changed);
+        assert_eq!(cached.files.len(), 1);
+    }
+
+    #[test]
+    fn modified_file_is_reloaded() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+        let path = dir.join("cached.dsc.resource.json");
+        fs::write(&path, MANIFEST).unwrap();
+        let (cached, _) = scan_directory(dir, None);
+
+        fs::write(&path, MANIFEST.replace("1.0.0", "1.10.0")).unwrap();
+        let (cached, changed) = scan_directory(dir, Some(cached));
+        assert!(changed);
+        let manifests = cached.files["cached.dsc.resource.json"].manifests.as_ref().unwrap();
+        let ParsedManifest::Resource(manifest) = &manifests[0] else {
+            panic!("expected a resource");
+        };
+        assert_eq!(manifest.version.to_string(), "1.10.0");
+    }
+
+    #[test]
+    fn condition_is_evaluated_on_every_load() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+        let path = dir.join("cached.dsc.resource.json");
+        fs::write(&path, MANIFEST.replace(r#""version""#, r#""condition": "[equals(1, 2)]", "version""#)).unwrap();
+        let (cached, _) = scan_directory(dir, None);
+
+        let (cached, changed) = scan_directory(dir, Some(cached));
+        assert!(!changed);
+        let parsed_manifests = cached.files["cached.dsc.resource.json"].manifests.as_ref().unwrap();
+        assert_eq!(parsed_manifests.len(), 1);
+        let imported = import_parsed_manifests(&path, parsed_manifests).unwrap();
+        assert!(imported.is_empty());
+    }
+
+    #[test]
+    fn non_manifest_files_are_ignored() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+        fs::write(dir.join("readme.txt"), "not a manifest").unwrap();
+        let (cached, _) = scan_directory(dir, None);
+        assert!(cached.files.is_empty());
+    }
+}
@@ -0,0 +1,208 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use crate::util::get_setting;
+use base64::{Engine as _, engine::general_purpose};
+use ed25519_dalek::{Signature, VerifyingKey};
+use rust_i18n::t;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+/// The extension appended to the file name of a manifest for its detached signature.
+pub const SIGNATURE_EXTENSION: &str = ".sig";
+
+/// Lines of a signature file starting with this prefix are ignored, like in minisign signatures.
+const COMMENT_PREFIX: &str = "untrusted comment:";
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase", tag = "status")]
+pub enum ManifestTrust {
+    /// No trusted publishers are configured, so the signature of the manifest wasn't verified.
+    #[default]
+    NotVerified,
+    /// The manifest is signed by a trusted publisher.
+    Trusted {
+        /// The name of the trusted publisher whose key verified the signature.
+        publisher: String,
+    },
+    /// The manifest isn't signed by a trusted publisher.
+    Untrusted {
+        /// Why the signature couldn't be verified.
+        reason: String,
+    },
+}
+
+impl ManifestTrust {
+    #[must_use]
+    pub fn is_not_verified(&self) -> bool {
+        matches!(self, ManifestTrust::NotVerified)
+    }
+
+    #[must_use]
+    pub fn is_untrusted(&self) -> bool {
+        matches!(self, ManifestTrust::Untrusted { .. })
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct TrustedPublishersSetting {
+    /// whether resources and extensions that aren't signed by a trusted publisher are refused
+    #[serde(default)]
+    pub enforce: bool,
+    /// the publishers whose signatures are trusted
+    #[serde(default)]
+    pub publishers: Vec<TrustedPublisher>,
+}
+
+impl TrustedPublishersSetting {
+    /// Whether manifest signatures need to be verified.
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.enforce || !self.publishers.is_empty()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TrustedPublisher {
+    /// the name reported for resources signed by this publisher
+    pub name: String,
+    /// the base64 encoded ed25519 public key of the publisher
+    #[serde(rename = "publicKey")]
+    pub public_key: String,
+}
+
+/// Get the `trustedPublishers` setting, the policy takes precedence over the settings file.
+#[must_use]
+pub fn get_trusted_publishers_setting() -> TrustedPublishersSetting {
+    let Ok(value) = get_setting("trustedPublishers") else {
+        return TrustedPublishersSetting::default();
+    };
+    let setting = if value.policy.is_null() { value.setting } else { value.policy };
+    match serde_json::from_value::<TrustedPublishersSetting>(setting) {
+        Ok(setting) => setting,
+        Err(err) => {
+            // fail closed so a malformed policy doesn't silently allow everything
+            warn!("{}", t!("discovery.manifestSignature.invalidSetting", error = err));
+            TrustedPublishersSetting { enforce: true, publishers: Vec::new() }
+        }
+    }
+}
+
+/// Get the path of the detached signature of a manifest.
+#[must_use]
+pub fn get_signature_path(manifest_path: &Path) -> PathBuf {
+    let mut path: OsString = manifest_path.as_os_str().to_os_string();
+    path.push(SIGNATURE_EXTENSION);
+    PathBuf::from(path)
+}
+
+/// Verify the detached ed25519 signature of a manifest file against the trusted publishers.
+///
+/// The signature file is next to the manifest with the `.sig` extension appended to the file
+/// name and contains the base64 encoded signature of the manifest file content.
+///
+/// # Arguments
+///
+/// * `manifest_path` - The path to the manifest file
+/// * `publishers` - The trusted publishers
+#[must_use]
+pub fn verify_manifest_signature(manifest_path: &Path, publishers: &[TrustedPublisher]) -> ManifestTrust {
+    let signature_path = get_signature_path(manifest_path);
+    let Ok(signature) = fs::read_to_string(&signature_path) else {
+        return ManifestTrust::Untrusted { reason: t!("discovery.manifestSignature.signatureNotFound", path = signature_path.display()).to_string() };
+    };
+    let signature = match decode_signature(&signature) {
+        Ok(signature) => signature,
+        Err(reason) => return ManifestTrust::Untrusted { reason },
+    };
+    let content = match fs::read(manifest_path) {
+        Ok(content) => content,
+        Err(err) => return ManifestTrust::Untrusted { reason: err.to_string() },
+    };
+
+    for publisher in publishers {
+        let key = match decode_public_key(&publisher.public_key) {
+            Ok(key) => key,
+            Err(reason) => {
+                warn!("{}", t!("discovery.manifestSignature.invalidPublicKey", publisher = publisher.name, reason = reason));
+                continue;
+            }
+        };
+        if key.verify_strict(&content, &signature).is_ok() {
+            debug!("{}", t!("discovery.manifestSignature.verified", path = manifest_path.display(), publisher = publisher.name));
+            return ManifestTrust::Trusted { publisher: publisher.name.clone() };
+        }
+    }
+
+    ManifestTrust::Untrusted { reason: t!("discovery.manifestSignature.noTrustedPublisher", path = signature_path.display()).to_string() }
+}
+
+fn decode_signature(content: &str) -> Result<Signature, String> {
+    let Some(encoded) = content.lines().map(str::trim).find(|line| !line.is_empty() && !line.starts_with(COMMENT_PREFIX)) else {
+        return Err(t!("discovery.manifestSignature.emptySignature").to_string());
+    };
+    let bytes = general_purpose::STANDARD.decode(encoded).map_err(|err| err.to_string())?;
+    let bytes: [u8; 64] = bytes.try_into().map_err(|_| t!("discovery.manifestSignature.invalidSignatureLength").to_string())?;
+    Ok(Signature::from_bytes(&bytes))
+}
+
+fn decode_public_key(encoded: &str) -> Result<VerifyingKey, String> {
+    let bytes = general_purpose::STANDARD.decode(encoded.trim()).map_err(|err| err.to_string())?;
+    let bytes: [u8; 32] = bytes.try_into().map_err(|_| t!("discovery.manifestSignature.invalidPublicKeyLength").to_string())?;
+    VerifyingKey::from_bytes(&bytes).map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    fn write_signed_manifest(dir: &Path, key: &SigningKey) -> PathBuf {
+        let path = dir.join("signed.dsc.resource.json");
+        let content = br#"{"type":"Test/Signed"}"#;
+        fs::write(&path, content).unwrap();
+        let signature = general_purpose::STANDARD.encode(key.sign(content).to_bytes());
+        fs::write(get_signature_path(&path), format!("{COMMENT_PREFIX} test\n{signature}\n")).unwrap();
+        path
+    }
+
+    fn publisher(name: &str, key: &SigningKey) -> TrustedPublisher {
+        TrustedPublisher { name: name.to_string(), public_key: general_purpose::STANDARD.encode(key.verifying_key().to_bytes()) }
+    }
+
+    #[test]
+    fn signature_from_trusted_publisher() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let other = SigningKey::from_bytes(&[9u8; 32]);
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_signed_manifest(dir.path(), &key);
+        let trust = verify_manifest_signature(&path, &[publisher("Other", &other), publisher("Contoso", &key)]);
+        assert_eq!(trust, ManifestTrust::Trusted { publisher: "Contoso".to_string() });
+    }
+
+    #[test]
+    fn signature_from_untrusted_publisher() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let other = SigningKey::from_bytes(&[9u8; 32]);
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_signed_manifest(dir.path(), &key);
+        assert!(verify_manifest_signature(&path, &[publisher("Other", &other)]).is_untrusted());
+
+        // tampering with the manifest invalidates the signature
+        fs::write(&path, br#"{"type":"Test/Tampered"}"#).unwrap();
+        assert!(verify_manifest_signature(&path, &[publisher("Contoso", &key)]).is_untrusted());
+    }
+
+    #[test]
+    fn missing_signature_is_untrusted() {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("unsigned.dsc.resource.json");
+        fs::write(&path, "{}").unwrap();
+        assert!(verify_manifest_signature(&path, &[publisher("Contoso", &key)]).is_untrusted());
+    }
+}
@@ -0,0 +1,612 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use crate::discovery::command_discovery::{DSC_ADAPTED_RESOURCE_EXTENSIONS, DSC_EXTENSION_EXTENSIONS, DSC_MANIFEST_LIST_EXTENSIONS, DSC_RESOURCE_EXTENSIONS};
+use crate::dscerror::DscError;
+use crate::dscresources::adapted_resource_manifest::{AdaptedDscResourceManifest, AdaptedPathOrContent};
+use crate::dscresources::resource_manifest::{CommandEnvironment, ExecutableDigest, GetArgKind, InputKind, Kind, ResourceManifest, SchemaKind, SetDeleteArgKind, WorkingDirectoryBase, INPUT_FILE_PLACEHOLDER};
+use crate::extensions::{import::ImportArgKind, secret::SecretArgKind};
+use crate::extensions::extension_manifest::ExtensionManifest;
+use crate::schemas::dsc_repo::DscRepoSchema;
+use crate::types::ExitCodesMap;
+use crate::util::canonicalize_which;
+use jsonschema::Validator;
+use rust_i18n::t;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs::read_to_string;
+use std::path::Path;
+use tracing::debug;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum DiagnosticSeverity {
+    /// The manifest is invalid or DSC can't use the resource or extension as declared.
+    Error,
+    /// The manifest is usable but likely doesn't behave as the author intended.
+    Warning,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestDiagnostic {
+    pub severity: DiagnosticSeverity,
+    /// The identifier of the rule that produced the diagnostic.
+    pub code: String,
+    pub message: String,
+    /// The JSON pointer to the part of the manifest the diagnostic applies to.
+    pub pointer: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestValidationResult {
+    /// The path of the validated manifest file.
+    pub path: String,
+    /// Whether the manifest has no diagnostics with the error severity.
+    pub valid: bool,
+    pub diagnostics: Vec<ManifestDiagnostic>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManifestFileKind {
+    AdaptedResource,
+    Extension,
+    List,
+    Resource,
+}
+
+impl ManifestFileKind {
+    /// Get the kind of manifest from the file name, if it uses one of the manifest extensions.
+    #[must_use]
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let file_name = path.file_name()?.to_str()?.to_lowercase();
+        if DSC_ADAPTED_RESOURCE_EXTENSIONS.iter().any(|ext| file_name.ends_with(ext)) {
+            Some(ManifestFileKind::AdaptedResource)
+        } else if DSC_EXTENSION_EXTENSIONS.iter().any(|ext| file_name.ends_with(ext)) {
+            Some(ManifestFileKind::Extension)
+        } else if DSC_MANIFEST_LIST_EXTENSIONS.iter().any(|ext| file_name.ends_with(ext)) {
+            Some(ManifestFileKind::List)
+        } else if DSC_RESOURCE_EXTENSIONS.iter().any(|ext| file_name.ends_with(ext)) {
+            Some(ManifestFileKind::Resource)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Default)]
+struct Diagnostics {
+    items: Vec<ManifestDiagnostic>,
+}
+
+impl Diagnostics {
+    fn error(&mut self, code: &str, pointer: &str, message: String) {
+        self.add(DiagnosticSeverity::Error, code, pointer, message);
+    }
+
+    fn warning(&mut self, code: &str, pointer: &str, message: String) {
+        self.add(DiagnosticSeverity::Warning, code, pointer, message);
+    }
+
+    fn add(&mut self, severity: DiagnosticSeverity, code: &str, pointer: &str, message: String) {
+        debug!("{}", t!("discovery.manifestValidation.diagnostic", code = code, pointer = pointer, message = message));
+        self.items.push(ManifestDiagnostic { severity, code: code.to_string(), message, pointer: pointer.to_string() });
+    }
+}
+
+/// Validate a resource, adapted resource, extension, or manifest list file.
+///
+/// Unlike discovery, which skips invalid manifests and only traces missing executables, the
+/// validation reports every problem found as a diagnostic.
+///
+/// # Arguments
+///
+/// * `path` - The path to the manifest file.
+///
+/// # Errors
+///
+/// Error if the file can't be read.
+pub fn validate_manifest_file(path: &Path) -> Result<ManifestValidationResult, DscError> {
+    let contents = read_to_string(path)?;
+    let is_json = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+    let directory = path.parent().unwrap_or(Path::new("."));
+    let diagnostics = match parse_manifest(&contents, is_json) {
+        Ok(manifest) => validate_manifest(&manifest, ManifestFileKind::from_path(path), directory),
+        Err(message) => {
+            let mut diagnostics = Diagnostics::default();
+            diagnostics.error("invalidSyntax", "", message);
+            diagnostics.items
+        }
+    };
+    Ok(ManifestValidationResult {
+        path: path.to_string_lossy().to_string(),
+        valid: !diagnostics.iter().any(|diagnostic| diagnostic.severity == DiagnosticSeverity::Error),
+        diagnostics,
+    })
+}
+
+/// Validate the content of a manifest.
+///
+/// # Arguments
+///
+/// * `manifest` - The manifest content.
+/// * `kind` - The kind of manifest, if not specified the kind is inferred from the content.
+/// * `directory` - The directory relative paths and executables are resolved from.
+#[must_use]
+pub fn validate_manifest(manifest: &Value, kind: Option<ManifestFileKind>, directory: &Path) -> Vec<ManifestDiagnostic> {
+    let mut diagnostics = Diagnostics::default();
+    let kind = kind.unwrap_or_else(|| infer_kind(manifest));
+    match kind {
+        ManifestFileKind::AdaptedResource => validate_adapted_resource(manifest, "", directory, &mut diagnostics),
+        ManifestFileKind::Extension => validate_extension(manifest, "", directory, &mut diagnostics),
+        ManifestFileKind::Resource => validate_resource(manifest, "", directory, &mut diagnostics),
+        ManifestFileKind::List => validate_list(manifest, directory, &mut diagnostics),
+    }
+    diagnostics.items
+}
+
+fn parse_manifest(contents: &str, is_json: bool) -> Result<Value, String> {
+    if is_json {
+        serde_json::from_str(contents).map_err(|err| err.to_string())
+    } else {
+        serde_yaml::from_str(contents).map_err(|err| err.to_string())
+    }
+}
+
+fn infer_kind(manifest: &Value) -> ManifestFileKind {
+    if manifest.get("requireAdapter").is_some() {
+        ManifestFileKind::AdaptedResource
+    } else if ["discover", "import", "importParameters", "secret"].iter().any(|method| manifest.get(method).is_some()) {
+        ManifestFileKind::Extension
+    } else if ["adaptedResources", "resources", "extensions"].iter().any(|list| manifest.get(list).is_some()) {
+        ManifestFileKind::List
+    } else {
+        ManifestFileKind::Resource
+    }
+}
+
+fn validate_list(manifest: &Value, directory: &Path, diagnostics: &mut Diagnostics) {
+    let Some(list) = manifest.as_object() else {
+        diagnostics.error("invalidManifest", "", t!("discovery.manifestValidation.listNotObject").to_string());
+        return;
+    };
+    for (key, value) in list {
+        let validate: fn(&Value, &str, &Path, &mut Diagnostics) = match key.as_str() {
+            "adaptedResources" => validate_adapted_resource,
+            "resources" => validate_resource,
+            "extensions" => validate_extension,
+            _ => {
+                diagnostics.error("invalidManifest", &format!("/{key}"), t!("discovery.manifestValidation.unknownListProperty", property = key).to_string());
+                continue;
+            }
+        };
+        let Some(items) = value.as_array() else {
+            diagnostics.error("invalidManifest", &format!("/{key}"), t!("discovery.manifestValidation.listPropertyNotArray", property = key).to_string());
+            continue;
+        };
+        for (index, item) in items.iter().enumerate() {
+            validate(item, &format!("/{key}/{index}"), directory, diagnostics);
+        }
+    }
+}
+
+#[allow(clippy::too_many_lines)]
+fn validate_resource(value: &Value, pointer: &str, directory: &Path, diagnostics: &mut Diagnostics) {
+    let manifest = match serde_json::from_value::<ResourceManifest>(value.clone()) {
+        Ok(manifest) => manifest,
+        Err(err) => {
+            diagnostics.error("invalidManifest", pointer, t!("discovery.manifestValidation.invalidResourceManifest", error = err).to_string());
+            return;
+        }
+    };
+
+    if manifest.validate_schema_uri().is_err() {
+        diagnostics.warning("unrecognizedSchema", &format!("{pointer}/$schema"), t!("discovery.manifestValidation.unrecognizedSchema", schema = manifest.schema_version).to_string());
+    }
+    if manifest.version.is_date_version() {
+        diagnostics.warning("dateVersion", &format!("{pointer}/version"), t!("discovery.manifestValidation.dateVersion", version = manifest.version).to_string());
+    }
+
+    let mut executables = Vec::new();
+    let mut environments = Vec::new();
+    if let Some(get) = &manifest.get {
+        executables.push(("get", get.executable.as_str(), get.sha256.as_ref()));
+        check_get_args("get", get.args.as_deref(), get.input.as_ref(), pointer, diagnostics);
+        environments.push(("get", &get.environment));
+    }
+    if let Some(set) = &manifest.set {
+        executables.push(("set", set.executable.as_str(), set.sha256.as_ref()));
+        check_set_args("set", set.args.as_deref(), set.input.as_ref(), pointer, diagnostics);
+        environments.push(("set", &set.environment));
+        let what_if_arg = set.args.as_deref().unwrap_or_default().iter().position(|arg| matches!(arg, SetDeleteArgKind::WhatIf { .. }));
+        if let Some(index) = what_if_arg {
+            if set.returns.is_none() && set.what_if_returns.is_none() {
+                diagnostics.warning("whatIfArgWithoutReturn", &format!("{pointer}/set/args/{index}"), t!("discovery.manifestValidation.whatIfArgWithoutReturn").to_string());
+            }
+            if manifest.what_if.is_some() {
+                diagnostics.warning("redundantWhatIf", &format!("{pointer}/whatIf"), t!("discovery.manifestValidation.redundantWhatIf").to_string());
+            }
+        } else if set.what_if_returns.is_some() && manifest.what_if.is_none() {
+            diagnostics.warning("whatIfReturnsWithoutWhatIf", &format!("{pointer}/set/whatIfReturns"), t!("discovery.manifestValidation.whatIfReturnsWithoutWhatIf").to_string());
+        }
+        if set.handles_exist == Some(true) {
+            match &manifest.schema {
+                Some(SchemaKind::Embedded(schema)) if !schema_has_property(schema, "_exist") => {
+                    diagnostics.error("handlesExistWithoutExist", &format!("{pointer}/set/handlesExist"), t!("discovery.manifestValidation.handlesExistWithoutExist").to_string());
+                },
+                _ => {},
+            }
+        }
+    }
+    if let Some(what_if) = &manifest.what_if {
+        executables.push(("whatIf", what_if.executable.as_str(), what_if.sha256.as_ref()));
+        check_set_args("whatIf", what_if.args.as_deref(), what_if.input.as_ref(), pointer, diagnostics);
+        environments.push(("whatIf", &what_if.environment));
+    }
+    if let Some(test) = &manifest.test {
+        executables.push(("test", test.executable.as_str(), test.sha256.as_ref()));
+        check_get_args("test", test.args.as_deref(), test.input.as_ref(), pointer, diagnostics);
+        environments.push(("test", &test.environment));
+    }
+    if let Some(delete) = &manifest.delete {
+        executables.push(("delete", delete.executable.as_str(), delete.sha256.as_ref()));
+        check_set_args("delete", delete.args.as_deref(), delete.input.as_ref(), pointer, diagnostics);
+        environments.push(("delete", &delete.environment));
+    }
+    if let Some(export) = &manifest.export {
+        executables.push(("export", export.executable.as_str(), export.sha256.as_ref()));
+        check_get_args("export", export.args.as_deref(), export.input.as_ref(), pointer, diagnostics);
+        environments.push(("export", &export.environment));
+    }
+    if let Some(resolve) = &manifest.resolve {
+        executables.push(("resolve", resolve.executable.as_str(), resolve.sha256.as_ref()));
+        check_get_args("resolve", resolve.args.as_deref(), resolve.input.as_ref(), pointer, diagnostics);
+        environments.push(("resolve", &resolve.environment));
+    }
+    if let Some(validate) = &manifest.validate {
+        executables.push(("validate", validate.executable.as_str(), validate.sha256.as_ref()));
+        check_get_args("validate", validate.args.as_deref(), validate.input.as_ref(), pointer, diagnostics);
+        environments.push(("validate", &validate.environment));
+    }
+    if let Some(server) = &manifest.server {
+        executables.push(("server", server.executable.as_str(), server.sha256.as_ref()));
+        // operations are sent to the running server, so only its own environment applies
+        for (operation, environment) in &environments {
+            if **environment != CommandEnvironment::default() {
+                diagnostics.error("environmentWithServer", &format!("{pointer}/{operation}"), t!("discovery.manifestValidation.environmentWithServer", operation = operation).to_string());
+            }
+        }
+        environments.push(("server", &server.environment));
+    }
+    if let Some(adapter) = &manifest.adapter
+        && let Some(list) = &adapter.list {
+        executables.push(("adapter/list", list.executable.as_str(), list.sha256.as_ref()));
+    }
+    match &manifest.schema {
+        Some(SchemaKind::Command(command)) => executables.push(("schema/command", command.executable.as_str(), command.sha256.as_ref())),
+        Some(SchemaKind::Embedded(schema)) => check_schema(schema, &format!("{pointer}/schema/embedded"), diagnostics),
+        None => {
+            if manifest.adapter.is_none() {
+                diagnostics.warning("missingSchema", &format!("{pointer}/schema"), t!("discovery.manifestValidation.missingSchema").to_string());
+            }
+        },
+    }
+    for (operation, executable, sha256) in executables {
+        check_executable(executable, sha256, &format!("{pointer}/{operation}"), directory, diagnostics);
+    }
+    for (operation, environment) in environments {
+        check_environment(environment, &format!("{pointer}/{operation}"), directory, diagnostics);
+    }
+
+    if manifest.get.is_none() && manifest.export.is_none() && manifest.adapter.is_none() {
+        diagnostics.warning("missingGet", &format!("{pointer}/get"), t!("discovery.manifestValidation.missingGet").to_string());
+    }
+    if manifest.kind == Some(Kind::Adapter) && manifest.adapter.is_none() {
+        diagnostics.error("adapterWithoutAdapter", &format!("{pointer}/kind"), t!("discovery.manifestValidation.adapterWithoutAdapter").to_string());
+    }
+    check_exit_codes(&manifest.exit_codes, pointer, diagnostics);
+}
+
+fn validate_adapted_resource(value: &Value, pointer: &str, directory: &Path, diagnostics: &mut Diagnostics) {
+    let manifest = match serde_json::from_value::<AdaptedDscResourceManifest>(value.clone()) {
+        Ok(manifest) => manifest,
+        Err(err) => {
+            diagnostics.error("invalidManifest", pointer, t!("discovery.manifestValidation.invalidAdaptedResourceManifest", error = err).to_string());
+            return;
+        }
+    };
+
+    if manifest.validate_schema_uri().is_err() {
+        diagnostics.warning("unrecognizedSchema", &format!("{pointer}/$schema"), t!("discovery.manifestValidation.unrecognizedSchema", schema = manifest.schema_version).to_string());
+    }
+    if manifest.version.is_date_version() {
+        diagnostics.warning("dateVersion", &format!("{pointer}/version"), t!("discovery.manifestValidation.dateVersion", version = manifest.version).to_string());
+    }
+    if manifest.capabilities.is_empty() {
+        diagnostics.warning("noCapabilities", &format!("{pointer}/capabilities"), t!("discovery.manifestValidation.noCapabilities").to_string());
+    }
+    if let AdaptedPathOrContent::Path(path) = &manifest.path_or_content
+        && !directory.join(path).exists() {
+        diagnostics.error("pathNotFound", &format!("{pointer}/path"), t!("discovery.manifestValidation.pathNotFound", path = path.to_string_lossy()).to_string());
+    }
+    check_schema(&Value::Object(manifest.schema), &format!("{pointer}/schema"), diagnostics);
+}
+
+fn validate_extension(value: &Value, pointer: &str, directory: &Path, diagnostics: &mut Diagnostics) {
+    let manifest = match serde_json::from_value::<ExtensionManifest>(value.clone()) {
+        Ok(manifest) => manifest,
+        Err(err) => {
+            diagnostics.error("invalidManifest", pointer, t!("discovery.manifestValidation.invalidExtensionManifest", error = err).to_string());
+            return;
+        }
+    };
+
+    if manifest.validate_schema_uri().is_err() {
+        diagnostics.warning("unrecognizedSchema", &format!("{pointer}/$schema"), t!("discovery.manifestValidation.unrecognizedSchema", schema = manifest.schema_version).to_string());
+    }
+
+    let mut executables = Vec::new();
+    if let Some(discover) = &manifest.discover {
+        executables.push(("discover", discover.executable.as_str(), discover.sha256.as_ref()));
+    }
+    if let Some(secret) = &manifest.secret {
+        executables.push(("secret", secret.executable.as_str(), secret.sha256.as_ref()));
+        let string_args: Vec<&str> = secret.args.as_deref().unwrap_or_default().iter()
+            .filter_map(|arg| if let SecretArgKind::String(s) = arg { Some(s.as_str()) } else { None })
+            .collect();
+        check_input_file("secret", &string_args, secret.input.as_ref(), pointer, diagnostics);
+    }
+    for (operation, import) in [("import", &manifest.import), ("importParameters", &manifest.import_parameters)] {
+        if let Some(import) = import {
+            executables.push((operation, import.executable.as_str(), import.sha256.as_ref()));
+            let string_args: Vec<&str> = import.args.as_deref().unwrap_or_default().iter()
+                .filter_map(|arg| if let ImportArgKind::String(s) = arg { Some(s.as_str()) } else { None })
+                .collect();
+            check_input_file(operation, &string_args, import.input.as_ref(), pointer, diagnostics);
+            if import.file_extensions.is_empty() {
+                diagnostics.warning("importWithoutFileExtensions", &format!("{pointer}/{operation}/fileExtensions"), t!("discovery.manifestValidation.importWithoutFileExtensions").to_string());
+            }
+        }
+    }
+    if executables.is_empty() {
+        diagnostics.warning("noCapabilities", pointer, t!("discovery.manifestValidation.noCapabilities").to_string());
+    }
+    for (operation, executable, sha256) in executables {
+        check_executable(executable, sha256, &format!("{pointer}/{operation}"), directory, diagnostics);
+    }
+    check_exit_codes(&manifest.exit_codes, pointer, diagnostics);
+}
+
+fn check_executable(executable: &str, sha256: Option<&ExecutableDigest>, pointer: &str, directory: &Path, diagnostics: &mut Diagnostics) {
+    if canonicalize_which(executable, Some(directory)).is_err() {
+        diagnostics.error("executableNotFound", &format!("{pointer}/executable"), t!("discovery.manifestValidation.executableNotFound", executable = executable).to_string());
+    }
+    let Some(sha256) = sha256 else {
+        return;
+    };
+    for digest in sha256.digests() {
+        if digest.len() != 64 || !digest.chars().all(|c| c.is_ascii_hexdigit()) {
+            diagnostics.error("invalidSha256", &format!("{pointer}/sha256"), t!("discovery.manifestValidation.invalidSha256", digest = digest).to_string());
+        }
+    }
+}
+
+fn check_environment(environment: &CommandEnvironment, pointer: &str, directory: &Path, diagnostics: &mut Diagnostics) {
+    if let Some(cwd) = &environment.cwd
+        && cwd.relative_to == WorkingDirectoryBase::Manifest
+        && !directory.join(&cwd.path).is_dir() {
+        diagnostics.error("cwdNotFound", &format!("{pointer}/cwd/path"), t!("discovery.manifestValidation.cwdNotFound", path = &cwd.path).to_string());
+    }
+    if environment.env_passthrough.as_ref().is_some_and(Vec::is_empty) {
+        diagnostics.warning("emptyEnvPassthrough", &format!("{pointer}/envPassthrough"), t!("discovery.manifestValidation.emptyEnvPassthrough").to_string());
+    }
+}
+
+fn check_get_args(operation: &str, args: Option<&[GetArgKind]>, input: Option<&InputKind>, pointer: &str, diagnostics: &mut Diagnostics) {
+    let json_args: Vec<usize> = args.unwrap_or_default().iter().enumerate()
+        .filter(|(_, arg)| matches!(arg, GetArgKind::Json { .. }))
+        .map(|(index, _)| index)
+        .collect();
+    check_json_input_args(operation, &json_args, input, pointer, diagnostics);
+
+    let string_args: Vec<&str> = args.unwrap_or_default().iter()
+        .filter_map(|arg| if let GetArgKind::String(s) = arg { Some(s.as_str()) } else { None })
+        .collect();
+    check_input_file(operation, &string_args, input, pointer, diagnostics);
+}
+
+fn check_set_args(operation: &str, args: Option<&[SetDeleteArgKind]>, input: Option<&InputKind>, pointer: &str, diagnostics: &mut Diagnostics) {
+    let args = args.unwrap_or_default();
+    let json_args: Vec<usize> = args.iter().enumerate()
+        .filter(|(_, arg)| matches!(arg, SetDeleteArgKind::Json { .. }))
+        .map(|(index, _)| index)
+        .collect();
+    check_json_input_args(operation, &json_args, input, pointer, diagnostics);
+
+    let string_args: Vec<&str> = args.iter()
+        .filter_map(|arg| if let SetDeleteArgKind::String(s) = arg { Some(s.as_str()) } else { None })
+        .collect();
+    check_input_file(operation, &string_args, input, pointer, diagnostics);
+
+    let what_if_args: Vec<usize> = args.iter().enumerate()
+        .filter(|(_, arg)| matches!(arg, SetDeleteArgKind::WhatIf { .. }))
+        .map(|(index, _)| index)
+        .collect();
+    if let Some(index) = what_if_args.get(1) {
+        diagnostics.error("duplicateWhatIfArg", &format!("{pointer}/{operation}/args/{index}"), t!("discovery.manifestValidation.duplicateWhatIfArg").to_string());
+    }
+}
+
+fn check_json_input_args(operation: &str, json_args: &[usize], input: Option<&InputKind>, pointer: &str, diagnostics: &mut Diagnostics) {
+    if let Some(index) = json_args.get(1) {
+        diagnostics.error("duplicateJsonInputArg", &format!("{pointer}/{operation}/args/{index}"), t!("discovery.manifestValidation.duplicateJsonInputArg").to_string());
+    }
+    if !json_args.is_empty() && input.is_some() {
+        diagnostics.warning("inputAndJsonInputArg", &format!("{pointer}/{operation}/input"), t!("discovery.manifestValidation.inputAndJsonInputArg").to_string());
+    }
+}
+
+fn check_input_file(operation: &str, string_args: &[&str], input: Option<&InputKind>, pointer: &str, diagnostics: &mut Diagnostics) {
+    if input == Some(&InputKind::File) && !string_args.iter().any(|arg| arg.contains(INPUT_FILE_PLACEHOLDER)) {
+        diagnostics.error("inputFileWithoutPlaceholder", &format!("{pointer}/{operation}/input"), t!("discovery.manifestValidation.inputFileWithoutPlaceholder", placeholder = INPUT_FILE_PLACEHOLDER).to_string());
+    }
+}
+
+fn check_schema(schema: &Value, pointer: &str, diagnostics: &mut Diagnostics) {
+    if let Err(err) = Validator::new(schema) {
+        diagnostics.error("invalidSchema", pointer, t!("discovery.manifestValidation.invalidSchema", error = err).to_string());
+    }
+}
+
+fn check_exit_codes(exit_codes: &ExitCodesMap, pointer: &str, diagnostics: &mut Diagnostics) {
+    if exit_codes.get_code(0).is_none() {
+        diagnostics.error("exitCodesMissingZero", &format!("{pointer}/exitCodes"), t!("discovery.manifestValidation.exitCodesMissingZero").to_string());
+    }
+}
+
+fn schema_has_property(schema: &Value, property: &str) -> bool {
+    schema.get("properties").and_then(Value::as_object).is_some_and(|properties| properties.contains_key(property))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use std::path::Path;
+
+    use super::{validate_manifest, DiagnosticSeverity, ManifestFileKind};
+
+    fn codes(manifest: &serde_json::Value, kind: Option<ManifestFileKind>) -> Vec<(String, DiagnosticSeverity, String)> {
+        validate_manifest(manifest, kind, Path::new("."))
+            .into_iter()
+            .map(|diagnostic| (diagnostic.code, diagnostic.severity, diagnostic.pointer))
+            .collect()
+    }
+
+    #[test]
+    fn duplicate_json_input_arg_is_error() {
+        let manifest = json!({
+            "$schema": "https://aka.ms/dsc/schemas/v3/bundled/resource/manifest.json",
+            "type": "Test/Duplicate",
+            "version": "0.1.0",
+            "get": {
+                "executable": "dsc",
+                "args": [{ "jsonInputArg": "--input" }, { "jsonInputArg": "--other" }]
+            },
+            "schema": { "embedded": { "type": "object" } }
+        });
+        let codes = codes(&manifest, Some(ManifestFileKind::Resource));
+        assert!(codes.contains(&("duplicateJsonInputArg".to_string(), DiagnosticSeverity::Error, "/get/args/1".to_string())));
+    }
+
+    #[test]
+    fn file_input_without_placeholder_is_error() {
+        let manifest = json!({
+            "$schema": "https://aka.ms/dsc/schemas/v3/bundled/resource/manifest.json",
+            "type": "Test/InputFile",
+            "version": "0.1.0",
+            "get": {
+                "executable": "dsc",
+                "args": ["--path", "{inputFile}"],
+                "input": "file"
+            },
+            "set": {
+                "executable": "dsc",
+                "args": ["--path"],
+                "input": "file"
+            },
+            "schema": { "embedded": { "type": "object" } }
+        });
+        let codes = codes(&manifest, Some(ManifestFileKind::Resource));
+        assert!(codes.contains(&("inputFileWithoutPlaceholder".to_string(), DiagnosticSeverity::Error, "/set/input".to_string())));
+        assert!(!codes.contains(&("inputFileWithoutPlaceholder".to_string(), DiagnosticSeverity::Error, "/get/input".to_string())));
+    }
+
+    #[test]
+    fn missing_cwd_is_error() {
+        let manifest = json!({
+            "$schema": "https://aka.ms/dsc/schemas/v3/bundled/resource/manifest.json",
+            "type": "Test/Cwd",
+            "version": "0.1.0",
+            "get": {
+                "executable": "dsc",
+                "cwd": { "path": "does_not_exist" },
+                "envPassthrough": []
+            },
+            "test": {
+                "executable": "dsc",
+                "cwd": { "path": "does_not_exist", "relativeTo": "configRoot" }
+            },
+            "schema": { "embedded": { "type": "object" } }
+        });
+        let codes = codes(&manifest, Some(ManifestFileKind::Resource));
+        assert!(codes.contains(&("cwdNotFound".to_string(), DiagnosticSeverity::Error, "/get/cwd/path".to_string())));
+        assert!(codes.contains(&("emptyEnvPassthrough".to_string(), DiagnosticSeverity::Warning, "/get/envPassthrough".to_string())));
+        assert!(!codes.contains(&("cwdNotFound".to_string(), DiagnosticSeverity::Error, "/test/cwd/path".to_string())));
+    }
+
+    #[test]
+    fn method_environment_with_server_is_error() {
+        let manifest = json!({
+            "$schema": "https://aka.ms/dsc/schemas/v3/bundled/resource/manifest.json",
+            "type": "Test/Server",
+            "version": "0.1.0",
+            "get": {
+                "executable": "dsc",
+                "env": { "NAME": "value" }
+            },
+            "test": { "executable": "dsc" },
+            "server": {
+                "executable": "dsc",
+                "protocol": "jsonrpc",
+                "cwd": { "path": "does_not_exist" }
+            },
+            "schema": { "embedded": { "type": "object" } }
+        });
+        let codes = codes(&manifest, Some(ManifestFileKind::Resource));
+        assert!(codes.contains(&("environmentWithServer".to_string(), DiagnosticSeverity::Error, "/get".to_string())));
+        assert!(!codes.contains(&("environmentWithServer".to_string(), DiagnosticSeverity::Error, "/test".to_string())));
+        assert!(codes.contains(&("cwdNotFound".to_string(), DiagnosticSeverity::Error, "/server/cwd/path".to_string())));
+    }
+
+    #[test]
+    fn semantic_errors_are_reported() {
+        let manifest = json!({
+            "$schema": "https://aka.ms/dsc/schemas/v3/bundled/resource/manifest.json",
+            "type": "Test/Semantic",
+            "version": "0.1.0",
+            "get": { "executable": "dsc" },
+            "set": {
+                "executable": "dsc",
+                "args": [{ "whatIfArg": "-w" }],
+                "handlesExist": true
+            },
+            "exitCodes": { "1": "Failure" },
+            "schema": { "embedded": { "type": "object", "properties": { "name": { "type": "string" } } } }
+        });
+        let codes = codes(&manifest, None);
+        assert!(codes.contains(&("whatIfArgWithoutReturn".to_string(), DiagnosticSeverity::Warning, "/set/args/0".to_string())));
+        assert!(codes.contains(&("handlesExistWithoutExist".to_string(), DiagnosticSeverity::Error, "/set/handlesExist".to_string())));
+        assert!(codes.contains(&("exitCodesMissingZero".to_string(), DiagnosticSeverity::Error, "/exitCodes".to_string())));
+    }
+
+    #[test]
+    fn manifest_list_reports_pointer_of_item() {
+        let manifest = json!({
+            "resources": [
+                {
+                    "$schema": "https://aka.ms/dsc/schemas/v3/bundled/resource/manifest.json",
+                    "type": "Test/Missing",
+                    "version": "0.1.0",
+                    "get": { "executable": "this-executable-does-not-exist" },
+                    "schema": { "embedded": { "type": "object" } }
+                },
+                { "type": "Test/Invalid" }
+            ]
+        });
+        let codes = codes(&manifest, None);
+        assert!(codes.contains(&("executableNotFound".to_string(), DiagnosticSeverity::Error, "/resources/0/get/executable".to_string())));
+        assert!(codes.contains(&("invalidManifest".to_string(), DiagnosticSeverity::Error, "/resources/1".to_string())));
+    }
+}
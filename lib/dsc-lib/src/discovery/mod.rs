@@ -3,6 +3,10 @@
 
 pub mod command_discovery;
 pub mod discovery_trait;
+pub mod manifest_cache;
+pub mod manifest_signature;
+pub mod manifest_validation;
+pub mod resource_bundle;
 
 use crate::configure::config_doc::ResourceDiscoveryMode;
 use crate::discovery::discovery_trait::{DiscoveryKind, ResourceDiscovery, DiscoveryFilter};
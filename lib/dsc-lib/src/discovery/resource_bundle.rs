@@ -0,0 +1,471 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use crate::discovery::command_discovery::{load_manifest, ImportedManifest};
+use crate::discovery::manifest_validation::{validate_manifest_file, DiagnosticSeverity, ManifestFileKind};
+use crate::dscerror::DscError;
+use crate::types::{FullyQualifiedTypeName, ResourceVersion, ResourceVersionReq};
+use crate::util::get_file_sha256;
+use flate2::read::GzDecoder;
+use rust_i18n::t;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{copy, create_dir_all, read, read_dir, read_to_string, remove_dir, remove_dir_all, remove_file, rename, write, File};
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+/// The name of the index of installed bundles in the install directory.
+const INDEX_FILE_NAME: &str = "installed.json";
+/// The file identifying a directory as an OCI image layout.
+const OCI_LAYOUT_FILE_NAME: &str = "oci-layout";
+/// The annotation holding the file name of a layer that isn't a tar archive.
+const OCI_TITLE_ANNOTATION: &str = "org.opencontainers.image.title";
+
+/// A bundle of resources installed into the managed install directory.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct InstalledBundle {
+    /// The directory the bundle is installed in, which is searched by discovery.
+    pub directory: PathBuf,
+    /// The path of the bundle the resources were installed from.
+    pub source: String,
+    /// The SHA-256 digest of the archive or, for an OCI image layout, of the image manifest.
+    pub sha256: String,
+    /// When the bundle was installed.
+    pub installed_at: String,
+    /// The resources provided by the bundle.
+    pub resources: Vec<InstalledResource>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+pub struct InstalledResource {
+    #[serde(rename = "type")]
+    pub type_name: FullyQualifiedTypeName,
+    pub version: ResourceVersion,
+}
+
+/// The index of the bundles installed into the install directory.
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct InstalledIndex {
+    bundles: Vec<InstalledBundle>,
+}
+
+impl InstalledIndex {
+    fn load(root: &Path) -> Result<Self, DscError> {
+        let file_path = root.join(INDEX_FILE_NAME);
+        if !file_path.exists() {
+            return Ok(InstalledIndex::default());
+        }
+        serde_json::from_slice::<InstalledIndex>(&read(&file_path)?).map_err(|err| {
+            DscError::ResourceBundle(t!("discovery.resourceBundle.invalidIndex", path = file_path.to_string_lossy(), error = err).to_string())
+        })
+    }
+
+    fn save(&self, root: &Path) -> Result<(), DscError> {
+        let file_path = root.join(INDEX_FILE_NAME);
+        let json = serde_json::to_vec_pretty(self)?;
+
+        // write to a temporary file and rename so concurrent invocations never read a partial index
+        let temp_path = file_path.with_extension(format!("{}.tmp", std::process::id()));
+        if let Err(err) = write(&temp_path, json).and_then(|()| rename(&temp_path, &file_path)) {
+            let _ = remove_file(&temp_path);
+            return Err(err.into());
+        }
+        debug!("{}", t!("discovery.resourceBundle.savedIndex", count = self.bundles.len(), path = file_path.to_string_lossy()));
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+struct OciIndex {
+    manifests: Vec<OciDescriptor>,
+}
+
+#[derive(Deserialize)]
+struct OciManifest {
+    layers: Vec<OciDescriptor>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OciDescriptor {
+    media_type: Option<String>,
+    digest: String,
+    #[serde(default)]
+    annotations: HashMap<String, String>,
+}
+
+/// Get the directories of the installed bundles for discovery to search.
+///
+/// An unreadable index is traced and ignored so it doesn't break discovery of other resources.
+#[must_use]
+pub fn get_installed_directories() -> Vec<PathBuf> {
+    match InstalledIndex::load(&get_install_path()) {
+        Ok(index) => index.bundles.into_iter().map(|bundle| bundle.directory).collect(),
+        Err(err) => {
+            warn!("{err}");
+            Vec::new()
+        }
+    }
+}
+
+/// Get the bundles installed into the install directory.
+///
+/// # Errors
+///
+/// This function will return an error if the index can't be read.
+pub fn get_installed_bundles() -> Result<Vec<InstalledBundle>, DscError> {
+    Ok(InstalledIndex::load(&get_install_path())?.bundles)
+}
+
+/// Install a resource bundle into a per-version directory of the install directory.
+///
+/// The bundle is a zip or tar archive, optionally gzip compressed, or an OCI image layout as a
+/// directory or tar archive. The manifests in the root of the bundle are validated before the
+/// bundle is installed, so a bundle with an invalid manifest is never made discoverable.
+///
+/// # Arguments
+///
+/// * `bundle` - The path to the bundle
+/// * `force` - Whether to replace bundles already providing the same resource versions
+///
+/// # Errors
+///
+/// This function will return an error if the bundle can't be extracted, contains an invalid
+/// manifest or no resources, or the same resource versions are already installed.
+pub fn install_bundle(bundle: &Path, force: bool) -> Result<InstalledBundle, DscError> {
+    let root = get_install_path();
+    create_dir_all(&root)?;
+    let staging = root.join(format!(".staging-{}", Uuid::new_v4()));
+    create_dir_all(&staging)?;
+    let result = stage_and_install(bundle, &root, &staging, force);
+    if let Err(err) = remove_dir_all(&staging) {
+        warn!("{}", t!("discovery.resourceBundle.failedRemoveStaging", path = staging.to_string_lossy(), error = err));
+    }
+    result
+}
+
+/// Uninstall the installed bundles providing a resource.
+///
+/// # Arguments
+///
+/// * `type_name` - The type of the resource
+/// * `version` - The versions to uninstall, all versions if not specified
+///
+/// # Errors
+///
+/// This function will return an error if no installed bundle provides the resource or a bundle
+/// directory can't be deleted.
+pub fn uninstall_resource(type_name: &FullyQualifiedTypeName, version: Option<&ResourceVersionReq>) -> Result<Vec<InstalledBundle>, DscError> {
+    let root = get_install_path();
+    let mut index = InstalledIndex::load(&root)?;
+    let (removed, kept): (Vec<InstalledBundle>, Vec<InstalledBundle>) = index.bundles.into_iter().partition(|bundle| {
+        bundle.resources.iter().any(|resource| resource.type_name == *type_name && version.is_none_or(|req| req.matches(&resource.version)))
+    });
+    if removed.is_empty() {
+        let version = version.map(ToString::to_string).unwrap_or_default();
+        return Err(DscError::ResourceBundle(t!("discovery.resourceBundle.notInstalled", resource = type_name, version = version).to_string()));
+    }
+
+    // save the index first so a failed deletion never leaves discovery pointing at a partial bundle
+    index.bundles = kept;
+    index.save(&root)?;
+    for bundle in &removed {
+        remove_bundle_directory(&root, &bundle.directory)?;
+    }
+    Ok(removed)
+}
+
+fn stage_and_install(bundle: &Path, root: &Path, staging: &Path, force: bool) -> Result<InstalledBundle, DscError> {
+    let content = staging.join("content");
+    let sha256 = extract_bundle(bundle, staging, &content)?;
+    let content = find_content_root(&content)?;
+    let resources = validate_bundle(&content)?;
+
+    // the bundle is installed in a directory named after its first resource so versions are side-by-side
+    let primary = &resources[0];
+    let mut directory = root.to_path_buf();
+    for segment in primary.type_name.split('/') {
+        directory.push(segment);
+    }
+    directory.push(primary.version.to_string());
+
+    let mut index = InstalledIndex::load(root)?;
+    let (conflicts, kept): (Vec<InstalledBundle>, Vec<InstalledBundle>) = index.bundles.into_iter().partition(|installed| {
+        installed.directory == directory || installed.resources.iter().any(|resource| resources.contains(resource))
+    });
+    if !conflicts.is_empty() && !force {
+        let installed = conflicts.iter().map(|installed| installed.directory.to_string_lossy().to_string()).collect::<Vec<_>>().join(", ");
+        return Err(DscError::ResourceBundle(t!("discovery.resourceBundle.alreadyInstalled", resource = primary.type_name, version = primary.version, directory = installed).to_string()));
+    }
+    for conflict in &conflicts {
+        info!("{}", t!("discovery.resourceBundle.replacing", directory = conflict.directory.to_string_lossy()));
+        remove_bundle_directory(root, &conflict.directory)?;
+    }
+    index.bundles = kept;
+
+    if let Some(parent) = directory.parent() {
+        create_dir_all(parent)?;
+    }
+    rename(&content, &directory)?;
+    let installed = InstalledBundle {
+        directory,
+        source: bundle.to_string_lossy().to_string(),
+        sha256,
+        installed_at: chrono::Local::now().to_rfc3339(),
+        resources,
+    };
+    info!("{}", t!("discovery.resourceBundle.installed", source = installed.source, directory = installed.directory.to_string_lossy()));
+    index.bundles.push(installed.clone());
+    index.save(root)?;
+    Ok(installed)
+}
+
+/// Extract the bundle into the content directory, returning the digest of the bundle.
+fn extract_bundle(bundle: &Path, staging: &Path, content: &Path) -> Result<String, DscError> {
+    if bundle.is_dir() {
+        if !bundle.join(OCI_LAYOUT_FILE_NAME).is_file() {
+            return Err(DscError::ResourceBundle(t!("discovery.resourceBundle.notOciLayout", path = bundle.to_string_lossy()).to_string()));
+        }
+        return extract_oci_layout(bundle, content);
+    }
+
+    let sha256 = get_file_sha256(bundle)?;
+    let archive = staging.join("archive");
+    create_dir_all(&archive)?;
+    extract_archive(bundle, &archive)?;
+    if archive.join(OCI_LAYOUT_FILE_NAME).is_file() {
+        return extract_oci_layout(&archive, content);
+    }
+    rename(&archive, content)?;
+    Ok(sha256)
+}
+
+fn extract_archive(path: &Path, destination: &Path) -> Result<(), DscError> {
+    let file_name = path.file_name().map(|name| name.to_string_lossy().to_lowercase()).unwrap_or_default();
+    debug!("{}", t!("discovery.resourceBundle.extracting", path = path.to_string_lossy()));
+    if file_name.ends_with(".zip") {
+        let mut archive = zip::ZipArchive::new(File::open(path)?).map_err(|err| DscError::ResourceBundle(err.to_string()))?;
+        // entries with paths outside of the destination are rejected by `extract`
+        archive.extract(destination).map_err(|err| DscError::ResourceBundle(err.to_string()))?;
+    } else if file_name.ends_with(".tar.gz") || file_name.ends_with(".tgz") {
+        unpack_tar(GzDecoder::new(File::open(path)?), destination)?;
+    } else if file_name.ends_with(".tar") {
+        unpack_tar(File::open(path)?, destination)?;
+    } else {
+        return Err(DscError::ResourceBundle(t!("discovery.resourceBundle.unsupportedBundle", path = path.to_string_lossy()).to_string()));
+    }
+    Ok(())
+}
+
+fn unpack_tar(reader: impl Read, destination: &Path) -> Result<(), DscError> {
+    // entries with paths outside of the destination are skipped by `unpack`
+    tar::Archive::new(reader).unpack(destination)?;
+    Ok(())
+}
+
+/// Extract the layers of the single image in an OCI image layout, returning the digest of the image manifest.
+fn extract_oci_layout(layout: &Path, content: &Path) -> Result<String, DscError> {
+    debug!("{}", t!("discovery.resourceBundle.extractingOciLayout", path = layout.to_string_lossy()));
+    let index: OciIndex = serde_json::from_str(&read_to_string(layout.join("index.json"))?)?;
+    let [descriptor] = index.manifests.as_slice() else {
+        return Err(DscError::ResourceBundle(t!("discovery.resourceBundle.ociManifestCount", count = index.manifests.len()).to_string()));
+    };
+    let manifest: OciManifest = serde_json::from_str(&read_to_string(get_blob_path(layout, &descriptor.digest)?)?)?;
+
+    create_dir_all(content)?;
+    for layer in &manifest.layers {
+        let blob = get_blob_path(layout, &layer.digest)?;
+        let media_type = layer.media_type.as_deref().unwrap_or_default();
+        if media_type.ends_with(".tar+gzip") {
+            unpack_tar(GzDecoder::new(File::open(&blob)?), content)?;
+        } else if media_type.ends_with(".tar") {
+            unpack_tar(File::open(&blob)?, content)?;
+        } else if let Some(title) = layer.annotations.get(OCI_TITLE_ANNOTATION) {
+            // a single file pushed as an artifact layer
+            let mut components = Path::new(title).components();
+            let (Some(Component::Normal(file_name)), None) = (components.next(), components.next()) else {
+                return Err(DscError::ResourceBundle(t!("discovery.resourceBundle.invalidLayerTitle", title = title).to_string()));
+            };
+            copy(&blob, content.join(file_name))?;
+        } else {
+            return Err(DscError::ResourceBundle(t!("discovery.resourceBundle.unsupportedLayer", digest = layer.digest, media_type = media_type).to_string()));
+        }
+    }
+    Ok(descriptor.digest.trim_start_matches("sha256:").to_string())
+}
+
+/// Get the path of a blob in an OCI image layout after verifying its digest.
+fn get_blob_path(layout: &Path, digest: &str) -> Result<PathBuf, DscError> {
+    let Some(hex) = digest.strip_prefix("sha256:").filter(|hex| hex.len() == 64 && hex.chars().all(|c| c.is_ascii_hexdigit())) else {
+        return Err(DscError::ResourceBundle(t!("discovery.resourceBundle.unsupportedDigest", digest = digest).to_string()));
+    };
+    let path = layout.join("blobs").join("sha256").join(hex);
+    let actual = get_file_sha256(&path)?;
+    if !actual.eq_ignore_ascii_case(hex) {
+        return Err(DscError::ResourceBundle(t!("discovery.resourceBundle.blobDigestMismatch", digest = digest, actual = actual).to_string()));
+    }
+    Ok(path)
+}
+
+/// Archives often wrap the content in a single directory, in which case that directory is the content.
+fn find_content_root(content: &Path) -> Result<PathBuf, DscError> {
+    let entries: Vec<PathBuf> = read_dir(content)?.filter_map(Result::ok).map(|entry| entry.path()).collect();
+    if let [entry] = entries.as_slice() && entry.is_dir() {
+        return Ok(entry.clone());
+    }
+    Ok(content.to_path_buf())
+}
+
+/// Validate the manifests in the root of the bundle, returning the resources they provide.
+fn validate_bundle(content: &Path) -> Result<Vec<InstalledResource>, DscError> {
+    let mut resources = Vec::new();
+    for entry in read_dir(content)?.filter_map(Result::ok) {
+        let path = entry.path();
+        if !path.is_file() || ManifestFileKind::from_path(&path).is_none() {
+            continue;
+        }
+
+        let result = validate_manifest_file(&path)?;
+        if !result.valid {
+            let errors = result.diagnostics.iter()
+                .filter(|diagnostic| diagnostic.severity == DiagnosticSeverity::Error)
+                .map(|diagnostic| format!("{} ({})", diagnostic.message, diagnostic.pointer))
+                .collect::<Vec<_>>()
+                .join("; ");
+            let file_name = entry.file_name();
+            return Err(DscError::ResourceBundle(t!("discovery.resourceBundle.invalidManifest", path = file_name.to_string_lossy(), errors = errors).to_string()));
+        }
+        for manifest in load_manifest(&path)? {
+            if let ImportedManifest::Resource(resource) = manifest {
+                resources.push(InstalledResource { type_name: resource.type_name, version: resource.version });
+            }
+        }
+    }
+
+    if resources.is_empty() {
+        return Err(DscError::ResourceBundle(t!("discovery.resourceBundle.noResources").to_string()));
+    }
+    resources.sort_by_key(|resource| resource.type_name.to_string().to_lowercase());
+    Ok(resources)
+}
+
+/// Delete the directory of a bundle and the parent directories it leaves empty.
+///
+/// The directory comes from the index, so it's only deleted if it's under the install directory.
+fn remove_bundle_directory(root: &Path, directory: &Path) -> Result<(), DscError> {
+    if !directory.exists() {
+        return Ok(());
+    }
+    let root = root.canonicalize()?;
+    let directory = directory.canonicalize()?;
+    if directory == root || !directory.starts_with(&root) {
+        return Err(DscError::ResourceBundle(t!("discovery.resourceBundle.directoryOutsideRoot", directory = directory.to_string_lossy(), root = root.to_string_lossy()).to_string()));
+    }
+    remove_dir_all(&directory)?;
+    let mut parent = directory.parent();
+    while let Some(path) = parent {
+        if path == root || !path.starts_with(&root) || remove_dir(path).is_err() {
+            break;
+        }
+        parent = path.parent();
+    }
+    Ok(())
+}
+
+/// Get the directory resource bundles are installed in, which can be overridden with the
+/// `DSC_RESOURCE_INSTALL_PATH` environment variable.
+#[must_use]
+pub fn get_install_path() -> PathBuf {
+    if let Some(path) = std::env::var_os("DSC_RESOURCE_INSTALL_PATH") {
+        return PathBuf::from(path);
+    }
+    get_default_install_path()
+}
+
+#[cfg(target_os = "windows")]
+fn get_default_install_path() -> PathBuf
+{
+    // $env:LocalAppData+"dsc\resources"
+    let Ok(local_app_data_path) = std::env::var("LocalAppData") else { return PathBuf::new(); };
+    Path::new(&local_app_data_path).join("dsc").join("resources")
+}
+
+#[cfg(not(target_os = "windows"))]
+fn get_default_install_path() -> PathBuf
+{
+    // $env:HOME+".dsc/resources"
+    let Ok(home_path) = std::env::var("HOME") else { return PathBuf::new(); };
+    Path::new(&home_path).join(".dsc").join("resources")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn content_in_single_directory_is_unwrapped() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+        fs::create_dir_all(dir.join("bundle")).unwrap();
+        assert_eq!(find_content_root(dir).unwrap(), dir.join("bundle"));
+
+        fs::write(dir.join("other.txt"), "").unwrap();
+        assert_eq!(find_content_root(dir).unwrap(), dir);
+    }
+
+    #[test]
+    fn blob_digest_is_verified() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+        let blobs = dir.join("blobs").join("sha256");
+        fs::create_dir_all(&blobs).unwrap();
+        // SHA-256 of "hello"
+        let hex = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+        fs::write(blobs.join(hex), "hello").unwrap();
+        assert_eq!(get_blob_path(dir, &format!("sha256:{hex}")).unwrap(), blobs.join(hex));
+
+        fs::write(blobs.join(hex), "tampered").unwrap();
+        assert!(get_blob_path(dir, &format!("sha256:{hex}")).is_err());
+        assert!(get_blob_path(dir, "sha256:../../index.json").is_err());
+        assert!(get_blob_path(dir, &format!("sha512:{hex}")).is_err());
+    }
+
+    #[test]
+    fn bundle_without_resources_is_invalid() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path();
+        fs::write(dir.join("readme.txt"), "").unwrap();
+        assert!(validate_bundle(dir).is_err());
+    }
+
+    #[test]
+    fn empty_parent_directories_are_removed() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        let directory = root.join("Test").join("Echo").join("1.0.0");
+        fs::create_dir_all(&directory).unwrap();
+        fs::create_dir_all(root.join("Test").join("Other")).unwrap();
+        remove_bundle_directory(&root, &directory).unwrap();
+        assert!(!root.join("Test").join("Echo").exists());
+        assert!(root.join("Test").join("Other").exists());
+    }
+
+    #[test]
+    fn directory_outside_root_is_not_removed() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path().join("root");
+        let outside = temp_dir.path().join("outside");
+        fs::create_dir_all(&root).unwrap();
+        fs::create_dir_all(&outside).unwrap();
+        assert!(remove_bundle_directory(&root, &outside).is_err());
+        assert!(remove_bundle_directory(&root, &root.join("..").join("outside")).is_err());
+        assert!(remove_bundle_directory(&root, &root).is_err());
+        assert!(outside.exists());
+        assert!(root.exists());
+    }
+}
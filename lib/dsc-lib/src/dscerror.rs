@@ -26,12 +26,21 @@ pub enum DscError {
     #[error("{t} '{0}' [{t2} {1}] {t3}: {2}", t = t!("dscerror.commandResource"), t2 = t!("dscerror.exitCode"), t3 = t!("dscerror.manifestDescription"))]
     CommandExitFromManifest(String, i32, String),
 
+    #[error("{t} '{0}' [{t2} {1}] {2}", t = t!("dscerror.commandResource"), t2 = t!("dscerror.exitCode"))]
+    CommandResourceError(String, i32, Box<crate::dscresources::invoke_result::ResourceError>),
+
     #[error("{0}")]
     CommandNotFound(String),
 
     #[error("{t} {0} {t2} '{1}'", t = t!("dscerror.commandOperation"), t2 = t!("dscerror.forExecutable"))]
     CommandOperation(String, String),
 
+    #[error("{t} '{0}', {t2} '{1}'", t = t!("dscerror.executableHashMismatch"), t2 = t!("dscerror.actualDigest"))]
+    ExecutableHashMismatch(String, String),
+
+    #[error("{t} '{0}'", t = t!("dscerror.executableNotPinned"))]
+    ExecutableNotPinned(String),
+
     #[error("{0}")]
     Extension(String),
 
@@ -80,6 +89,9 @@ pub enum DscError {
     #[error("JSON: {0}")]
     Json(#[from] serde_json::Error),
 
+    #[error("{t} '{0}': {1}", t = t!("dscerror.lockMismatch"))]
+    LockMismatch(String, String),
+
     #[error("{t}: {0}", t = t!("dscerror.language"))]
     Language(#[from] LanguageError),
 
@@ -128,6 +140,9 @@ pub enum DscError {
     #[error("{t}: {0}", t = t!("dscerror.registryKey"))]
     RegistryKey(#[from] registry::key::Error),
 
+    #[error("{t}: {0}", t = t!("dscerror.resourceBundle"))]
+    ResourceBundle(String),
+
     #[error("{t}: {0}", t = t!("dscerror.resourceMissingDirectory"))]
     ResourceMissingDirectory(String),
 
@@ -164,6 +179,12 @@ pub enum DscError {
     #[error(transparent)]
     TypeNameFilter(#[from] crate::types::TypeNameFilterError),
 
+    #[error("{t} '{1}' {t2} '{0}': {2}", t = t!("dscerror.policyDenied"), t2 = t!("dscerror.policyDeniedResource"))]
+    PolicyDenied(String, String, String),
+
+    #[error("{t} '{0}': {1}", t = t!("dscerror.untrustedResource"))]
+    UntrustedResource(String, String),
+
     #[error("{t}: {0}", t = t!("dscerror.utf16Conversion"))]
     Utf16Conversion(#[from] std::string::FromUtf16Error),
 
@@ -3,30 +3,36 @@
 
 use clap::ValueEnum;
 use dsc_lib_security_context::{SecurityContext, get_security_context};
-use jsonschema::Validator;
 use rust_i18n::t;
 use serde::Deserialize;
 use serde_json::{Map, Value};
-use std::{collections::HashMap, env, path::Path, process::Stdio};
-use crate::{configure::{config_doc::{ExecutionKind, SecurityContextKind}, config_result::{ResourceGetResult, ResourceTestResult}, schema_cache::{get_resource_schema, RESOURCE_SCHEMAS}}, dscresources::resource_manifest::{ExportSchemaKind, ExportSchemaOrFiltering, SchemaArgKind}, types::ExitCodesMap, util::canonicalize_which};
+use std::{collections::HashMap, env, io::Write, path::{Path, PathBuf}, process::Stdio};
+use tempfile::NamedTempFile;
+use crate::{configure::{config_doc::{ExecutionKind, SecurityContextKind}, config_result::{ResourceGetResult, ResourceTestResult}, context::Context, function_library::DSC_CONFIG_ROOT, schema_cache::{get_resource_schema, RESOURCE_SCHEMAS}}, parser::Statement, dscresources::resource_manifest::{ExecutableDigest, ExportSchemaKind, ExportSchemaOrFiltering, SchemaArgKind}, types::ExitCodesMap, util::{canonicalize_which, verify_executable_digest}};
 use crate::dscerror::DscError;
 use crate::locked_insert;
+use crate::schemas::keywords::DscValidationOptionsExtensions;
+use crate::progress::{report_resource_progress, ResourceProgress};
 use super::{
-    dscresource::{get_diff, get_diff_with_schema, redact, DscResource},
+    dscresource::{get_diff, get_diff_with_reasons, normalize_with_schema, redact, redact_json_string, DscResource},
     invoke_result::{
         DeleteResult, DeleteResultKind, ExportResult,
         GetResult, ResolveResult, SetResult, TestResult, ValidateResult,
-        ResourceGetResponse, ResourceSetResponse, ResourceTestResponse, get_in_desired_state
+        Reason, ResourceErrorData, ResourceGetResponse, ResourceSetResponse, ResourceTestResponse, get_in_desired_state
     },
     resource_manifest::{
-        GetArgKind, SetDeleteArgKind, InputKind, Kind, ReturnKind, SchemaKind
-    }
+        CommandEnvironment, GetArgKind, SetDeleteArgKind, InputKind, Kind, ReturnKind, SchemaKind, WorkingDirectoryBase, INPUT_FILE_PLACEHOLDER
+    },
+    resource_server::invoke_server,
 };
 use tracing::{error, warn, info, debug, trace};
-use tokio::{io::{AsyncBufReadExt, AsyncWriteExt, BufReader}, process::Command};
+use tokio::{io::{AsyncBufReadExt, AsyncWriteExt, BufReader}, process::Command, sync::mpsc};
 
 pub const EXIT_PROCESS_TERMINATED: i32 = 0x102;
 
+/// Called with each line of stdout written by a command.
+pub type StdoutLineHandler<'a> = &'a mut dyn FnMut(&str) -> Result<(), DscError>;
+
 /// Invoke the get operation on a resource
 ///
 /// # Arguments
@@ -42,7 +48,7 @@ pub fn invoke_get(resource: &DscResource, filter: &str, target_resource: Option<
     let Some(manifest) = &resource.manifest else {
         return Err(DscError::MissingManifest(resource.type_name.to_string()));
     };
-    let mut command_input = CommandInput { env: None, stdin: None };
+    let mut command_input = CommandInput::default();
     let Some(get) = &manifest.get else {
         return Err(DscError::NotImplemented("get".to_string()));
     };
@@ -56,9 +62,10 @@ pub fn invoke_get(resource: &DscResource, filter: &str, target_resource: Option<
         verify_json_from_manifest(resource, filter, target_resource)?;
         command_input = get_command_input(get.input.as_ref(), filter)?;
     }
+    let command_input = command_input.with_environment(&get.environment, &resource.directory)?;
 
     info!("{}", t!("dscresources.commandResource.invokeGetUsing", resource = &resource.type_name, executable = &get.executable));
-    let (_exit_code, stdout, stderr) = invoke_command(&get.executable, args, command_input.stdin.as_deref(), Some(&resource.directory), command_input.env, manifest.exit_codes.as_ref())?;
+    let (_exit_code, stdout, stderr) = invoke_operation(resource, command_resource, "get", &get.executable, get.sha256.as_ref(), args, filter, command_input, &ExecutionKind::Actual)?;
     if resource.kind == Kind::Resource {
         debug!("{}", t!("dscresources.commandResource.verifyOutputUsing", resource = &resource.type_name, executable = &get.executable));
         verify_json_from_manifest(resource, &stdout, target_resource)?;
@@ -138,12 +145,14 @@ pub fn invoke_set(resource: &DscResource, desired: &str, skip_test: bool, execut
         return Err(DscError::NotImplemented("set".to_string()));
     };
     validate_security_context(&set.require_security_context, &command_resource.type_name, "set")?;
+    let desired = normalize_desired_state(resource, desired, target_resource)?;
+    let desired = desired.as_str();
     verify_json_from_manifest(resource, desired, target_resource)?;
 
     // if resource doesn't implement a pre-test, we execute test first to see if a set is needed
     if !skip_test && set.pre_test != Some(true) {
         info!("{}", t!("dscresources.commandResource.noPretest", resource = &command_resource.type_name));
-        let test_result = invoke_test(resource, desired, target_resource)?;
+        let test_result = test_normalized(resource, desired, target_resource)?;
         if is_synthetic_what_if {
             return Ok(test_result.into());
         }
@@ -184,10 +193,10 @@ pub fn invoke_set(resource: &DscResource, desired: &str, skip_test: bool, execut
     };
     validate_security_context(&get.require_security_context, &command_resource.type_name, "get")?;
     let args = process_get_args(get.args.as_ref(), desired, command_resource);
-    let command_input = get_command_input(get.input.as_ref(), desired)?;
+    let command_input = get_command_input(get.input.as_ref(), desired)?.with_environment(&get.environment, &resource.directory)?;
 
     info!("{}", t!("dscresources.commandResource.setGetCurrent", resource = &command_resource.type_name, executable = &get.executable));
-    let (exit_code, stdout, stderr) = invoke_command(&get.executable, args, command_input.stdin.as_deref(), Some(&resource.directory), command_input.env, manifest.exit_codes.as_ref())?;
+    let (exit_code, stdout, stderr) = invoke_operation(resource, command_resource, "get", &get.executable, get.sha256.as_ref(), args, desired, command_input, &ExecutionKind::Actual)?;
 
     if resource.kind == Kind::Resource {
         debug!("{}", t!("dscresources.commandResource.setVerifyGet", resource = &resource.type_name, executable = &get.executable));
@@ -213,22 +222,9 @@ pub fn invoke_set(resource: &DscResource, desired: &str, skip_test: bool, execut
         pre_state_value
     };
 
-    let mut env: Option<HashMap<String, String>> = None;
-    let mut input_desired: Option<&str> = None;
     let (args, _) = process_set_delete_args(set.args.as_ref(), desired, command_resource, execution_type);
-    match &set.input {
-        Some(InputKind::Env) => {
-            env = Some(json_to_hashmap(desired)?);
-        },
-        Some(InputKind::Stdin) => {
-            input_desired = Some(desired);
-        },
-        None => {
-            // leave input as none
-        },
-    }
-
-    let (exit_code, stdout, stderr) = invoke_command(&set.executable, args, input_desired, Some(&resource.directory), env, manifest.exit_codes.as_ref())?;
+    let command_input = get_command_input(set.input.as_ref(), desired)?.with_environment(&set.environment, &resource.directory)?;
+    let (exit_code, stdout, stderr) = invoke_operation(resource, command_resource, "set", &set.executable, set.sha256.as_ref(), args, desired, command_input, execution_type)?;
 
     let return_kind = if execution_type == &ExecutionKind::WhatIf {
         set.what_if_returns.as_ref().or(set.returns.as_ref())
@@ -323,6 +319,12 @@ pub fn invoke_set(resource: &DscResource, desired: &str, skip_test: bool, execut
 /// Error is returned if the underlying command returns a non-zero exit code.
 pub fn invoke_test(resource: &DscResource, expected: &str, target_resource: Option<&DscResource>) -> Result<TestResult, DscError> {
     debug!("{}", t!("dscresources.commandResource.invokeTest", resource = &resource.type_name));
+    let expected = normalize_desired_state(resource, expected, target_resource)?;
+    test_normalized(resource, &expected, target_resource)
+}
+
+/// Invoke the test operation for a desired state that's already normalized.
+fn test_normalized(resource: &DscResource, expected: &str, target_resource: Option<&DscResource>) -> Result<TestResult, DscError> {
     let Some(manifest) = &resource.manifest else {
         return Err(DscError::MissingManifest(resource.type_name.to_string()));
     };
@@ -339,10 +341,10 @@ pub fn invoke_test(resource: &DscResource, expected: &str, target_resource: Opti
     };
     validate_security_context(&test.require_security_context, &command_resource.type_name, "test")?;
     let args = process_get_args(test.args.as_ref(), expected, command_resource);
-    let command_input = get_command_input(test.input.as_ref(), expected)?;
+    let command_input = get_command_input(test.input.as_ref(), expected)?.with_environment(&test.environment, &resource.directory)?;
 
     info!("{}", t!("dscresources.commandResource.invokeTestUsing", resource = &command_resource.type_name, executable = &test.executable));
-    let (exit_code, stdout, stderr) = invoke_command(&test.executable, args, command_input.stdin.as_deref(), Some(&resource.directory), command_input.env, manifest.exit_codes.as_ref())?;
+    let (exit_code, stdout, stderr) = invoke_operation(resource, command_resource, "test", &test.executable, test.sha256.as_ref(), args, expected, command_input, &ExecutionKind::Actual)?;
 
     if command_resource.kind == Kind::Importer {
         debug!("{}", t!("dscresources.commandResource.testGroupTestResponse"));
@@ -365,13 +367,15 @@ pub fn invoke_test(resource: &DscResource, expected: &str, target_resource: Opti
                 }
             };
             let in_desired_state = get_desired_state(&actual_value)?;
-            let diff_properties = get_diff(&expected_value, &actual_value);
+            let (diff_properties, diff_reasons) = get_diff_with_reasons(&expected_value, &actual_value, None);
+            let reasons = get_reasons_or_diff(&actual_value, in_desired_state, diff_reasons)?;
             expected_value = redact(&expected_value);
             Ok(TestResult::Resource(ResourceTestResponse {
                 desired_state: expected_value,
                 actual_state: actual_value,
                 in_desired_state: in_desired_state.unwrap_or(diff_properties.is_empty()),
                 diff_properties,
+                reasons,
             }))
         },
         Some(ReturnKind::StateAndDiff) => {
@@ -393,11 +397,13 @@ pub fn invoke_test(resource: &DscResource, expected: &str, target_resource: Opti
             let diff_properties: Vec<String> = serde_json::from_str(diff_properties)?;
             expected_value = redact(&expected_value);
             let in_desired_state = get_desired_state(&actual_value)?;
+            let reasons = get_reasons(&actual_value)?.unwrap_or_default();
             Ok(TestResult::Resource(ResourceTestResponse {
                 desired_state: expected_value,
                 actual_state: actual_value,
                 in_desired_state: in_desired_state.unwrap_or(diff_properties.is_empty()),
                 diff_properties,
+                reasons,
             }))
         },
         None => {
@@ -415,13 +421,15 @@ pub fn invoke_test(resource: &DscResource, expected: &str, target_resource: Opti
                     response.actual_state
                 }
             };
-            let diff_properties = get_diff( &expected_value, &actual_state);
+            let (diff_properties, diff_reasons) = get_diff_with_reasons(&expected_value, &actual_state, None);
+            let reasons = get_reasons(&actual_state)?.unwrap_or(diff_reasons);
             expected_value = redact(&expected_value);
             Ok(TestResult::Resource(ResourceTestResponse {
                 desired_state: expected_value,
                 actual_state,
                 in_desired_state: diff_properties.is_empty(),
                 diff_properties,
+                reasons,
             }))
         },
     }
@@ -440,6 +448,31 @@ fn get_desired_state(actual: &Value) -> Result<Option<bool>, DscError> {
     Ok(in_desired_state)
 }
 
+fn get_reasons(actual: &Value) -> Result<Option<Vec<Reason>>, DscError> {
+    // if actual state contains _reasons, the resource explains why it isn't in the desired state
+    let Some(reasons) = actual.get("_reasons") else {
+        return Ok(None);
+    };
+    match Vec::<Reason>::deserialize(reasons) {
+        Ok(reasons) => Ok(Some(reasons)),
+        Err(err) => Err(DscError::Operation(t!("dscresources.commandResource.reasonsInvalid", err = err).to_string())),
+    }
+}
+
+/// Get the reasons the resource returned, falling back to the reasons generated from the diff.
+///
+/// The generated reasons are dropped when the resource reports it's in the desired state, since
+/// they would contradict it.
+fn get_reasons_or_diff(actual: &Value, in_desired_state: Option<bool>, diff_reasons: Vec<Reason>) -> Result<Vec<Reason>, DscError> {
+    if let Some(reasons) = get_reasons(actual)? {
+        return Ok(reasons);
+    }
+    if in_desired_state == Some(true) {
+        return Ok(Vec::new());
+    }
+    Ok(diff_reasons)
+}
+
 fn invoke_synthetic_test(resource: &DscResource, expected: &str, target_resource: Option<&DscResource>) -> Result<TestResult, DscError> {
     let get_result = invoke_get(resource, expected, target_resource)?;
     let actual_state = match get_result {
@@ -455,8 +488,21 @@ fn invoke_synthetic_test(resource: &DscResource, expected: &str, target_resource
         }
     };
     let expected_value: Value = serde_json::from_str(expected)?;
+    let schema = get_cached_schema(resource, target_resource);
+    let (diff_properties, reasons) = get_diff_with_reasons(&expected_value, &actual_state, schema.as_ref());
+    Ok(TestResult::Resource(ResourceTestResponse {
+        desired_state: expected_value,
+        actual_state,
+        in_desired_state: diff_properties.is_empty(),
+        diff_properties,
+        reasons,
+    }))
+}
+
+/// Get the schema of a resource from the schema cache, retrieving and caching it on a miss.
+fn get_cached_schema(resource: &DscResource, target_resource: Option<&DscResource>) -> Option<Value> {
     let cached_resource = target_resource.unwrap_or(resource);
-    let schema: Option<Value> = get_resource_schema(&cached_resource.type_name, &cached_resource.version)
+    get_resource_schema(&cached_resource.type_name, &cached_resource.version)
         .or_else(|| {
             // Cache miss: parse and use the schema returned by get_schema. This covers cases
             // where get_schema returns early (e.g. target_resource.schema) without caching.
@@ -470,14 +516,76 @@ fn invoke_synthetic_test(resource: &DscResource, expected: &str, target_resource
                 schema_value.clone()
             );
             Some(schema_value)
-        });
-    let diff_properties = get_diff_with_schema(&expected_value, &actual_state, schema.as_ref());
-    Ok(TestResult::Resource(ResourceTestResponse {
-        desired_state: expected_value,
-        actual_state,
-        in_desired_state: diff_properties.is_empty(),
-        diff_properties,
-    }))
+        })
+}
+
+/// Apply the schema defaults and enum casing to the desired state of a resource that opts in
+/// with the `normalize` manifest field, then validate the result against the resource schema.
+///
+/// # Arguments
+///
+/// * `resource` - The resource manifest
+/// * `desired` - The desired state of the resource in JSON
+/// * `target_resource` - The adapted resource, if any
+///
+/// # Errors
+///
+/// Error returned if the normalized desired state isn't valid against the resource schema.
+fn normalize_desired_state(resource: &DscResource, desired: &str, target_resource: Option<&DscResource>) -> Result<String, DscError> {
+    let mut normalized = normalize_desired_states(resource, &[desired], target_resource)?;
+    Ok(normalized.swap_remove(0))
+}
+
+/// Apply the schema defaults and enum casing to the desired state of multiple instances of a
+/// resource, compiling the resource schema once for validating every instance.
+///
+/// The `normalize` manifest field is read from the resource whose schema is used, which is the
+/// adapted resource when there is one.
+///
+/// # Arguments
+///
+/// * `resource` - The resource manifest
+/// * `desired` - The desired state of each instance in JSON
+/// * `target_resource` - The adapted resource, if any
+///
+/// # Errors
+///
+/// Error returned if the normalized desired state of any instance isn't valid against the
+/// resource schema.
+fn normalize_desired_states<S: AsRef<str>>(resource: &DscResource, desired: &[S], target_resource: Option<&DscResource>) -> Result<Vec<String>, DscError> {
+    let command_resource = target_resource.unwrap_or(resource);
+    let unchanged = || desired.iter().map(|instance| instance.as_ref().to_string()).collect();
+    if command_resource.manifest.as_ref().and_then(|manifest| manifest.normalize) != Some(true) {
+        return Ok(unchanged());
+    }
+    let Some(schema) = get_cached_schema(resource, target_resource) else {
+        debug!("{}", t!("dscresources.commandResource.normalizeNoSchema", resource = &command_resource.type_name));
+        return Ok(unchanged());
+    };
+    let validator = match jsonschema::options().with_dsc_keywords().build(&schema) {
+        Ok(validator) => validator,
+        Err(err) => {
+            return Err(DscError::Schema(err.to_string()));
+        },
+    };
+
+    let mut results = Vec::with_capacity(desired.len());
+    for instance in desired {
+        let normalized = normalize_with_schema(&serde_json::from_str(instance.as_ref())?, &schema);
+        let errors: Vec<String> = validator.iter_errors(&normalized).map(|err| {
+            let pointer = err.instance_path().to_string();
+            let pointer = if pointer.is_empty() { "/".to_string() } else { pointer };
+            t!("dscresources.commandResource.normalizeSchemaViolation", pointer = pointer, message = err).to_string()
+        }).collect();
+        if !errors.is_empty() {
+            return Err(DscError::Validation(t!("dscresources.commandResource.normalizeInvalid", resource = &command_resource.type_name, errors = errors.join("; ")).to_string()));
+        }
+
+        let normalized = serde_json::to_string(&normalized)?;
+        trace!("{}", t!("dscresources.commandResource.normalizedDesiredState", resource = &command_resource.type_name, json = redact_json_string(&normalized)));
+        results.push(normalized);
+    }
+    Ok(results)
 }
 
 /// Invoke the delete operation against a command resource.
@@ -513,10 +621,10 @@ pub fn invoke_delete(resource: &DscResource, filter: &str, target_resource: Opti
         let test_result = invoke_test(resource, filter, target_resource)?;
         return Ok(DeleteResultKind::SyntheticWhatIf(test_result));
     }
-    let command_input = get_command_input(delete.input.as_ref(), filter)?;
+    let command_input = get_command_input(delete.input.as_ref(), filter)?.with_environment(&delete.environment, &resource.directory)?;
 
     info!("{}", t!("dscresources.commandResource.invokeDeleteUsing", resource = &command_resource.type_name, executable = &delete.executable));
-    let (_exit_code, stdout, _stderr) = invoke_command(&delete.executable, args, command_input.stdin.as_deref(), Some(&resource.directory), command_input.env, manifest.exit_codes.as_ref())?;
+    let (_exit_code, stdout, _stderr) = invoke_operation(resource, command_resource, "delete", &delete.executable, delete.sha256.as_ref(), args, filter, command_input, execution_type)?;
     let result = if execution_type == &ExecutionKind::WhatIf {
         let delete_result: DeleteResult = serde_json::from_str(&stdout)?;
         DeleteResultKind::ResourceWhatIf(delete_result)
@@ -556,10 +664,10 @@ pub fn invoke_validate(resource: &DscResource, config: &str, target_resource: Op
         None => resource
     };
     let args = process_get_args(validate.args.as_ref(), config, target_resource);
-    let command_input = get_command_input(validate.input.as_ref(), config)?;
+    let command_input = get_command_input(validate.input.as_ref(), config)?.with_environment(&validate.environment, &resource.directory)?;
 
     info!("{}", t!("dscresources.commandResource.invokeValidateUsing", resource = &resource.type_name, executable = &validate.executable));
-    let (_exit_code, stdout, _stderr) = invoke_command(&validate.executable, args, command_input.stdin.as_deref(), Some(&resource.directory), command_input.env, manifest.exit_codes.as_ref())?;
+    let (_exit_code, stdout, _stderr) = invoke_method_command(&validate.executable, args, command_input, &resource.directory, manifest.exit_codes.as_ref(), validate.sha256.as_ref(), None)?;
     let result: ValidateResult = serde_json::from_str(&stdout)?;
     Ok(result)
 }
@@ -600,7 +708,7 @@ pub fn get_schema(resource: &DscResource, target_resource: Option<&DscResource>)
     let (schema, schema_value) = match schema_kind {
         SchemaKind::Command(command) => {
             let args = process_schema_args(command.args.as_ref(), target_resource);
-            let (_exit_code, stdout, _stderr) = invoke_command(&command.executable, args, None, Some(&resource.directory), None, manifest.exit_codes.as_ref())?;
+            let (_exit_code, stdout, _stderr) = invoke_command(&command.executable, args, None, Some(&resource.directory), None, manifest.exit_codes.as_ref(), command.sha256.as_ref())?;
             let schema_value: Value = serde_json::from_str(&stdout)?;
             (stdout, schema_value)
         },
@@ -631,7 +739,7 @@ fn verify_with_export_schema(input: &str, resource: &DscResource, target_resourc
     let schema = match export.schema_or_filtering {
         Some(ExportSchemaOrFiltering::Schema(ExportSchemaKind::Command(ref command))) => {
             let args = process_schema_args(command.args.as_ref(), command_resource);
-            let (_exit_code, stdout, _stderr) = invoke_command(&command.executable, args, None, Some(&resource.directory), None, manifest.exit_codes.as_ref())?;
+            let (_exit_code, stdout, _stderr) = invoke_command(&command.executable, args, None, Some(&resource.directory), None, manifest.exit_codes.as_ref(), command.sha256.as_ref())?;
             stdout
         },
         Some(ExportSchemaOrFiltering::Schema(ExportSchemaKind::Embedded(ref schema))) => {
@@ -659,7 +767,7 @@ fn verify_with_export_schema(input: &str, resource: &DscResource, target_resourc
         }
     };
     let schema = serde_json::from_str(&schema)?;
-    let compiled_schema = match Validator::new(&schema) {
+    let compiled_schema = match jsonschema::options().with_dsc_keywords().build(&schema) {
         Ok(schema) => schema,
         Err(e) => {
             return Err(DscError::Schema(e.to_string()));
@@ -688,6 +796,32 @@ fn verify_with_export_schema(input: &str, resource: &DscResource, target_resourc
 ///
 /// Error returned if the resource does not successfully export the current state
 pub fn invoke_export(resource: &DscResource, input: Option<&str>, target_resource: Option<&DscResource>) -> Result<ExportResult, DscError> {
+    let mut instances: Vec<Value> = Vec::new();
+    invoke_export_stream(resource, input, target_resource, &mut |instance| {
+        instances.push(instance);
+        Ok(())
+    })?;
+
+    Ok(ExportResult {
+        actual_state: instances,
+    })
+}
+
+/// Invoke the export operation on a resource, passing each instance to `on_instance` as the
+/// resource writes it instead of collecting all of the instances.
+///
+/// # Arguments
+///
+/// * `resource` - The resource manifest
+/// * `input` - Input to the command
+/// * `target_resource` - The adapted resource to export, if `resource` is an adapter
+/// * `on_instance` - Called with each exported instance
+///
+/// # Errors
+///
+/// Error returned if the resource does not successfully export the current state or
+/// `on_instance` returns an error.
+pub fn invoke_export_stream(resource: &DscResource, input: Option<&str>, target_resource: Option<&DscResource>, on_instance: &mut dyn FnMut(Value) -> Result<(), DscError>) -> Result<(), DscError> {
     let Some(manifest) = &resource.manifest else {
         return Err(DscError::MissingManifest(resource.type_name.to_string()));
     };
@@ -696,26 +830,23 @@ pub fn invoke_export(resource: &DscResource, input: Option<&str>, target_resourc
         if manifest.get.is_some() {
             info!("{}", t!("dscresources.commandResource.exportNotSupportedUsingGet", resource = &resource.type_name));
             let get_result = invoke_get(resource, input.unwrap_or(""), target_resource)?;
-            let mut instances: Vec<Value> = Vec::new();
             match get_result {
                 GetResult::Group(group_response) => {
                     for result in group_response {
-                        instances.push(serde_json::to_value(result)?);
+                        on_instance(serde_json::to_value(result)?)?;
                     }
                 },
                 GetResult::Resource(response) => {
-                    instances.push(response.actual_state);
+                    on_instance(response.actual_state)?;
                 }
             }
-            return Ok(ExportResult {
-                actual_state: instances,
-            });
+            return Ok(());
         }
         // if neither export nor get is supported, return an error
         return Err(DscError::Operation(t!("dscresources.commandResource.exportNotSupported", resource = &resource.type_name).to_string()))
     };
 
-    let mut command_input: CommandInput = CommandInput { env: None, stdin: None };
+    let mut command_input = CommandInput::default();
     let args: Option<Vec<String>>;
     let command_resource = match target_resource {
         Some(r) => r,
@@ -738,27 +869,256 @@ pub fn invoke_export(resource: &DscResource, input: Option<&str>, target_resourc
     } else {
         args = process_get_args(export.args.as_ref(), "", command_resource);
     }
+    let command_input = command_input.with_environment(&export.environment, &resource.directory)?;
 
-    let (_exit_code, stdout, stderr) = invoke_command(&export.executable, args, command_input.stdin.as_deref(), Some(&resource.directory), command_input.env, manifest.exit_codes.as_ref())?;
-    let mut instances: Vec<Value> = Vec::new();
-    for line in stdout.lines()
-    {
+    let mut on_line = |line: &str| -> Result<(), DscError> {
         let instance: Value = match serde_json::from_str(line){
             Result::Ok(r) => {r},
             Result::Err(err) => {
-                return Err(DscError::Operation(t!("dscresources.commandResource.failedParseJson", executable = &export.executable, stdout = stdout, stderr = stderr, err = err).to_string()))
+                return Err(DscError::Operation(t!("dscresources.commandResource.failedParseExportJson", executable = &export.executable, line = line, err = err).to_string()))
             }
         };
         if resource.kind == Kind::Resource {
             debug!("{}", t!("dscresources.commandResource.exportVerifyOutput", resource = &resource.type_name, executable = &export.executable));
             verify_json_from_manifest(resource, line, target_resource)?;
         }
-        instances.push(instance);
+        on_instance(instance)
+    };
+    invoke_operation_stream(resource, command_resource, "export", &export.executable, export.sha256.as_ref(), args, input.unwrap_or_default(), command_input, &ExecutionKind::Actual, &mut on_line)
+}
+
+/// Invoke the get operation on a resource with the `batch` capability for multiple instances in
+/// one invocation.
+///
+/// # Arguments
+///
+/// * `resource` - The resource manifest
+/// * `filters` - The instances to get, each in JSON
+///
+/// # Errors
+///
+/// Error returned if the resource does not successfully get the current state of every instance.
+pub fn invoke_get_batch(resource: &DscResource, filters: &[String]) -> Result<Vec<GetResult>, DscError> {
+    let states = invoke_get_batch_states(resource, filters)?;
+    Ok(states.into_iter().map(|actual_state| GetResult::Resource(ResourceGetResponse { actual_state })).collect())
+}
+
+fn invoke_get_batch_states(resource: &DscResource, filters: &[String]) -> Result<Vec<Value>, DscError> {
+    let Some(manifest) = &resource.manifest else {
+        return Err(DscError::MissingManifest(resource.type_name.to_string()));
+    };
+    let Some(get) = &manifest.get else {
+        return Err(DscError::NotImplemented("get".to_string()));
+    };
+    validate_security_context(&get.require_security_context, &resource.type_name, "get")?;
+    let input = get_batch_input(resource, filters)?;
+    let args = process_get_args(get.args.as_ref(), &input, resource);
+    let command_input = get_command_input(get.input.as_ref(), &input)?.with_environment(&get.environment, &resource.directory)?;
+
+    info!("{}", t!("dscresources.commandResource.invokeBatchUsing", operation = "get", count = filters.len(), resource = &resource.type_name, executable = &get.executable));
+    let (_exit_code, stdout, _stderr) = invoke_operation(resource, resource, "get", &get.executable, get.sha256.as_ref(), args, &input, command_input, &ExecutionKind::Actual)?;
+    get_batch_states(resource, &get.executable, &stdout, filters.len())
+}
+
+/// Invoke the test operation on a resource with the `batch` capability for multiple instances in
+/// one invocation.
+///
+/// If the resource doesn't implement test, the instances are compared to the states returned by a
+/// batched get.
+///
+/// # Arguments
+///
+/// * `resource` - The resource manifest
+/// * `expected` - The expected state of each instance in JSON
+///
+/// # Errors
+///
+/// Error returned if the resource does not successfully test every instance.
+pub fn invoke_test_batch(resource: &DscResource, expected: &[String]) -> Result<Vec<TestResult>, DscError> {
+    let expected = normalize_desired_states(resource, expected, None)?;
+    test_normalized_batch(resource, &expected)
+}
+
+fn test_normalized_batch(resource: &DscResource, expected: &[String]) -> Result<Vec<TestResult>, DscError> {
+    let Some(manifest) = &resource.manifest else {
+        return Err(DscError::MissingManifest(resource.type_name.to_string()));
+    };
+    let actual_states: Vec<(Value, Option<Vec<String>>)> = if let Some(test) = &manifest.test {
+        validate_security_context(&test.require_security_context, &resource.type_name, "test")?;
+        let input = get_batch_input(resource, expected)?;
+        let args = process_get_args(test.args.as_ref(), &input, resource);
+        let command_input = get_command_input(test.input.as_ref(), &input)?.with_environment(&test.environment, &resource.directory)?;
+
+        info!("{}", t!("dscresources.commandResource.invokeBatchUsing", operation = "test", count = expected.len(), resource = &resource.type_name, executable = &test.executable));
+        let (_exit_code, stdout, _stderr) = invoke_operation(resource, resource, "test", &test.executable, test.sha256.as_ref(), args, &input, command_input, &ExecutionKind::Actual)?;
+        if test.returns == Some(ReturnKind::StateAndDiff) {
+            get_batch_states_and_diffs(resource, &test.executable, &stdout, expected.len())?
+                .into_iter().map(|(actual_state, diff)| (actual_state, Some(diff))).collect()
+        } else {
+            get_batch_states(resource, &test.executable, &stdout, expected.len())?
+                .into_iter().map(|actual_state| (actual_state, None)).collect()
+        }
+    } else {
+        info!("{}", t!("dscresources.commandResource.testSyntheticTest", resource = &resource.type_name));
+        invoke_get_batch_states(resource, expected)?
+            .into_iter().map(|actual_state| (actual_state, None)).collect()
+    };
+
+    let schema = get_cached_schema(resource, None);
+    let mut results = Vec::with_capacity(expected.len());
+    for (expected, (actual_state, diff)) in expected.iter().zip(actual_states) {
+        let expected_value: Value = serde_json::from_str(expected)?;
+        let in_desired_state = get_desired_state(&actual_state)?;
+        let (diff_properties, diff_reasons) = match diff {
+            Some(diff) => (diff, Vec::new()),
+            None => get_diff_with_reasons(&expected_value, &actual_state, schema.as_ref()),
+        };
+        let reasons = get_reasons_or_diff(&actual_state, in_desired_state, diff_reasons)?;
+        results.push(TestResult::Resource(ResourceTestResponse {
+            desired_state: redact(&expected_value),
+            actual_state,
+            in_desired_state: in_desired_state.unwrap_or(diff_properties.is_empty()),
+            diff_properties,
+            reasons,
+        }));
     }
+    Ok(results)
+}
 
-    Ok(ExportResult {
-        actual_state: instances,
-    })
+/// Invoke the set operation on a resource with the `batch` capability for multiple instances in
+/// one invocation.
+///
+/// Unless the test is skipped or the resource implements a pre-test, the instances are tested
+/// with a batched test first and only the instances that aren't in the desired state are set. The
+/// state of every instance before the set is retrieved with a batched get.
+///
+/// # Arguments
+///
+/// * `resource` - The resource manifest
+/// * `desired` - The desired state of each instance in JSON
+/// * `skip_test` - Whether to skip the test before setting the instances
+///
+/// # Errors
+///
+/// Error returned if the resource does not successfully set every instance.
+pub fn invoke_set_batch(resource: &DscResource, desired: &[String], skip_test: bool) -> Result<Vec<SetResult>, DscError> {
+    let Some(manifest) = &resource.manifest else {
+        return Err(DscError::MissingManifest(resource.type_name.to_string()));
+    };
+    let Some(set) = &manifest.set else {
+        return Err(DscError::NotImplemented("set".to_string()));
+    };
+    validate_security_context(&set.require_security_context, &resource.type_name, "set")?;
+    let desired = normalize_desired_states(resource, desired, None)?;
+
+    let mut results: Vec<Option<SetResult>> = vec![None; desired.len()];
+    let mut pending = Vec::with_capacity(desired.len());
+    // if resource doesn't implement a pre-test, we execute test first to see which instances need a set
+    if !skip_test && set.pre_test != Some(true) {
+        info!("{}", t!("dscresources.commandResource.noPretest", resource = &resource.type_name));
+        for (index, test_result) in test_normalized_batch(resource, &desired)?.into_iter().enumerate() {
+            match test_result {
+                TestResult::Resource(response) if response.in_desired_state => {
+                    results[index] = Some(SetResult::Resource(ResourceSetResponse {
+                        before_state: response.desired_state,
+                        after_state: redact(&response.actual_state),
+                        changed_properties: None,
+                    }));
+                },
+                _ => pending.push(index),
+            }
+        }
+    } else {
+        pending.extend(0..desired.len());
+    }
+
+    if !pending.is_empty() {
+        let pending_desired: Vec<String> = pending.iter().map(|index| desired[*index].clone()).collect();
+        let before_states = invoke_get_batch_states(resource, &pending_desired)?;
+
+        let input = get_batch_input(resource, &pending_desired)?;
+        let (args, _) = process_set_delete_args(set.args.as_ref(), &input, resource, &ExecutionKind::Actual);
+        let command_input = get_command_input(set.input.as_ref(), &input)?.with_environment(&set.environment, &resource.directory)?;
+
+        info!("{}", t!("dscresources.commandResource.invokeBatchUsing", operation = "set", count = pending_desired.len(), resource = &resource.type_name, executable = &set.executable));
+        let (_exit_code, stdout, _stderr) = invoke_operation(resource, resource, "set", &set.executable, set.sha256.as_ref(), args, &input, command_input, &ExecutionKind::Actual)?;
+        let after_states: Vec<(Value, Option<Vec<String>>)> = match set.returns {
+            Some(ReturnKind::State) => {
+                get_batch_states(resource, &set.executable, &stdout, pending_desired.len())?
+                    .into_iter().map(|after_state| (after_state, None)).collect()
+            },
+            Some(ReturnKind::StateAndDiff) => {
+                get_batch_states_and_diffs(resource, &set.executable, &stdout, pending_desired.len())?
+                    .into_iter().map(|(after_state, diff)| (after_state, Some(diff))).collect()
+            },
+            // perform a batched get and compare the result to the state before the set
+            None => {
+                invoke_get_batch_states(resource, &pending_desired)?
+                    .into_iter().map(|after_state| (after_state, None)).collect()
+            },
+        };
+
+        for ((index, before_state), (after_state, diff)) in pending.into_iter().zip(before_states).zip(after_states) {
+            let changed_properties = diff.unwrap_or_else(|| get_diff(&after_state, &before_state));
+            results[index] = Some(SetResult::Resource(ResourceSetResponse {
+                before_state: redact(&before_state),
+                after_state: redact(&after_state),
+                changed_properties: Some(changed_properties),
+            }));
+        }
+    }
+    Ok(results.into_iter().flatten().collect())
+}
+
+/// Validate each instance and combine them into the JSON array sent to a batch resource.
+fn get_batch_input(resource: &DscResource, instances: &[String]) -> Result<String, DscError> {
+    let mut values = Vec::with_capacity(instances.len());
+    for instance in instances {
+        verify_json_from_manifest(resource, instance, None)?;
+        values.push(serde_json::from_str::<Value>(instance)?);
+    }
+    Ok(serde_json::to_string(&values)?)
+}
+
+/// Parse the JSON line written by a batch resource for each instance.
+fn get_batch_states(resource: &DscResource, executable: &str, stdout: &str, count: usize) -> Result<Vec<Value>, DscError> {
+    let mut states = Vec::with_capacity(count);
+    for line in stdout.lines().filter(|line| !line.is_empty()) {
+        let state: Value = serde_json::from_str(line).map_err(|err| {
+            DscError::Operation(t!("dscresources.commandResource.failedParseBatchJson", executable = executable, line = line, err = err).to_string())
+        })?;
+        if resource.kind == Kind::Resource {
+            verify_json_from_manifest(resource, line, None)?;
+        }
+        states.push(state);
+    }
+    if states.len() != count {
+        return Err(DscError::Operation(t!("dscresources.commandResource.batchCountMismatch", resource = &resource.type_name, expected = count, actual = states.len()).to_string()));
+    }
+    Ok(states)
+}
+
+/// Parse the JSON line with the state and the JSON line with the changed properties written by a
+/// batch resource for each instance.
+fn get_batch_states_and_diffs(resource: &DscResource, executable: &str, stdout: &str, count: usize) -> Result<Vec<(Value, Vec<String>)>, DscError> {
+    let lines: Vec<&str> = stdout.lines().filter(|line| !line.is_empty()).collect();
+    if lines.len() != count * 2 {
+        return Err(DscError::Operation(t!("dscresources.commandResource.batchCountMismatch", resource = &resource.type_name, expected = count, actual = lines.len() / 2).to_string()));
+    }
+    let mut states = Vec::with_capacity(count);
+    for pair in lines.chunks(2) {
+        let state: Value = serde_json::from_str(pair[0]).map_err(|err| {
+            DscError::Operation(t!("dscresources.commandResource.failedParseBatchJson", executable = executable, line = pair[0], err = err).to_string())
+        })?;
+        if resource.kind == Kind::Resource {
+            verify_json_from_manifest(resource, pair[0], None)?;
+        }
+        let diff: Vec<String> = serde_json::from_str(pair[1]).map_err(|err| {
+            DscError::Operation(t!("dscresources.commandResource.failedParseBatchJson", executable = executable, line = pair[1], err = err).to_string())
+        })?;
+        states.push((state, diff));
+    }
+    Ok(states)
 }
 
 /// Invoke the resolve operation on a resource
@@ -788,14 +1148,101 @@ pub fn invoke_resolve(resource: &DscResource, input: &str) -> Result<ResolveResu
     command_resource.type_name = resource.type_name.clone();
     command_resource.path = resource.path.clone();
     let args = process_get_args(resolve.args.as_ref(), input, &command_resource);
-    let command_input = get_command_input(resolve.input.as_ref(), input)?;
+    let command_input = get_command_input(resolve.input.as_ref(), input)?.with_environment(&resolve.environment, &resource.directory)?;
 
     info!("{}", t!("dscresources.commandResource.invokeResolveUsing", resource = &resource.type_name, executable = &resolve.executable));
-    let (_exit_code, stdout, _stderr) = invoke_command(&resolve.executable, args, command_input.stdin.as_deref(), Some(&resource.directory), command_input.env, manifest.exit_codes.as_ref())?;
+    let (_exit_code, stdout, _stderr) = invoke_method_command(&resolve.executable, args, command_input, &resource.directory, manifest.exit_codes.as_ref(), resolve.sha256.as_ref(), None)?;
     let result: ResolveResult = serde_json::from_str(&stdout)?;
     Ok(result)
 }
 
+/// Invoke an operation of a resource and return the exit code, stdout, and stderr.
+///
+/// The request is sent to the long-lived server of the resource if the manifest defines one,
+/// otherwise the executable of the operation is run as a new process.
+///
+/// # Arguments
+///
+/// * `resource` - The resource whose manifest defines the operation
+/// * `command_resource` - The resource the operation is for, differs from `resource` for adapted resources
+/// * `operation` - The name of the operation
+/// * `executable` - The command to execute when there is no server
+/// * `sha256` - The digests allowed for the executable
+/// * `args` - Optional arguments to pass to the command
+/// * `input` - The JSON input of the operation
+/// * `command_input` - The input to pass to the command
+/// * `execution_type` - Whether this is an actual operation or what-if
+///
+/// # Errors
+///
+/// Error is returned if the operation fails.
+#[allow(clippy::too_many_arguments)]
+fn invoke_operation(resource: &DscResource, command_resource: &DscResource, operation: &str, executable: &str, sha256: Option<&ExecutableDigest>, args: Option<Vec<String>>, input: &str, command_input: CommandInput, execution_type: &ExecutionKind) -> Result<(i32, String, String), DscError> {
+    let Some(manifest) = &resource.manifest else {
+        return Err(DscError::MissingManifest(resource.type_name.to_string()));
+    };
+    if let Some(server) = &manifest.server {
+        return invoke_server(&resource.path, &resource.directory, server, manifest.exit_codes.as_ref(), &command_resource.type_name, operation, input, execution_type == &ExecutionKind::WhatIf);
+    }
+    invoke_method_command(executable, args, command_input, &resource.directory, manifest.exit_codes.as_ref(), sha256, None)
+}
+
+/// Invoke an operation of a resource, passing each non-empty line of stdout to `on_line` as the
+/// resource writes it.
+///
+/// A resource server returns its whole response at once, so its lines are passed after it responds.
+///
+/// # Errors
+///
+/// Error is returned if the operation fails or `on_line` returns an error.
+#[allow(clippy::too_many_arguments)]
+fn invoke_operation_stream(resource: &DscResource, command_resource: &DscResource, operation: &str, executable: &str, sha256: Option<&ExecutableDigest>, args: Option<Vec<String>>, input: &str, command_input: CommandInput, execution_type: &ExecutionKind, on_line: StdoutLineHandler<'_>) -> Result<(), DscError> {
+    let Some(manifest) = &resource.manifest else {
+        return Err(DscError::MissingManifest(resource.type_name.to_string()));
+    };
+    if let Some(server) = &manifest.server {
+        let (_exit_code, stdout, _stderr) = invoke_server(&resource.path, &resource.directory, server, manifest.exit_codes.as_ref(), &command_resource.type_name, operation, input, execution_type == &ExecutionKind::WhatIf)?;
+        for line in stdout.lines().filter(|line| !line.is_empty()) {
+            on_line(line)?;
+        }
+        return Ok(());
+    }
+    invoke_method_command(executable, args, command_input, &resource.directory, manifest.exit_codes.as_ref(), sha256, Some(on_line))?;
+    Ok(())
+}
+
+/// Invoke the command of a method with its input, working directory, and environment.
+///
+/// The executable is found relative to the directory of the manifest, even if the method declares
+/// a different working directory.
+///
+/// # Errors
+///
+/// Error is returned if the command fails.
+fn invoke_method_command(executable: &str, args: Option<Vec<String>>, command_input: CommandInput, directory: &Path, exit_codes: &ExitCodesMap, sha256: Option<&ExecutableDigest>, on_stdout_line: Option<StdoutLineHandler<'_>>) -> Result<(i32, String, String), DscError> {
+    let args = command_input.process_args(args);
+    let environment = ProcessEnvironment {
+        cwd: Some(command_input.cwd.as_deref().unwrap_or(directory)),
+        env: command_input.env,
+        env_passthrough: command_input.env_passthrough.as_deref(),
+    };
+    invoke_command_with_stdout_handler(executable, args, command_input.stdin.as_deref(), Some(directory), environment, exit_codes, sha256, on_stdout_line)
+}
+
+/// The working directory and environment of a command.
+struct ProcessEnvironment<'a> {
+    /// The working directory to execute the command in.
+    cwd: Option<&'a Path>,
+    /// Environment variable mappings to add or update.
+    env: Option<HashMap<String, String>>,
+    /// If specified, only these environment variables are inherited from DSC.
+    env_passthrough: Option<&'a [String]>,
+}
+
+/// Environment variables DSC uses to communicate with the command, inherited even when the
+/// environment is scrubbed.
+pub(crate) const DSC_ENV_PASSTHROUGH: [&str; 2] = ["DSC_TRACE_LEVEL", DSC_CONFIG_ROOT];
+
 /// Asynchronously invoke a command and return the exit code, stdout, and stderr.
 ///
 /// # Arguments
@@ -803,20 +1250,24 @@ pub fn invoke_resolve(resource: &DscResource, input: &str) -> Result<ResolveResu
 /// * `executable` - The command to execute
 /// * `args` - Optional arguments to pass to the command
 /// * `input` - Optional input to pass to the command
-/// * `cwd` - Optional working directory to execute the command in
-/// * `env` - Optional environment variable mappings to add or update
+/// * `environment` - The working directory and environment for the command
 /// * `exit_codes` - Descriptions of exit codes, either defined by the manifest or using the
 ///   default descriptions for success and failure.
+/// * `on_stdout_line` - If specified, called with each non-empty line of stdout as it's written
+///   instead of returning stdout.
 ///
 /// # Errors
 ///
-/// Error is returned if the command fails to execute or stdin/stdout/stderr cannot be opened.
+/// Error is returned if the command fails to execute, stdin/stdout/stderr cannot be opened, or
+/// `on_stdout_line` returns an error.
 ///
-async fn run_process_async(executable: &str, args: Option<Vec<String>>, input: Option<&str>, cwd: Option<&Path>, env: Option<HashMap<String, String>>, exit_codes: &ExitCodesMap) -> Result<(i32, String, String), DscError> {
+async fn run_process_async(executable: &str, args: Option<Vec<String>>, input: Option<&str>, environment: ProcessEnvironment<'_>, exit_codes: &ExitCodesMap, mut on_stdout_line: Option<StdoutLineHandler<'_>>) -> Result<(i32, String, String), DscError> {
 
     // use somewhat large initial buffer to avoid early string reallocations;
     // the value is based on list result of largest of built-in adapters - WMI adapter ~500KB
     const INITIAL_BUFFER_CAPACITY: usize = 1024*1024;
+    // the number of stdout lines buffered before the child process is blocked from writing more
+    const STDOUT_LINE_CAPACITY: usize = 1024;
 
     let mut command = Command::new(executable);
     if input.is_some() {
@@ -829,10 +1280,18 @@ async fn run_process_async(executable: &str, args: Option<Vec<String>>, input: O
     if let Some(args) = args {
         command.args(args);
     }
-    if let Some(cwd) = cwd {
+    if let Some(cwd) = environment.cwd {
         command.current_dir(cwd);
     }
-    if let Some(env) = env {
+    if let Some(env_passthrough) = environment.env_passthrough {
+        command.env_clear();
+        for name in env_passthrough.iter().map(String::as_str).chain(DSC_ENV_PASSTHROUGH) {
+            if let Some(value) = env::var_os(name) {
+                command.env(name, value);
+            }
+        }
+    }
+    if let Some(env) = environment.env {
         command.envs(env);
     }
     if executable == "dsc" && env::var("DEBUG_DSC").is_ok() {
@@ -857,7 +1316,7 @@ async fn run_process_async(executable: &str, args: Option<Vec<String>>, input: O
     let mut stderr_reader = BufReader::new(stderr).lines();
 
     if let Some(input) = input {
-        trace!("Writing to command STDIN: {input}");
+        trace!("Writing to command STDIN: {}", redact_json_string(input));
         let Some(mut stdin) = child.stdin.take() else {
             return Err(DscError::CommandOperation(t!("dscresources.commandResource.processChildStdin").to_string(), executable.to_string()));
         };
@@ -875,35 +1334,62 @@ async fn run_process_async(executable: &str, args: Option<Vec<String>>, input: O
         child.wait().await
     });
 
+    let (stdout_sender, mut stdout_receiver) = mpsc::channel::<String>(STDOUT_LINE_CAPACITY);
     let stdout_task = tokio::spawn(async move {
-        let mut stdout_result = String::with_capacity(INITIAL_BUFFER_CAPACITY);
         while let Ok(Some(line)) = stdout_reader.next_line().await {
-            stdout_result.push_str(&line);
-            stdout_result.push('\n');
+            if stdout_sender.send(line).await.is_err() {
+                break;
+            }
         }
-        stdout_result
     });
 
     let stderr_task = tokio::spawn(async move {
         let mut filtered_stderr = String::with_capacity(INITIAL_BUFFER_CAPACITY);
+        let mut resource_error = None;
         while let Ok(Some(stderr_line)) = stderr_reader.next_line().await {
+            // the error only replaces stderr when the process fails, otherwise the line is kept
+            if let Some(error_data) = get_resource_error_data(&stderr_line) {
+                resource_error = Some(error_data.error);
+            }
             let filtered_stderr_line = log_stderr_line(&child_id, &stderr_line);
             if !filtered_stderr_line.is_empty() {
                 filtered_stderr.push_str(filtered_stderr_line);
                 filtered_stderr.push('\n');
             }
         }
-        filtered_stderr
+        (filtered_stderr, resource_error)
     });
 
+    let mut stdout_result = String::with_capacity(if on_stdout_line.is_some() { 0 } else { INITIAL_BUFFER_CAPACITY });
+    let mut on_stdout_line_result = Ok(());
+    while let Some(line) = stdout_receiver.recv().await {
+        match on_stdout_line.as_mut() {
+            Some(on_stdout_line) => {
+                // after the handler fails, the remaining output is drained so the process can exit
+                if on_stdout_line_result.is_ok() && !line.is_empty() {
+                    on_stdout_line_result = on_stdout_line(&line);
+                }
+            },
+            None => {
+                stdout_result.push_str(&line);
+                stdout_result.push('\n');
+            }
+        }
+    }
+
     let exit_code = child_task.await.unwrap()?.code();
-    let stdout_result = stdout_task.await.unwrap();
-    let stderr_result = stderr_task.await.unwrap();
+    stdout_task.await.unwrap();
+    let (stderr_result, resource_error) = stderr_task.await.unwrap();
 
     if let Some(code) = exit_code {
         debug!("{}", t!("dscresources.commandResource.processChildExit", executable = executable, id = child_id, code = code));
 
         if code != 0 {
+            // A structured error from the resource is more specific than the manifest description of the exit code.
+            if let Some(resource_error) = resource_error {
+                error!("{}", t!("dscresources.commandResource.resourceError", pid = child_id, error = resource_error));
+                return Err(DscError::CommandResourceError(executable.to_string(), code, Box::new(resource_error)));
+            }
             // Only use manifest-provided exit code mappings when the map is not empty/default,
             // so that default mappings do not suppress stderr-based diagnostics.
             if !exit_codes.is_empty_or_default()
@@ -917,6 +1403,7 @@ async fn run_process_async(executable: &str, args: Option<Vec<String>>, input: O
             return Err(DscError::Command(executable.to_string(), code, stderr_result));
         }
 
+        on_stdout_line_result?;
         Ok((code, stdout_result, stderr_result))
     } else {
         debug!("{}", t!("dscresources.commandResource.processChildTerminated", executable = executable, id = child_id));
@@ -935,26 +1422,70 @@ async fn run_process_async(executable: &str, args: Option<Vec<String>>, input: O
 /// * `env` - Optional environment variable mappings to add or update
 /// * `exit_codes` - Descriptions of exit codes, either defined by the manifest or using the
 ///   default descriptions for success and failure.
+/// * `sha256` - The digests pinned in the manifest for the executable, verified before it's run
 ///
 /// # Errors
 ///
-/// Error is returned if the command fails to execute or stdin/stdout/stderr cannot be opened.
+/// Error is returned if the executable doesn't match the pinned digests, the command fails to
+/// execute, or stdin/stdout/stderr cannot be opened.
 ///
 /// # Panics
 ///
 /// Will panic if tokio runtime can't be created.
 ///
 #[allow(clippy::implicit_hasher)]
-pub fn invoke_command(executable: &str, args: Option<Vec<String>>, input: Option<&str>, cwd: Option<&Path>, env: Option<HashMap<String, String>>, exit_codes: &ExitCodesMap) -> Result<(i32, String, String), DscError> {
+pub fn invoke_command(executable: &str, args: Option<Vec<String>>, input: Option<&str>, cwd: Option<&Path>, env: Option<HashMap<String, String>>, exit_codes: &ExitCodesMap, sha256: Option<&ExecutableDigest>) -> Result<(i32, String, String), DscError> {
+    invoke_command_with_stdout_handler(executable, args, input, cwd, ProcessEnvironment { cwd, env, env_passthrough: None }, exit_codes, sha256, None)
+}
+
+/// Invoke a command, passing each non-empty line of stdout to `on_stdout_line` as the command
+/// writes it, and return the exit code and stderr.
+///
+/// Only a bounded number of lines are buffered, so commands with a large amount of output are
+/// processed in bounded memory.
+///
+/// # Arguments
+///
+/// * `executable` - The command to execute
+/// * `args` - Optional arguments to pass to the command
+/// * `input` - Optional input to pass to the command
+/// * `cwd` - Optional working directory to execute the command in
+/// * `env` - Optional environment variable mappings to add or update
+/// * `exit_codes` - Descriptions of exit codes, either defined by the manifest or using the
+///   default descriptions for success and failure.
+/// * `sha256` - The digests pinned in the manifest for the executable, verified before it's run
+/// * `on_stdout_line` - Called with each non-empty line of stdout
+///
+/// # Errors
+///
+/// Error is returned if the executable doesn't match the pinned digests, the command fails to
+/// execute, stdin/stdout/stderr cannot be opened, or `on_stdout_line` returns an error.
+///
+/// # Panics
+///
+/// Will panic if tokio runtime can't be created.
+///
+#[allow(clippy::implicit_hasher, clippy::too_many_arguments)]
+pub fn invoke_command_stream(executable: &str, args: Option<Vec<String>>, input: Option<&str>, cwd: Option<&Path>, env: Option<HashMap<String, String>>, exit_codes: &ExitCodesMap, sha256: Option<&ExecutableDigest>, on_stdout_line: StdoutLineHandler<'_>) -> Result<(i32, String), DscError> {
+    let (code, _stdout, stderr) = invoke_command_with_stdout_handler(executable, args, input, cwd, ProcessEnvironment { cwd, env, env_passthrough: None }, exit_codes, sha256, Some(on_stdout_line))?;
+    Ok((code, stderr))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn invoke_command_with_stdout_handler(executable: &str, args: Option<Vec<String>>, input: Option<&str>, cwd: Option<&Path>, environment: ProcessEnvironment<'_>, exit_codes: &ExitCodesMap, sha256: Option<&ExecutableDigest>, on_stdout_line: Option<StdoutLineHandler<'_>>) -> Result<(i32, String, String), DscError> {
     let executable = canonicalize_which(executable, cwd)?;
+    verify_executable_digest(&executable, sha256)?;
 
     let run_async = async {
-        trace!("{}", t!("dscresources.commandResource.commandInvoke", executable = executable, args = args : {:?}));
-        if let Some(cwd) = cwd {
+        trace!("{}", t!("dscresources.commandResource.commandInvoke", executable = executable, args = redact_args(args.as_ref()) : {:?}));
+        if let Some(cwd) = environment.cwd {
             trace!("{}", t!("dscresources.commandResource.commandCwd", cwd = cwd.display()));
         }
+        if let Some(env_passthrough) = environment.env_passthrough {
+            trace!("{}", t!("dscresources.commandResource.commandEnvPassthrough", names = env_passthrough.join(", ")));
+        }
 
-        match run_process_async(&executable, args, input, cwd, env, exit_codes).await {
+        match run_process_async(&executable, args, input, environment, exit_codes, on_stdout_line).await {
             Ok((code, stdout, stderr)) => {
                 Ok((code, stdout, stderr))
             },
@@ -983,6 +1514,11 @@ pub fn invoke_command(executable: &str, args: Option<Vec<String>>, input: Option
     }
 }
 
+/// Redact secure values from JSON arguments so the command line can be logged.
+fn redact_args(args: Option<&Vec<String>>) -> Option<Vec<String>> {
+    args.map(|args| args.iter().map(|arg| redact_json_string(arg)).collect())
+}
+
 /// Process the arguments for a command resource's get operation.
 ///
 /// # Arguments
@@ -1143,14 +1679,81 @@ fn process_set_delete_args(args: Option<&Vec<SetDeleteArgKind>>, input: &str, re
     (Some(processed_args), supports_whatif)
 }
 
-struct CommandInput {
-    env: Option<HashMap<String, String>>,
-    stdin: Option<String>,
+#[derive(Default)]
+pub(crate) struct CommandInput {
+    pub env: Option<HashMap<String, String>>,
+    pub stdin: Option<String>,
+    /// The temporary file holding the input, removed when dropped.
+    pub file: Option<NamedTempFile>,
+    /// The working directory declared by the method, otherwise the directory of the manifest is used.
+    pub cwd: Option<PathBuf>,
+    /// The environment variables inherited by the command, when the environment is scrubbed.
+    pub env_passthrough: Option<Vec<String>>,
 }
 
-fn get_command_input(input_kind: Option<&InputKind>, input: &str) -> Result<CommandInput, DscError> {
+impl CommandInput {
+    /// Apply the environment and working directory declared by the method in the manifest.
+    ///
+    /// Instance properties passed as environment variables take precedence over the variables
+    /// declared by the method.
+    pub(crate) fn with_environment(mut self, environment: &CommandEnvironment, directory: &Path) -> Result<Self, DscError> {
+        if let Some(declared_env) = &environment.env {
+            let mut parser = Statement::new()?;
+            let context = Context::new();
+            let mut env = HashMap::new();
+            for (name, value) in declared_env {
+                let value = match parser.parse_and_execute(value, &context)? {
+                    Value::String(value) => value,
+                    value => value.to_string(),
+                };
+                env.insert(name.clone(), value);
+            }
+            if let Some(input_env) = self.env.take() {
+                env.extend(input_env);
+            }
+            self.env = Some(env);
+        }
+        if let Some(cwd) = &environment.cwd {
+            let base = match cwd.relative_to {
+                WorkingDirectoryBase::Manifest => directory.to_path_buf(),
+                WorkingDirectoryBase::ConfigRoot => match env::var(DSC_CONFIG_ROOT) {
+                    Ok(config_root) => PathBuf::from(config_root),
+                    Err(_) => {
+                        let current_dir = env::current_dir()?;
+                        debug!("{}", t!("dscresources.commandResource.configRootNotSet", path = cwd.path, current_dir = current_dir.display()));
+                        current_dir
+                    },
+                },
+            };
+            self.cwd = Some(base.join(&cwd.path));
+        }
+        self.env_passthrough.clone_from(&environment.env_passthrough);
+        Ok(self)
+    }
+
+    /// Replace the input file placeholder in the arguments with the path to the input file.
+    pub(crate) fn process_args(&self, args: Option<Vec<String>>) -> Option<Vec<String>> {
+        let Some(file) = &self.file else {
+            return args;
+        };
+        let path = file.path().to_string_lossy();
+        args.map(|args| args.into_iter().map(|arg| arg.replace(INPUT_FILE_PLACEHOLDER, &path)).collect())
+    }
+}
+
+/// Write the input to a temporary file only readable by the current user.
+fn write_input_file(input: &str) -> Result<NamedTempFile, DscError> {
+    let mut file = tempfile::Builder::new().prefix("dsc-input-").suffix(".json").tempfile()?;
+    file.write_all(input.as_bytes())?;
+    file.flush()?;
+    debug!("{}", t!("dscresources.commandResource.wroteInputFile", path = file.path().display()));
+    Ok(file)
+}
+
+pub(crate) fn get_command_input(input_kind: Option<&InputKind>, input: &str) -> Result<CommandInput, DscError> {
     let mut env: Option<HashMap<String, String>> = None;
     let mut stdin: Option<String> = None;
+    let mut file: Option<NamedTempFile> = None;
     match input_kind {
         Some(InputKind::Env) => {
             debug!("{}", t!("dscresources.commandResource.parseAsEnvVars"));
@@ -1160,6 +1763,10 @@ fn get_command_input(input_kind: Option<&InputKind>, input: &str) -> Result<Comm
             debug!("{}", t!("dscresources.commandResource.parseAsStdin"));
             stdin = Some(input.to_string());
         },
+        Some(InputKind::File) => {
+            debug!("{}", t!("dscresources.commandResource.parseAsFile"));
+            file = Some(write_input_file(input)?);
+        },
         None => {
             debug!("{}", t!("dscresources.commandResource.noInput"));
             // leave input as none
@@ -1169,6 +1776,8 @@ fn get_command_input(input_kind: Option<&InputKind>, input: &str) -> Result<Comm
     Ok(CommandInput {
         env,
         stdin,
+        file,
+        ..Default::default()
     })
 }
 
@@ -1197,7 +1806,7 @@ fn verify_json_from_manifest(resource: &DscResource, json: &str, target_resource
     // otherwise, use schema validation
     let schema = get_schema(resource, target_resource)?;
     let schema: Value = serde_json::from_str(&schema)?;
-    let compiled_schema = match Validator::new(&schema) {
+    let compiled_schema = match jsonschema::options().with_dsc_keywords().build(&schema) {
         Ok(schema) => schema,
         Err(e) => {
             return Err(DscError::Schema(e.to_string()));
@@ -1256,6 +1865,29 @@ fn json_to_hashmap(json: &str) -> Result<HashMap<String, String>, DscError> {
     Ok(map)
 }
 
+/// Parse a structured error written by a resource to stderr as `{"errorData": {"error": {...}}}`.
+///
+/// # Arguments
+///
+/// * `stderr_line` - The stderr line from the process
+///
+/// # Returns
+///
+/// The error data if the line is a valid structured error, otherwise `None`.
+pub(crate) fn get_resource_error_data(stderr_line: &str) -> Option<ResourceErrorData> {
+    let Ok(Value::Object(mut json_obj)) = serde_json::from_str::<Value>(stderr_line) else {
+        return None;
+    };
+    let error_data = json_obj.remove("errorData")?;
+    match serde_json::from_value::<ResourceErrorData>(error_data) {
+        Ok(error_data) => Some(error_data),
+        Err(err) => {
+            warn!("{}", t!("dscresources.commandResource.invalidErrorData", error = err));
+            None
+        }
+    }
+}
+
 /// Log output from a process as traces.
 ///
 /// # Arguments
@@ -1311,6 +1943,11 @@ pub fn log_stderr_line<'a>(process_id: &u32, trace_line: &'a str) -> &'a str
                 debug!("PID {process_id}: {}", msg.as_str().unwrap_or_default());
             } else if let Some(msg) = json_obj.get("trace") {
                 trace!("PID {process_id}: {}", msg.as_str().unwrap_or_default());
+            } else if let Some(progress) = json_obj.get("progress") {
+                match ResourceProgress::deserialize(progress) {
+                    Ok(progress) => report_resource_progress(*process_id, progress),
+                    Err(err) => warn!("PID {process_id}: {}", t!("dscresources.commandResource.invalidProgress", error = err)),
+                }
             } else {
                 // the line is a valid json, but not one of standard trace lines - return it as filtered stderr_line
                 trace!("PID {process_id}: {trace_line}");
@@ -2,11 +2,14 @@
 // Licensed under the MIT License.
 
 use crate::{configure::{Configurator, config_doc::{Configuration, ExecutionKind, Resource}, context::ProcessMode, parameters::{SECURE_VALUE_REDACTED, is_secure_value}, schema_cache::get_resource_schema}, dscresources::resource_manifest::{AdapterInputKind, Kind}, types::{FullyQualifiedTypeName, ResourceVersion}};
+use crate::configure::config_result::check_resource_errors;
 use crate::discovery::discovery_trait::DiscoveryFilter;
+use crate::discovery::manifest_signature::{get_trusted_publishers_setting, ManifestTrust};
 use crate::dscresources::invoke_result::{ResourceGetResponse, ResourceSetResponse};
+use crate::dscresources::resource_policy::{check_resource_policy, PolicyOperation};
+use crate::schemas::keywords::{CompareKind, DscValidationOptionsExtensions};
 use crate::schemas::transforms::idiomaticize_string_enum;
 use dscerror::DscError;
-use jsonschema::Validator;
 use rust_i18n::t;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -21,7 +24,7 @@ use super::{
     command_resource,
     dscerror,
     invoke_result::{
-        DeleteResultKind, ExportResult, GetResult, ResolveResult, ResourceTestResponse, SetResult, TestResult, ValidateResult
+        DeleteResultKind, ExportResult, GetResult, Reason, ResolveResult, ResourceTestResponse, SetResult, TestResult, ValidateResult
     },
     resource_manifest::ResourceManifest,
 };
@@ -63,6 +66,9 @@ pub struct DscResource {
     pub manifest: Option<ResourceManifest>,
     /// The content of the adapted resource, if available.
     pub adapted_content: Option<Map<String, Value>>,
+    /// Whether the manifest is signed by a trusted publisher.
+    #[serde(default, skip_serializing_if = "ManifestTrust::is_not_verified")]
+    pub trust: ManifestTrust,
 }
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq, Deserialize, Serialize, JsonSchema, DscRepoSchema, Ord, PartialOrd)]
@@ -88,6 +94,8 @@ pub enum Capability {
     Export,
     /// The resource supports resolving imported configuration.
     Resolve,
+    /// The resource supports receiving multiple instances in one invocation of get, set, and test.
+    Batch,
 }
 
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize, JsonSchema)]
@@ -119,9 +127,28 @@ impl DscResource {
             target_resource: None,
             manifest: None,
             adapted_content: None,
+            trust: ManifestTrust::NotVerified,
         }
     }
 
+    /// A resource receives multiple instances in one invocation when it has the `batch` capability
+    /// and is invoked directly as a command.
+    fn supports_batch(&self) -> bool {
+        self.capabilities.contains(&Capability::Batch)
+            && self.require_adapter.is_none()
+            && matches!(self.implemented_as, Some(ImplementedAs::Command))
+    }
+
+    /// Refuse to invoke the resource if it isn't signed by a trusted publisher and the policy
+    /// enforces trusted publishers.
+    fn check_trust(&self) -> Result<(), DscError> {
+        if let ManifestTrust::Untrusted { reason } = &self.trust
+            && get_trusted_publishers_setting().enforce {
+            return Err(DscError::UntrustedResource(self.type_name.to_string(), reason.clone()));
+        }
+        Ok(())
+    }
+
     fn create_config_for_adapter(self, adapter: &FullyQualifiedTypeName, input: &str) -> Result<Configurator, DscError> {
         // create new configuration with adapter and use this as the resource
         let mut configuration = Configuration::new();
@@ -157,6 +184,7 @@ impl DscResource {
         }
 
         let result = configurator.invoke_get()?;
+        check_resource_errors(result.had_errors, &result.messages)?;
         let GetResult::Resource(ref resource_result) = result.results[0].result else {
             return Err(DscError::Operation(t!("dscresources.dscresource.invokeReturnedWrongResult", operation = "get", resource = self.type_name).to_string()));
         };
@@ -181,6 +209,7 @@ impl DscResource {
         }
 
         let result = configurator.invoke_set(false)?;
+        check_resource_errors(result.had_errors, &result.messages)?;
         let SetResult::Resource(ref resource_result) = result.results[0].result else {
             return Err(DscError::Operation(t!("dscresources.dscresource.invokeReturnedWrongResult", operation = "set", resource = self.type_name).to_string()));
         };
@@ -214,6 +243,7 @@ impl DscResource {
         }
 
         let result = configurator.invoke_test()?;
+        check_resource_errors(result.had_errors, &result.messages)?;
         let TestResult::Resource(ref resource_result) = result.results[0].result else {
             return Err(DscError::Operation(t!("dscresources.dscresource.invokeReturnedWrongResult", operation = "test", resource = self.type_name).to_string()));
         };
@@ -229,12 +259,13 @@ impl DscResource {
             .as_array().ok_or(DscError::Operation(t!("dscresources.dscresource.propertyIncorrectType", property = "result", property_type = "array").to_string()))?[0]
             .as_object().ok_or(DscError::Operation(t!("dscresources.dscresource.propertyIncorrectType", property = "result", property_type = "object").to_string()))?
             .get("properties").ok_or(DscError::Operation(t!("dscresources.dscresource.propertyNotFound", property = "properties").to_string()))?.clone();
-        let diff_properties = get_diff(&desired_state, &actual_state);
+        let (diff_properties, reasons) = get_diff_with_reasons(&desired_state, &actual_state, None);
         let test_result = TestResult::Resource(ResourceTestResponse {
             desired_state,
             actual_state,
             in_desired_state: resource_result.in_desired_state,
             diff_properties,
+            reasons,
         });
         Ok(test_result)
     }
@@ -250,7 +281,8 @@ impl DscResource {
             return Err(DscError::NotSupported(t!("dscresources.dscresource.adapterDoesNotSupportDelete", adapter = adapter.type_name).to_string()));
         }
 
-        configurator.invoke_set(false)?;
+        let result = configurator.invoke_set(false)?;
+        check_resource_errors(result.had_errors, &result.messages)?;
         Ok(DeleteResultKind::ResourceActual)
     }
 
@@ -388,6 +420,23 @@ pub trait Invoke {
     /// This function will return an error if the underlying resource fails.
     fn export(&self, input: &str) -> Result<ExportResult, DscError>;
 
+    /// Invoke the export operation on the resource, passing each instance to `on_instance` as it's
+    /// exported instead of collecting all of the instances.
+    ///
+    /// Resources invoked through an adapter that takes a whole configuration are exported
+    /// before their instances are passed to `on_instance`.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - Input for export operation.
+    /// * `on_instance` - Called with each exported instance.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the underlying resource fails or `on_instance` returns
+    /// an error.
+    fn export_stream(&self, input: &str, on_instance: &mut dyn FnMut(Value) -> Result<(), DscError>) -> Result<(), DscError>;
+
     /// Invoke the resolve operation on the resource.
     ///
     /// # Arguments
@@ -398,11 +447,57 @@ pub trait Invoke {
     ///
     /// This function will return an error if the underlying resource fails.
     fn resolve(&self, input: &str) -> Result<ResolveResult, DscError>;
+
+    /// Invoke the get operation on the resource for multiple instances.
+    ///
+    /// A resource with the `batch` capability receives all of the instances in one invocation,
+    /// otherwise the resource is invoked once for each instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `filters` - The instances to get, each in JSON.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the underlying resource fails.
+    fn get_batch(&self, filters: &[String]) -> Result<Vec<GetResult>, DscError>;
+
+    /// Invoke the test operation on the resource for multiple instances.
+    ///
+    /// A resource with the `batch` capability receives all of the instances in one invocation,
+    /// otherwise the resource is invoked once for each instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `expected` - The expected state of each instance in JSON.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the underlying resource fails.
+    fn test_batch(&self, expected: &[String]) -> Result<Vec<TestResult>, DscError>;
+
+    /// Invoke the set operation on the resource for multiple instances.
+    ///
+    /// A resource with the `batch` capability receives all of the instances in one invocation,
+    /// unless the operation is what-if, otherwise the resource is invoked once for each instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `desired` - The desired state of each instance in JSON.
+    /// * `skip_test` - Whether to skip the test before setting the instances.
+    /// * `execution_type` - Whether to apply the desired states or only report the changes.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the underlying resource fails.
+    fn set_batch(&self, desired: &[String], skip_test: bool, execution_type: &ExecutionKind) -> Result<Vec<SetResult>, DscError>;
 }
 
 impl Invoke for DscResource {
     fn get(&self, filter: &str) -> Result<GetResult, DscError> {
         debug!("{}", t!("dscresources.dscresource.invokeGet", resource = self.type_name));
+        self.check_trust()?;
+        check_resource_policy(&self.type_name, PolicyOperation::Get, None)?;
         if let Some(deprecation_message) = self.deprecation_message.as_ref() {
             warn!("{}", t!("dscresources.dscresource.deprecationMessage", resource = self.type_name, message = deprecation_message));
         }
@@ -422,6 +517,8 @@ impl Invoke for DscResource {
 
     fn set(&self, desired: &str, skip_test: bool, execution_type: &ExecutionKind) -> Result<SetResult, DscError> {
         debug!("{}", t!("dscresources.dscresource.invokeSet", resource = self.type_name));
+        self.check_trust()?;
+        check_resource_policy(&self.type_name, PolicyOperation::Set, Some(execution_type))?;
         if let Some(deprecation_message) = self.deprecation_message.as_ref() {
             warn!("{}", t!("dscresources.dscresource.deprecationMessage", resource = self.type_name, message = deprecation_message));
         }
@@ -441,6 +538,8 @@ impl Invoke for DscResource {
 
     fn test(&self, expected: &str) -> Result<TestResult, DscError> {
         debug!("{}", t!("dscresources.dscresource.invokeTest", resource = self.type_name));
+        self.check_trust()?;
+        check_resource_policy(&self.type_name, PolicyOperation::Test, None)?;
         if let Some(deprecation_message) = self.deprecation_message.as_ref() {
             warn!("{}", t!("dscresources.dscresource.deprecationMessage", resource = self.type_name, message = deprecation_message));
         }
@@ -475,13 +574,14 @@ impl Invoke for DscResource {
                     } else {
                         self.schema().ok().and_then(|s| serde_json::from_str(&s).ok())
                     };
-                    let diff_properties = get_diff_with_schema( &desired_state, &actual_state, schema.as_ref());
+                    let (diff_properties, reasons) = get_diff_with_reasons(&desired_state, &actual_state, schema.as_ref());
                     desired_state = redact(&desired_state);
                     let test_result = TestResult::Resource(ResourceTestResponse {
                         desired_state,
                         actual_state,
                         in_desired_state: diff_properties.is_empty(),
                         diff_properties,
+                        reasons,
                     });
                     Ok(test_result)
                 }
@@ -497,6 +597,8 @@ impl Invoke for DscResource {
 
     fn delete(&self, filter: &str, execution_type: &ExecutionKind) -> Result<DeleteResultKind, DscError> {
         debug!("{}", t!("dscresources.dscresource.invokeDelete", resource = self.type_name));
+        self.check_trust()?;
+        check_resource_policy(&self.type_name, PolicyOperation::Delete, Some(execution_type))?;
         if let Some(deprecation_message) = self.deprecation_message.as_ref() {
             warn!("{}", t!("dscresources.dscresource.deprecationMessage", resource = self.type_name, message = deprecation_message));
         }
@@ -516,6 +618,7 @@ impl Invoke for DscResource {
 
     fn validate(&self, config: &str) -> Result<ValidateResult, DscError> {
         debug!("{}", t!("dscresources.dscresource.invokeValidate", resource = self.type_name));
+        self.check_trust()?;
         if let Some(deprecation_message) = self.deprecation_message.as_ref() {
             warn!("{}", t!("dscresources.dscresource.deprecationMessage", resource = self.type_name, message = deprecation_message));
         }
@@ -541,6 +644,7 @@ impl Invoke for DscResource {
         }
 
         debug!("{}", t!("dscresources.dscresource.invokeSchema", resource = self.type_name));
+        self.check_trust()?;
         if let Some(deprecation_message) = self.deprecation_message.as_ref() {
             warn!("{}", t!("dscresources.dscresource.deprecationMessage", resource = self.type_name, message = deprecation_message));
         }
@@ -563,6 +667,8 @@ impl Invoke for DscResource {
 
     fn export(&self, input: &str) -> Result<ExportResult, DscError> {
         debug!("{}", t!("dscresources.dscresource.invokeExport", resource = self.type_name));
+        self.check_trust()?;
+        check_resource_policy(&self.type_name, PolicyOperation::Export, None)?;
         if let Some(deprecation_message) = self.deprecation_message.as_ref() {
             warn!("{}", t!("dscresources.dscresource.deprecationMessage", resource = self.type_name, message = deprecation_message));
         }
@@ -573,8 +679,28 @@ impl Invoke for DscResource {
         command_resource::invoke_export(self, Some(input), self.target_resource.as_deref())
     }
 
+    fn export_stream(&self, input: &str, on_instance: &mut dyn FnMut(Value) -> Result<(), DscError>) -> Result<(), DscError> {
+        debug!("{}", t!("dscresources.dscresource.invokeExport", resource = self.type_name));
+        self.check_trust()?;
+        check_resource_policy(&self.type_name, PolicyOperation::Export, None)?;
+        if let Some(deprecation_message) = self.deprecation_message.as_ref() {
+            warn!("{}", t!("dscresources.dscresource.deprecationMessage", resource = self.type_name, message = deprecation_message));
+        }
+        if let Some(adapter) = &self.require_adapter {
+            let export_result = self.invoke_export_with_adapter(adapter, self, input)?;
+            for instance in export_result.actual_state {
+                on_instance(instance)?;
+            }
+            return Ok(());
+        }
+
+        command_resource::invoke_export_stream(self, Some(input), self.target_resource.as_deref(), on_instance)
+    }
+
     fn resolve(&self, input: &str) -> Result<ResolveResult, DscError> {
         debug!("{}", t!("dscresources.dscresource.invokeResolve", resource = self.type_name));
+        self.check_trust()?;
+        check_resource_policy(&self.type_name, PolicyOperation::Resolve, None)?;
         if let Some(deprecation_message) = self.deprecation_message.as_ref() {
             warn!("{}", t!("dscresources.dscresource.deprecationMessage", resource = self.type_name, message = deprecation_message));
         }
@@ -584,6 +710,45 @@ impl Invoke for DscResource {
 
         command_resource::invoke_resolve(self, input)
     }
+
+    fn get_batch(&self, filters: &[String]) -> Result<Vec<GetResult>, DscError> {
+        if !self.supports_batch() {
+            return filters.iter().map(|filter| self.get(filter)).collect();
+        }
+        debug!("{}", t!("dscresources.dscresource.invokeGetBatch", resource = self.type_name, count = filters.len()));
+        self.check_trust()?;
+        check_resource_policy(&self.type_name, PolicyOperation::Get, None)?;
+        if let Some(deprecation_message) = self.deprecation_message.as_ref() {
+            warn!("{}", t!("dscresources.dscresource.deprecationMessage", resource = self.type_name, message = deprecation_message));
+        }
+        command_resource::invoke_get_batch(self, filters)
+    }
+
+    fn test_batch(&self, expected: &[String]) -> Result<Vec<TestResult>, DscError> {
+        if !self.supports_batch() {
+            return expected.iter().map(|expected| self.test(expected)).collect();
+        }
+        debug!("{}", t!("dscresources.dscresource.invokeTestBatch", resource = self.type_name, count = expected.len()));
+        self.check_trust()?;
+        check_resource_policy(&self.type_name, PolicyOperation::Test, None)?;
+        if let Some(deprecation_message) = self.deprecation_message.as_ref() {
+            warn!("{}", t!("dscresources.dscresource.deprecationMessage", resource = self.type_name, message = deprecation_message));
+        }
+        command_resource::invoke_test_batch(self, expected)
+    }
+
+    fn set_batch(&self, desired: &[String], skip_test: bool, execution_type: &ExecutionKind) -> Result<Vec<SetResult>, DscError> {
+        if !self.supports_batch() || execution_type == &ExecutionKind::WhatIf {
+            return desired.iter().map(|desired| self.set(desired, skip_test, execution_type)).collect();
+        }
+        debug!("{}", t!("dscresources.dscresource.invokeSetBatch", resource = self.type_name, count = desired.len()));
+        self.check_trust()?;
+        check_resource_policy(&self.type_name, PolicyOperation::Set, Some(execution_type))?;
+        if let Some(deprecation_message) = self.deprecation_message.as_ref() {
+            warn!("{}", t!("dscresources.dscresource.deprecationMessage", resource = self.type_name, message = deprecation_message));
+        }
+        command_resource::invoke_set_batch(self, desired, skip_test)
+    }
 }
 
 #[must_use]
@@ -623,6 +788,22 @@ pub fn redact(value: &Value) -> Value {
     value.clone()
 }
 
+/// Redact secure values from a string if it contains a JSON object or array so it can be logged
+///
+/// # Arguments
+///
+/// * `text` - The string to redact
+///
+/// # Returns
+///
+/// The string with secure values redacted, or the original string if it isn't JSON
+pub fn redact_json_string(text: &str) -> String {
+    match serde_json::from_str::<Value>(text) {
+        Ok(value) if value.is_object() || value.is_array() => serde_json::to_string(&redact(&value)).unwrap_or_else(|_| text.to_string()),
+        _ => text.to_string(),
+    }
+}
+
 /// Gets the input kind for an adapter resource
 ///
 /// # Arguments
@@ -671,6 +852,107 @@ pub fn get_diff(expected: &Value, actual: &Value) -> Vec<String> {
 ///
 /// An array of top level properties that differ, if any
 pub(crate) fn get_diff_with_schema(expected: &Value, actual: &Value, schema: Option<&Value>) -> Vec<String> {
+    get_diff_with_reasons(expected, actual, schema).0
+}
+
+#[must_use]
+/// Performs the same comparison as `get_diff_with_schema` and also explains each difference.
+///
+/// # Arguments
+///
+/// * `expected` - The expected value
+/// * `actual` - The actual value
+/// * `schema` - Optional JSON Schema to identify write-only properties and default values
+///
+/// # Returns
+///
+/// The top level properties that differ and a reason for every differing property, identified
+/// by its JSON Pointer
+pub(crate) fn get_diff_with_reasons(expected: &Value, actual: &Value, schema: Option<&Value>) -> (Vec<String>, Vec<Reason>) {
+    let mut reasons = Vec::<Reason>::new();
+    let diff_properties = diff_with_schema(expected, actual, schema, schema, "", &mut reasons);
+    (diff_properties, reasons)
+}
+
+#[must_use]
+/// Normalizes an instance against the JSON Schema of its resource. Properties missing from an
+/// object are added with the schema's `default` value and strings matching an `enum` entry
+/// without regard to case are replaced with the casing declared by the schema.
+///
+/// # Arguments
+///
+/// * `value` - The instance to normalize
+/// * `schema` - The JSON Schema of the resource
+///
+/// # Returns
+///
+/// The normalized instance
+pub(crate) fn normalize_with_schema(value: &Value, schema: &Value) -> Value {
+    normalize_value(value, schema, schema)
+}
+
+fn normalize_value(value: &Value, schema: &Value, root_schema: &Value) -> Value {
+    let schema = resolve_local_ref(schema, root_schema);
+    match value {
+        Value::Object(map) => {
+            let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+                return value.clone();
+            };
+            let mut normalized = Map::new();
+            for (key, property_value) in map {
+                let property_value = match properties.get(key) {
+                    Some(property_schema) => normalize_value(property_value, property_schema, root_schema),
+                    None => property_value.clone(),
+                };
+                normalized.insert(key.clone(), property_value);
+            }
+            for (key, property_schema) in properties {
+                if normalized.contains_key(key) {
+                    continue;
+                }
+                if let Some(default) = resolve_local_ref(property_schema, root_schema).get("default") {
+                    normalized.insert(key.clone(), default.clone());
+                }
+            }
+            Value::Object(normalized)
+        },
+        Value::Array(array) => {
+            let Some(items_schema) = schema.get("items").filter(|items| items.is_object()) else {
+                return value.clone();
+            };
+            Value::Array(array.iter().map(|item| normalize_value(item, items_schema, root_schema)).collect())
+        },
+        Value::String(string) => {
+            schema.get("enum")
+                .and_then(Value::as_array)
+                .and_then(|values| values.iter().filter_map(Value::as_str).find(|entry| entry.eq_ignore_ascii_case(string)))
+                .map_or_else(|| value.clone(), |entry| Value::String(entry.to_string()))
+        },
+        _ => value.clone(),
+    }
+}
+
+/// Follows local JSON Pointer references from the root schema, stopping at a reference that
+/// is external, missing, or cyclic.
+fn resolve_local_ref<'a>(schema: &'a Value, root_schema: &'a Value) -> &'a Value {
+    let mut resolved_schema = schema;
+    let mut visited_references = HashSet::<String>::new();
+    while let Some(reference) = resolved_schema.get("$ref").and_then(Value::as_str) {
+        let Some(pointer) = reference.strip_prefix('#') else {
+            break;
+        };
+        if !visited_references.insert(pointer.to_string()) {
+            break;
+        }
+        let Some(target_schema) = root_schema.pointer(pointer) else {
+            break;
+        };
+        resolved_schema = target_schema;
+    }
+    resolved_schema
+}
+
+fn diff_with_schema(expected: &Value, actual: &Value, schema: Option<&Value>, root_schema: Option<&Value>, pointer: &str, reasons: &mut Vec<Reason>) -> Vec<String> {
     let mut diff_properties: Vec<String> = Vec::new();
     if expected.is_null() {
         return diff_properties;
@@ -692,7 +974,7 @@ pub(crate) fn get_diff_with_schema(expected: &Value, actual: &Value, schema: Opt
         }
 
         for (key, value) in &*map {
-            if is_schema_write_only(schema, key) {
+            if is_schema_write_only(schema, root_schema, key) {
                 continue;
             }
 
@@ -701,31 +983,17 @@ pub(crate) fn get_diff_with_schema(expected: &Value, actual: &Value, schema: Opt
                 continue;
             }
 
+            let property_schema = get_property_schema(schema, root_schema, key);
+            let compare_kind = property_schema.and_then(CompareKind::from_schema);
+            if compare_kind == Some(CompareKind::Ignore) {
+                // the schema declares the property isn't compared
+                continue;
+            }
+
             if value.is_object() {
                 // When comparing nested objects, pass the corresponding nested schema so that
                 // nested `writeOnly` properties and nested defaults are handled correctly.
-                let sub_schema = schema.and_then(|schema| {
-                    let mut property_schema = schema
-                        .get("properties")
-                        .and_then(Value::as_object)
-                        .and_then(|properties| properties.get(key))?;
-                    // Resolve local refs so nested comparisons can still see `properties`, `default`, and `writeOnly`.
-                    let mut visited_references = HashSet::<String>::new();
-                    while let Some(reference) = property_schema.get("$ref").and_then(Value::as_str) {
-                        let Some(pointer) = reference.strip_prefix('#') else {
-                            break;
-                        };
-                        if !visited_references.insert(pointer.to_string()) {
-                            break;
-                        }
-                        let Some(resolved_schema) = schema.pointer(pointer) else {
-                            break;
-                        };
-                        property_schema = resolved_schema;
-                    }
-                    Some(property_schema)
-                });
-                let sub_diff = get_diff_with_schema(value, &actual[key], sub_schema);
+                let sub_diff = diff_with_schema(value, &actual[key], property_schema, root_schema, &property_pointer(pointer, key), reasons);
                 if !sub_diff.is_empty() {
                     debug!("{}", t!("dscresources.dscresource.subDiff", key = key));
                     diff_properties.push(key.to_string());
@@ -739,34 +1007,28 @@ pub(crate) fn get_diff_with_schema(expected: &Value, actual: &Value, schema: Opt
 
                 if let Some(actual_object) = actual.as_object() {
                     if actual_object.contains_key(key) {
-                        if let Some(value_array) = value.as_array() {
-                            if let Some(actual_array) = actual[key].as_array() {
-                                if !is_same_array(value_array, actual_array) {
-                                    info!("{}", t!("dscresources.dscresource.diffArray", key = key));
-                                    diff_properties.push(key.to_string());
-                                }
-                            } else {
-                                info!("{}", t!("dscresources.dscresource.diffNotArray", key = actual[key]));
-                                diff_properties.push(key.to_string());
-                            }
-                        } else if value != &actual[key] {
+                        if !is_same_value(key, value, &actual[key], compare_kind) {
                             diff_properties.push(key.to_string());
+                            reasons.push(get_reason(pointer, key, value, Some(&actual[key])));
                         }
                     } else {
                         // Property not in actual - check schema for a default value
-                        if let Some(default_value) = get_schema_default(schema, key) {
-                            if value != &default_value {
+                        if let Some(default_value) = get_schema_default(schema, root_schema, key) {
+                            if !is_same_value(key, value, &default_value, compare_kind) {
                                 info!("{}", t!("dscresources.dscresource.diffKeyMissing", key = key));
                                 diff_properties.push(key.to_string());
+                                reasons.push(get_reason(pointer, key, value, None));
                             }
                         } else {
                             info!("{}", t!("dscresources.dscresource.diffKeyMissing", key = key));
                             diff_properties.push(key.to_string());
+                            reasons.push(get_reason(pointer, key, value, None));
                         }
                     }
                 } else {
                     info!("{}", t!("dscresources.dscresource.diffKeyNotObject", key = key));
                     diff_properties.push(key.to_string());
+                    reasons.push(get_reason(pointer, key, value, None));
                 }
             }
         }
@@ -775,57 +1037,116 @@ pub(crate) fn get_diff_with_schema(expected: &Value, actual: &Value, schema: Opt
     diff_properties
 }
 
+/// Looks up the subschema for a property, resolving local references from the root schema so
+/// that nested comparisons can still see `properties`, `default`, `writeOnly`, and
+/// `x-dsc-compare`.
+fn get_property_schema<'a>(schema: Option<&'a Value>, root_schema: Option<&'a Value>, property_name: &str) -> Option<&'a Value> {
+    let schema = schema?;
+    let property_schema = schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .and_then(|properties| properties.get(property_name))?;
+    Some(resolve_local_ref(property_schema, root_schema.unwrap_or(schema)))
+}
+
+/// Compares a property value using the comparison its schema declares with the `x-dsc-compare`
+/// keyword. Without a declared comparison, arrays are compared independent of order and other
+/// values must be equal.
+fn is_same_value(key: &str, expected: &Value, actual: &Value, compare_kind: Option<CompareKind>) -> bool {
+    if compare_kind == Some(CompareKind::CaseInsensitive) {
+        return is_same_value(key, &to_lowercase(expected), &to_lowercase(actual), None);
+    }
+
+    let Some(expected_array) = expected.as_array() else {
+        return expected == actual;
+    };
+    let Some(actual_array) = actual.as_array() else {
+        info!("{}", t!("dscresources.dscresource.diffNotArray", key = key));
+        return false;
+    };
+    let same = match compare_kind {
+        Some(CompareKind::Set) => is_subset_array(expected_array, actual_array) && is_subset_array(actual_array, expected_array),
+        Some(CompareKind::Subset) => is_subset_array(expected_array, actual_array),
+        _ => is_same_array(expected_array, actual_array),
+    };
+    if !same {
+        info!("{}", t!("dscresources.dscresource.diffArray", key = key));
+    }
+    same
+}
+
+/// Lowercases a string value or the string items of an array value.
+fn to_lowercase(value: &Value) -> Value {
+    match value {
+        Value::String(string) => Value::String(string.to_lowercase()),
+        Value::Array(items) => Value::Array(items.iter().map(to_lowercase).collect()),
+        _ => value.clone(),
+    }
+}
+
+/// Builds the JSON Pointer for a property of the object at `pointer`.
+fn property_pointer(pointer: &str, key: &str) -> String {
+    format!("{pointer}/{}", key.replace('~', "~0").replace('/', "~1"))
+}
+
+/// Explains why a property differs from its expected value.
+///
+/// # Arguments
+///
+/// * `pointer` - The JSON Pointer of the object containing the property
+/// * `key` - The name of the property
+/// * `expected` - The expected value of the property
+/// * `actual` - The actual value of the property, or `None` if the property is missing
+fn get_reason(pointer: &str, key: &str, expected: &Value, actual: Option<&Value>) -> Reason {
+    let (code, phrase) = match actual {
+        Some(actual) => ("valueMismatch", t!("dscresources.dscresource.reasonValueMismatch", expected = format_reason_value(expected), actual = format_reason_value(actual))),
+        None => ("propertyMissing", t!("dscresources.dscresource.reasonPropertyMissing", expected = format_reason_value(expected))),
+    };
+    Reason {
+        code: code.to_string(),
+        phrase: phrase.to_string(),
+        property: Some(property_pointer(pointer, key)),
+    }
+}
+
+fn format_reason_value(value: &Value) -> String {
+    match value {
+        Value::String(string) => format!("'{string}'"),
+        _ => value.to_string(),
+    }
+}
+
 /// Looks up the default value for a property from a JSON Schema.
 ///
 /// # Arguments
 ///
 /// * `schema` - Optional JSON Schema value
+/// * `root_schema` - Optional root JSON Schema to resolve local references from
 /// * `property_name` - The property name to look up
 ///
 /// # Returns
 ///
 /// The default value if found in the schema's properties definition, otherwise None
-fn get_schema_default(schema: Option<&Value>, property_name: &str) -> Option<Value> {
-    let schema = schema?;
-    let properties = schema.get("properties")?.as_object()?;
-    let property_schema = properties.get(property_name)?.as_object()?;
-    property_schema.get("default").cloned()
+fn get_schema_default(schema: Option<&Value>, root_schema: Option<&Value>, property_name: &str) -> Option<Value> {
+    let property_schema = schema?.get("properties")?.as_object()?.get(property_name)?;
+    property_schema.get("default")
+        .or_else(|| get_property_schema(schema, root_schema, property_name)?.get("default"))
+        .cloned()
 }
 
 /// Returns whether a property's JSON Schema sets `writeOnly` to `true`, directly or
-/// through a local JSON Pointer reference.
-fn is_schema_write_only(schema: Option<&Value>, property_name: &str) -> bool {
-    let Some(schema) = schema else {
-        return false;
-    };
-    let Some(mut property_schema) = schema
-        .get("properties")
+/// through a local JSON Pointer reference resolved from the root schema.
+fn is_schema_write_only(schema: Option<&Value>, root_schema: Option<&Value>, property_name: &str) -> bool {
+    let Some(property_schema) = schema
+        .and_then(|schema| schema.get("properties"))
         .and_then(Value::as_object)
         .and_then(|properties| properties.get(property_name))
     else {
         return false;
     };
-    let mut visited_references = HashSet::new();
-
-    loop {
-        if property_schema.get("writeOnly").and_then(Value::as_bool) == Some(true) {
-            return true;
-        }
-
-        let Some(reference) = property_schema.get("$ref").and_then(Value::as_str) else {
-            return false;
-        };
-        let Some(pointer) = reference.strip_prefix('#') else {
-            return false;
-        };
-        if !visited_references.insert(pointer) {
-            return false;
-        }
-        let Some(resolved_schema) = schema.pointer(pointer) else {
-            return false;
-        };
-        property_schema = resolved_schema;
-    }
+    let is_write_only = |schema: &Value| schema.get("writeOnly").and_then(Value::as_bool) == Some(true);
+    is_write_only(property_schema)
+        || get_property_schema(schema, root_schema, property_name).is_some_and(is_write_only)
 }
 
 /// Validates the properties of a resource against its schema.
@@ -891,7 +1212,7 @@ pub fn validate_json(source: &str, schema: &Value, json: &Value) -> Result<(), D
     debug!("{}: {source}", t!("dscresources.dscresource.validatingSchema"));
     trace!("JSON: {json}");
     trace!("Schema: {schema}");
-    let compiled_schema = match Validator::new(schema) {
+    let compiled_schema = match jsonschema::options().with_dsc_keywords().build(schema) {
         Ok(compiled_schema) => compiled_schema,
         Err(err) => {
             return Err(DscError::Validation(format!("{}: {err}", t!("dscresources.dscresource.failedToCompileSchema"))));
@@ -922,6 +1243,18 @@ fn is_same_array(expected: &Vec<Value>, actual: &Vec<Value>) -> bool {
     true
 }
 
+/// Checks that every item of `expected` is in `actual`, independent of order and duplicates
+fn is_subset_array(expected: &Vec<Value>, actual: &Vec<Value>) -> bool {
+    for item in expected {
+        if !array_contains(actual, item) {
+            info!("{}", t!("dscresources.dscresource.diffMissingItem"));
+            return false;
+        }
+    }
+
+    true
+}
+
 fn array_contains(array: &Vec<Value>, find: &Value) -> bool {
     for item in array {
         if find.is_boolean() && item.is_boolean() && find.as_bool().unwrap() == item.as_bool().unwrap() {
@@ -1138,6 +1471,54 @@ fn diff_with_schema_write_only_local_ref_ignores_missing_property() {
     assert!(diff.is_empty(), "Expected referenced write-only property to be ignored, got: {diff:?}");
 }
 
+#[test]
+fn diff_with_schema_nested_ref_resolves_from_root_schema() {
+    use serde_json::json;
+    let expected = json!({"nested": {"state": "Running"}});
+    let actual = json!({"nested": {"state": "running"}});
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "nested": { "$ref": "#/$defs/nested" }
+        },
+        "$defs": {
+            "nested": {
+                "type": "object",
+                "properties": {
+                    "state": { "$ref": "#/$defs/state" }
+                }
+            },
+            "state": { "type": "string", "x-dsc-compare": "caseInsensitive" }
+        }
+    });
+    let diff = get_diff_with_schema(&expected, &actual, Some(&schema));
+    assert!(diff.is_empty(), "Expected nested reference to resolve from the root schema, got: {diff:?}");
+}
+
+#[test]
+fn diff_with_schema_nested_write_only_ref_resolves_from_root_schema() {
+    use serde_json::json;
+    let expected = json!({"nested": {"password": "secret", "name": "test"}});
+    let actual = json!({"nested": {"name": "test"}});
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "nested": {
+                "type": "object",
+                "properties": {
+                    "name": { "type": "string" },
+                    "password": { "$ref": "#/$defs/password" }
+                }
+            }
+        },
+        "$defs": {
+            "password": { "type": "string", "writeOnly": true }
+        }
+    });
+    let diff = get_diff_with_schema(&expected, &actual, Some(&schema));
+    assert!(diff.is_empty(), "Expected nested referenced write-only property to be ignored, got: {diff:?}");
+}
+
 #[test]
 fn diff_with_schema_nested_external_ref_falls_back_to_normal_comparison() {
     use serde_json::json;
@@ -1227,3 +1608,115 @@ fn diff_with_schema_default_integer() {
     let diff = get_diff_with_schema(&expected, &actual, Some(&schema));
     assert!(diff.is_empty(), "Expected no diff when expected matches schema default integer, got: {diff:?}");
 }
+
+#[test]
+fn diff_reasons_use_nested_pointers() {
+    use serde_json::json;
+    let expected = json!({"service": {"state": "Running", "a/b": 1}, "version": "1.3"});
+    let actual = json!({"service": {"state": "Stopped"}, "version": "1.3"});
+    let (diff, reasons) = get_diff_with_reasons(&expected, &actual, None);
+    assert_eq!(diff, vec!["service".to_string()]);
+    assert_eq!(reasons.len(), 2);
+    let state = reasons.iter().find(|reason| reason.property.as_deref() == Some("/service/state")).unwrap();
+    assert_eq!(state.code, "valueMismatch");
+    assert_eq!(state.phrase, "Expected 'Running', actual 'Stopped'");
+    let missing = reasons.iter().find(|reason| reason.property.as_deref() == Some("/service/a~1b")).unwrap();
+    assert_eq!(missing.code, "propertyMissing");
+}
+
+#[test]
+fn diff_reasons_empty_when_in_desired_state() {
+    use serde_json::json;
+    let expected = json!({"name": "test", "count": 5});
+    let actual = json!({"name": "test"});
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "count": { "type": "integer", "default": 5 }
+        }
+    });
+    let (diff, reasons) = get_diff_with_reasons(&expected, &actual, Some(&schema));
+    assert!(diff.is_empty());
+    assert!(reasons.is_empty());
+}
+
+#[test]
+fn diff_with_schema_compare_keyword() {
+    use serde_json::json;
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "tags": { "type": "array", "x-dsc-compare": "set" },
+            "state": { "type": "string", "x-dsc-compare": "caseInsensitive" },
+            "features": { "type": "array", "x-dsc-compare": "subset" },
+            "lastModified": { "type": "string", "x-dsc-compare": "ignore" }
+        }
+    });
+    let actual = json!({"tags": ["b", "a", "a"], "state": "Running", "features": ["x", "y", "z"], "lastModified": "today"});
+    let expected = json!({"tags": ["a", "b"], "state": "RUNNING", "features": ["z", "x"], "lastModified": "yesterday"});
+    let diff = get_diff_with_schema(&expected, &actual, Some(&schema));
+    assert!(diff.is_empty(), "Expected no diff with compare keywords, got: {diff:?}");
+
+    let expected = json!({"tags": ["a", "c"], "state": "Stopped", "features": ["w"]});
+    let mut diff = get_diff_with_schema(&expected, &actual, Some(&schema));
+    diff.sort();
+    assert_eq!(diff, vec!["features".to_string(), "state".to_string(), "tags".to_string()]);
+}
+
+#[test]
+fn diff_without_compare_keyword_is_case_sensitive_and_counts_duplicates() {
+    use serde_json::json;
+    let actual = json!({"tags": ["b", "a", "a"], "state": "Running"});
+    let expected = json!({"tags": ["a", "b"], "state": "running"});
+    let mut diff = get_diff_with_schema(&expected, &actual, None);
+    diff.sort();
+    assert_eq!(diff, vec!["state".to_string(), "tags".to_string()]);
+}
+
+#[test]
+fn normalize_with_schema_applies_defaults_and_enum_casing() {
+    use serde_json::json;
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "name": { "type": "string" },
+            "state": { "$ref": "#/$defs/state" },
+            "startupType": { "type": "string", "enum": ["Automatic", "Manual", "Disabled"], "default": "Manual" },
+            "settings": {
+                "type": "object",
+                "properties": {
+                    "mode": { "type": "string", "enum": ["ReadOnly", "ReadWrite"] },
+                    "retries": { "type": "integer", "default": 3 }
+                }
+            },
+            "ports": { "type": "array", "items": { "type": "string", "enum": ["TCP", "UDP"] } }
+        },
+        "$defs": {
+            "state": { "type": "string", "enum": ["Running", "Stopped"], "default": "Running" }
+        }
+    });
+    let instance = json!({"name": "spooler", "settings": {"mode": "readwrite"}, "ports": ["tcp", "Udp", "other"]});
+    let normalized = normalize_with_schema(&instance, &schema);
+    assert_eq!(normalized, json!({
+        "name": "spooler",
+        "state": "Running",
+        "startupType": "Manual",
+        "settings": {"mode": "ReadWrite", "retries": 3},
+        "ports": ["TCP", "UDP", "other"]
+    }));
+}
+
+#[test]
+fn normalize_with_schema_keeps_specified_values() {
+    use serde_json::json;
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "state": { "type": "string", "enum": ["Running", "Stopped"], "default": "Running" },
+            "extra": { "$ref": "https://example.com/schema.json" }
+        }
+    });
+    let instance = json!({"state": "STOPPED", "extra": "value", "unknown": "running"});
+    let normalized = normalize_with_schema(&instance, &schema);
+    assert_eq!(normalized, json!({"state": "Stopped", "extra": "value", "unknown": "running"}));
+}
@@ -5,6 +5,7 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
 use crate::configure::config_result::{ResourceGetResult, ResourceSetResult, ResourceTestResult};
 use crate::schemas::dsc_repo::DscRepoSchema;
 
@@ -152,6 +153,23 @@ pub struct ResourceTestResponse {
     /// The properties that were different from the expected state.
     #[serde(rename = "differingProperties")]
     pub diff_properties: Vec<String>,
+    /// The explanations of why the resource wasn't in the desired state.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reasons: Vec<Reason>,
+}
+
+/// An explanation of why a property of a resource instance isn't in the desired state, returned by
+/// resources in the `_reasons` property of the actual state.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Reason {
+    /// A machine readable code identifying the reason.
+    pub code: String,
+    /// A human readable explanation of the reason.
+    pub phrase: String,
+    /// The JSON Pointer to the property the reason applies to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub property: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, DscRepoSchema)]
@@ -208,3 +226,50 @@ pub enum DeleteResultKind {
     /// Actual delete from resource has no output
     ResourceActual
 }
+
+/// Structured error a resource writes to stderr as `{"errorData": {"error": {...}}}`, following the
+/// shape of the Bicep `ErrorData` message.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct ResourceErrorData {
+    pub error: ResourceError,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct ResourceError {
+    /// A machine readable code identifying the error.
+    pub code: String,
+    /// The property of the instance the error applies to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    /// A human readable description of the error.
+    pub message: String,
+    /// Whether the operation may succeed if invoked again.
+    #[serde(default)]
+    pub retryable: bool,
+    /// Additional errors that contributed to this error.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<ResourceErrorDetail>,
+    /// The underlying error reported by the resource, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inner_error: Option<String>,
+}
+
+impl Display for ResourceError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.code, self.message)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct ResourceErrorDetail {
+    /// A machine readable code identifying the error.
+    pub code: String,
+    /// The property of the instance the error applies to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    /// A human readable description of the error.
+    pub message: String,
+}
@@ -6,5 +6,7 @@ pub mod command_resource;
 pub mod dscresource;
 pub mod invoke_result;
 pub mod resource_manifest;
+pub mod resource_policy;
+pub mod resource_server;
 
 use super::dscerror;
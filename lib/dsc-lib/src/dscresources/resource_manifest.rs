@@ -6,6 +6,7 @@ use schemars::JsonSchema;
 use semver::Version;
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
+use std::collections::HashMap;
 
 use crate::{
     configure::config_doc::SecurityContextKind,
@@ -88,6 +89,17 @@ pub struct ResourceManifest {
     /// Indicates the resource is a adapter of other resources.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub adapter: Option<Adapter>,
+    /// Details how to start a long-lived process that serves the operations of the resource.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server: Option<ServerMethod>,
+    /// Indicates the get, set, and test operations accept a JSON array of instances in one
+    /// invocation and write a JSON line with the state of each instance, in the same order.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub batch: Option<bool>,
+    /// Indicates DSC applies the schema defaults and enum casing to the desired state and
+    /// validates it against the resource schema before invoking the set and test operations.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub normalize: Option<bool>,
     /// Mapping of exit codes to descriptions.  Zero is always success and non-zero is always failure.
     #[serde(skip_serializing_if = "ExitCodesMap::is_empty_or_default", default)]
     pub exit_codes: ExitCodesMap,
@@ -208,8 +220,15 @@ pub enum InputKind {
     /// The input is accepted as a JSON object via STDIN.
     #[serde(rename = "stdin")]
     Stdin,
+    /// The input is written as a JSON object to a temporary file, and the path to the file replaces
+    /// the `{inputFile}` placeholder in the arguments.
+    #[serde(rename = "file")]
+    File,
 }
 
+/// The placeholder in the arguments replaced with the path to the input file.
+pub const INPUT_FILE_PLACEHOLDER: &str = "{inputFile}";
+
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, DscRepoSchema)]
 #[dsc_repo_schema(base_name = "manifest.schema", folder_path = "resource")]
 pub enum SchemaKind {
@@ -235,10 +254,49 @@ pub enum ExportSchemaKind {
 pub struct SchemaCommand {
     /// The command to run to get the schema.
     pub executable: String,
+    /// The SHA-256 digest of the executable, or a list of allowed digests, checked before it's run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<ExecutableDigest>,
     /// The arguments to pass to the command.
     pub args: Option<Vec<SchemaArgKind>>,
 }
 
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandEnvironment {
+    /// Environment variables to set for the command.  Values can be expressions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub env: Option<HashMap<String, String>>,
+    /// The working directory for the command.  Default is the directory of the manifest.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<WorkingDirectory>,
+    /// The names of the environment variables inherited by the command.  When specified, all other
+    /// environment variables are removed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub env_passthrough: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkingDirectory {
+    /// The path of the working directory.
+    pub path: String,
+    /// The directory a relative path is resolved from.  Default is `manifest`.
+    #[serde(default)]
+    pub relative_to: WorkingDirectoryBase,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[schemars(transform = idiomaticize_string_enum)]
+pub enum WorkingDirectoryBase {
+    /// The path is relative to the directory of the manifest.
+    #[default]
+    Manifest,
+    /// The path is relative to the directory of the configuration document.
+    ConfigRoot,
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, DscRepoSchema)]
 #[schemars(transform = idiomaticize_string_enum)]
 #[dsc_repo_schema(base_name = "returnKind", folder_path = "definitions")]
@@ -251,11 +309,40 @@ pub enum ReturnKind {
     StateAndDiff,
 }
 
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[serde(untagged)]
+pub enum ExecutableDigest {
+    /// The hexadecimal SHA-256 digest of the executable.
+    Single(String),
+    /// The hexadecimal SHA-256 digests allowed for the executable, like one per platform.
+    Multiple(Vec<String>),
+}
+
+impl ExecutableDigest {
+    /// Get the allowed digests.
+    #[must_use]
+    pub fn digests(&self) -> Vec<&str> {
+        match self {
+            ExecutableDigest::Single(digest) => vec![digest.as_str()],
+            ExecutableDigest::Multiple(digests) => digests.iter().map(String::as_str).collect(),
+        }
+    }
+
+    /// Check if the digest matches one of the allowed digests, ignoring case.
+    #[must_use]
+    pub fn matches(&self, digest: &str) -> bool {
+        self.digests().iter().any(|allowed| allowed.eq_ignore_ascii_case(digest))
+    }
+}
+
 #[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize, JsonSchema, DscRepoSchema)]
 #[dsc_repo_schema(base_name = "manifest.get", folder_path = "resource")]
 pub struct GetMethod {
     /// The command to run to get the state of the resource.
     pub executable: String,
+    /// The SHA-256 digest of the executable, or a list of allowed digests, checked before it's run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<ExecutableDigest>,
     /// The arguments to pass to the command to perform a Get.
     pub args: Option<Vec<GetArgKind>>,
     /// How to pass optional input for a Get.
@@ -264,6 +351,9 @@ pub struct GetMethod {
     /// The security context required to run the Get method.  Default if not specified is `current`.
     #[serde(rename = "requireSecurityContext", skip_serializing_if = "Option::is_none")]
     pub require_security_context: Option<SecurityContextKind>,
+    /// The environment and working directory for the command.
+    #[serde(flatten)]
+    pub environment: CommandEnvironment,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, DscRepoSchema)]
@@ -271,6 +361,9 @@ pub struct GetMethod {
 pub struct SetMethod {
     /// The command to run to set the state of the resource.
     pub executable: String,
+    /// The SHA-256 digest of the executable, or a list of allowed digests, checked before it's run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<ExecutableDigest>,
     /// The arguments to pass to the command to perform a Set.
     pub args: Option<Vec<SetDeleteArgKind>>,
     /// How to pass required input for a Set.
@@ -290,6 +383,9 @@ pub struct SetMethod {
     /// The type of return value expected from the Set method when running in what-if mode. When specified, this overrides the `return` property during what-if execution.
     #[serde(rename = "whatIfReturns", skip_serializing_if = "Option::is_none")]
     pub what_if_returns: Option<ReturnKind>,
+    /// The environment and working directory for the command.
+    #[serde(flatten)]
+    pub environment: CommandEnvironment,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, DscRepoSchema)]
@@ -297,6 +393,9 @@ pub struct SetMethod {
 pub struct TestMethod {
     /// The command to run to test the state of the resource.
     pub executable: String,
+    /// The SHA-256 digest of the executable, or a list of allowed digests, checked before it's run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<ExecutableDigest>,
     /// The arguments to pass to the command to perform a Test.
     pub args: Option<Vec<GetArgKind>>,
     /// How to pass required input for a Test.
@@ -307,6 +406,9 @@ pub struct TestMethod {
     /// The security context required to run the Test method.  Default if not specified is `current`.
     #[serde(rename = "requireSecurityContext", skip_serializing_if = "Option::is_none")]
     pub require_security_context: Option<SecurityContextKind>,
+    /// The environment and working directory for the command.
+    #[serde(flatten)]
+    pub environment: CommandEnvironment,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, DscRepoSchema)]
@@ -314,6 +416,9 @@ pub struct TestMethod {
 pub struct DeleteMethod {
     /// The command to run to delete the state of the resource.
     pub executable: String,
+    /// The SHA-256 digest of the executable, or a list of allowed digests, checked before it's run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<ExecutableDigest>,
     /// The arguments to pass to the command to perform a Delete.
     pub args: Option<Vec<SetDeleteArgKind>>,
     /// How to pass required input for a Delete.
@@ -321,6 +426,9 @@ pub struct DeleteMethod {
     /// The security context required to run the Delete method.  Default if not specified is `current`.
     #[serde(rename = "requireSecurityContext", skip_serializing_if = "Option::is_none")]
     pub require_security_context: Option<SecurityContextKind>,
+    /// The environment and working directory for the command.
+    #[serde(flatten)]
+    pub environment: CommandEnvironment,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, DscRepoSchema)]
@@ -328,10 +436,16 @@ pub struct DeleteMethod {
 pub struct ValidateMethod { // TODO: enable validation via schema or command
     /// The command to run to validate the state of the resource.
     pub executable: String,
+    /// The SHA-256 digest of the executable, or a list of allowed digests, checked before it's run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<ExecutableDigest>,
     /// The arguments to pass to the command to perform a Validate.
     pub args: Option<Vec<GetArgKind>>,
     /// How to pass required input for a Validate.
     pub input: Option<InputKind>,
+    /// The environment and working directory for the command.
+    #[serde(flatten)]
+    pub environment: CommandEnvironment,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
@@ -346,6 +460,9 @@ pub enum ExportSchemaOrFiltering {
 pub struct ExportMethod {
     /// The command to run to enumerate instances of the resource.
     pub executable: String,
+    /// The SHA-256 digest of the executable, or a list of allowed digests, checked before it's run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<ExecutableDigest>,
     /// The arguments to pass to the command to perform a Export.
     pub args: Option<Vec<GetArgKind>>,
     /// How to pass input for a Export.
@@ -355,6 +472,9 @@ pub struct ExportMethod {
     pub require_security_context: Option<SecurityContextKind>,
     #[serde(flatten, skip_serializing_if = "Option::is_none")]
     pub schema_or_filtering: Option<ExportSchemaOrFiltering>,
+    /// The environment and working directory for the command.
+    #[serde(flatten)]
+    pub environment: CommandEnvironment,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, DscRepoSchema)]
@@ -362,10 +482,16 @@ pub struct ExportMethod {
 pub struct ResolveMethod {
     /// The command to run to enumerate instances of the resource.
     pub executable: String,
+    /// The SHA-256 digest of the executable, or a list of allowed digests, checked before it's run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<ExecutableDigest>,
     /// The arguments to pass to the command to perform a Export.
     pub args: Option<Vec<GetArgKind>>,
     /// How to pass input for a Export.
     pub input: Option<InputKind>,
+    /// The environment and working directory for the command.
+    #[serde(flatten)]
+    pub environment: CommandEnvironment,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, DscRepoSchema)]
@@ -392,10 +518,40 @@ pub enum AdapterInputKind {
     Single,
 }
 
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct ServerMethod {
+    /// The command to run to start the resource server.
+    pub executable: String,
+    /// The SHA-256 digest of the executable, or a list of allowed digests, checked before it's run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<ExecutableDigest>,
+    /// The arguments to pass to the command to start the resource server.
+    pub args: Option<Vec<String>>,
+    /// The protocol used to send requests to the resource server.
+    pub protocol: ServerProtocol,
+    /// The number of seconds to wait for a response before the resource server is killed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u32>,
+    /// The environment and working directory for the resource server, which serves every operation.
+    #[serde(flatten)]
+    pub environment: CommandEnvironment,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[schemars(transform = idiomaticize_string_enum)]
+pub enum ServerProtocol {
+    /// Requests and responses are JSON-RPC 2.0 messages exchanged as lines over STDIN and STDOUT.
+    #[serde(rename = "jsonrpc")]
+    JsonRpc,
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
 pub struct ListMethod {
     /// The command to run to list resources supported by a group resource.
     pub executable: String,
+    /// The SHA-256 digest of the executable, or a list of allowed digests, checked before it's run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<ExecutableDigest>,
     /// The arguments to pass to the command to perform a List.
     pub args: Option<Vec<String>>,
 }
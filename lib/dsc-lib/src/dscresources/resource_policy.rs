@@ -0,0 +1,207 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use crate::configure::config_doc::ExecutionKind;
+use crate::dscerror::DscError;
+use crate::types::{FullyQualifiedTypeName, TypeNameFilter};
+use crate::util::get_setting;
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use tracing::{debug, info, warn};
+
+/// The resource operations governed by the `resourcePolicy` setting.
+///
+/// Retrieving the schema and validating an instance don't change or read the state of the system,
+/// so they're always allowed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PolicyOperation {
+    Get,
+    Set,
+    Test,
+    Delete,
+    Export,
+    Resolve,
+}
+
+impl Display for PolicyOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let operation = match self {
+            PolicyOperation::Get => "get",
+            PolicyOperation::Set => "set",
+            PolicyOperation::Test => "test",
+            PolicyOperation::Delete => "delete",
+            PolicyOperation::Export => "export",
+            PolicyOperation::Resolve => "resolve",
+        };
+        write!(f, "{operation}")
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ResourcePolicySetting {
+    /// the rules evaluated in order, the first rule matching the resource type decides
+    #[serde(default)]
+    pub rules: Vec<ResourcePolicyRule>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct ResourcePolicyRule {
+    /// the resource type or wildcard type name the rule applies to
+    #[serde(rename = "type")]
+    pub type_name: TypeNameFilter,
+    /// if specified, only these operations are allowed
+    pub allow: Option<Vec<PolicyOperation>>,
+    /// the operations that are denied
+    #[serde(default)]
+    pub deny: Vec<PolicyOperation>,
+    /// the operations that are only allowed in what-if mode
+    #[serde(default)]
+    pub require_what_if: Vec<PolicyOperation>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum PolicyDecision {
+    /// No rule matches the resource type.
+    NoMatchingRule,
+    /// The operation is allowed by the rule with the given type filter.
+    Allowed(String),
+    /// The operation is denied by the rule with the given type filter for the given reason.
+    Denied(String, String),
+}
+
+impl ResourcePolicySetting {
+    /// Evaluate the rules for an operation on a resource type.
+    ///
+    /// # Arguments
+    ///
+    /// * `type_name` - The type of the resource being invoked
+    /// * `operation` - The operation being invoked
+    /// * `execution_type` - The execution type for operations that change the system, `None` otherwise
+    #[must_use]
+    pub fn evaluate(&self, type_name: &FullyQualifiedTypeName, operation: PolicyOperation, execution_type: Option<&ExecutionKind>) -> PolicyDecision {
+        let Some(rule) = self.rules.iter().find(|rule| rule.type_name.is_match(type_name)) else {
+            return PolicyDecision::NoMatchingRule;
+        };
+        let filter = rule.type_name.to_string();
+        if rule.deny.contains(&operation) {
+            return PolicyDecision::Denied(filter, t!("dscresources.resourcePolicy.operationDenied", operation = operation).to_string());
+        }
+        if let Some(allow) = &rule.allow && !allow.contains(&operation) {
+            return PolicyDecision::Denied(filter, t!("dscresources.resourcePolicy.operationNotAllowed", operation = operation).to_string());
+        }
+        if rule.require_what_if.contains(&operation) && execution_type == Some(&ExecutionKind::Actual) {
+            return PolicyDecision::Denied(filter, t!("dscresources.resourcePolicy.whatIfRequired", operation = operation).to_string());
+        }
+        PolicyDecision::Allowed(filter)
+    }
+}
+
+/// Get the `resourcePolicy` setting, the policy takes precedence over the settings file.
+///
+/// # Errors
+///
+/// This function will return an error if the setting is invalid so a malformed policy doesn't
+/// silently allow everything.
+pub fn get_resource_policy_setting() -> Result<ResourcePolicySetting, DscError> {
+    let Ok(value) = get_setting("resourcePolicy") else {
+        return Ok(ResourcePolicySetting::default());
+    };
+    let setting = if value.policy.is_null() { value.setting } else { value.policy };
+    if setting.is_null() {
+        return Ok(ResourcePolicySetting::default());
+    }
+    serde_json::from_value::<ResourcePolicySetting>(setting).map_err(|err| {
+        DscError::Setting(t!("dscresources.resourcePolicy.invalidSetting", error = err).to_string())
+    })
+}
+
+/// Check the `resourcePolicy` setting before invoking an operation on a resource.
+///
+/// Decisions made by a rule are traced for auditing.
+///
+/// # Arguments
+///
+/// * `type_name` - The type of the resource being invoked
+/// * `operation` - The operation being invoked
+/// * `execution_type` - The execution type for operations that change the system, `None` otherwise
+///
+/// # Errors
+///
+/// This function will return an error if the setting is invalid or the operation is denied.
+pub fn check_resource_policy(type_name: &FullyQualifiedTypeName, operation: PolicyOperation, execution_type: Option<&ExecutionKind>) -> Result<(), DscError> {
+    let setting = get_resource_policy_setting()?;
+    match setting.evaluate(type_name, operation, execution_type) {
+        PolicyDecision::NoMatchingRule => {
+            debug!("{}", t!("dscresources.resourcePolicy.noMatchingRule", resource = type_name, operation = operation));
+            Ok(())
+        },
+        PolicyDecision::Allowed(rule) => {
+            info!("{}", t!("dscresources.resourcePolicy.auditAllowed", resource = type_name, operation = operation, rule = rule));
+            Ok(())
+        },
+        PolicyDecision::Denied(rule, reason) => {
+            warn!("{}", t!("dscresources.resourcePolicy.auditDenied", resource = type_name, operation = operation, rule = rule, reason = reason));
+            Err(DscError::PolicyDenied(type_name.to_string(), operation.to_string(), reason))
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn registry_policy() -> ResourcePolicySetting {
+        serde_json::from_value(json!({
+            "rules": [
+                { "type": "Microsoft.Windows/Registry", "allow": ["get", "test"] },
+                { "type": "Microsoft.DSC.Debug/*", "requireWhatIf": ["set"], "deny": ["delete"] }
+            ]
+        })).unwrap()
+    }
+
+    #[test]
+    fn allow_list_denies_other_operations() {
+        let setting = registry_policy();
+        let registry = FullyQualifiedTypeName::parse("microsoft.windows/registry").unwrap();
+        assert_eq!(setting.evaluate(&registry, PolicyOperation::Get, None), PolicyDecision::Allowed("Microsoft.Windows/Registry".to_string()));
+        assert!(matches!(setting.evaluate(&registry, PolicyOperation::Set, Some(&ExecutionKind::WhatIf)), PolicyDecision::Denied(..)));
+        assert!(matches!(setting.evaluate(&registry, PolicyOperation::Delete, Some(&ExecutionKind::Actual)), PolicyDecision::Denied(..)));
+    }
+
+    #[test]
+    fn wildcard_rule_requires_what_if() {
+        let setting = registry_policy();
+        let echo = FullyQualifiedTypeName::parse("Microsoft.DSC.Debug/Echo").unwrap();
+        assert!(matches!(setting.evaluate(&echo, PolicyOperation::Set, Some(&ExecutionKind::Actual)), PolicyDecision::Denied(..)));
+        assert!(matches!(setting.evaluate(&echo, PolicyOperation::Set, Some(&ExecutionKind::WhatIf)), PolicyDecision::Allowed(_)));
+        assert!(matches!(setting.evaluate(&echo, PolicyOperation::Delete, Some(&ExecutionKind::WhatIf)), PolicyDecision::Denied(..)));
+        assert!(matches!(setting.evaluate(&echo, PolicyOperation::Export, None), PolicyDecision::Allowed(_)));
+    }
+
+    #[test]
+    fn first_matching_rule_decides() {
+        let setting: ResourcePolicySetting = serde_json::from_value(json!({
+            "rules": [
+                { "type": "Microsoft.Windows/Registry", "allow": ["get", "set"] },
+                { "type": "*", "deny": ["set"] }
+            ]
+        })).unwrap();
+        let registry = FullyQualifiedTypeName::parse("Microsoft.Windows/Registry").unwrap();
+        let other = FullyQualifiedTypeName::parse("Contoso/Other").unwrap();
+        assert!(matches!(setting.evaluate(&registry, PolicyOperation::Set, Some(&ExecutionKind::Actual)), PolicyDecision::Allowed(_)));
+        assert!(matches!(setting.evaluate(&other, PolicyOperation::Set, Some(&ExecutionKind::Actual)), PolicyDecision::Denied(..)));
+        assert_eq!(ResourcePolicySetting::default().evaluate(&other, PolicyOperation::Set, None), PolicyDecision::NoMatchingRule);
+    }
+
+    #[test]
+    fn unknown_operation_is_invalid() {
+        assert!(serde_json::from_value::<ResourcePolicySetting>(json!({
+            "rules": [ { "type": "Contoso/*", "deny": ["remove"] } ]
+        })).is_err());
+    }
+}
@@ -0,0 +1,361 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+//! Long-lived resource processes that serve operations over JSON-RPC.
+//!
+//! A resource whose manifest defines `server` is started once per run instead of once per
+//! operation, with the environment and working directory declared by `server` as the environments
+//! of the methods don't apply. Each operation is sent as a JSON-RPC 2.0 request on a single line to
+//! STDIN of the process and the response is read as a single line from STDOUT. The `params` of a
+//! request contain the `resourceType`, the `input` JSON of the operation and whether it is a
+//! `whatIf`. The `result` of a response is an array of the JSON documents the operation would write
+//! as lines to STDOUT when invoked as a command. At the end of the run a `shutdown` request is sent
+//! and STDIN is closed, the process is expected to exit when either happens. A server that doesn't
+//! respond to a request within the timeout of the manifest is killed and started again on the next
+//! request.
+
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::{
+    collections::HashMap,
+    env,
+    io::{BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    process::{Child, ChildStdin, Command, Stdio},
+    sync::{mpsc::{self, Receiver, RecvTimeoutError}, Arc, LazyLock, Mutex, PoisonError},
+    thread,
+    time::{Duration, Instant},
+};
+use tracing::{debug, error, info, trace, warn};
+
+use crate::{dscerror::DscError, types::ExitCodesMap, util::{canonicalize_which, verify_executable_digest}};
+use super::{
+    command_resource::{get_resource_error_data, log_stderr_line, CommandInput, DSC_ENV_PASSTHROUGH},
+    dscresource::redact_json_string,
+    invoke_result::ResourceError,
+    resource_manifest::ServerMethod,
+};
+
+const JSONRPC_VERSION: &str = "2.0";
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+/// Time to wait for a response when the manifest doesn't define `timeout`.
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Running resource servers keyed by the path of the manifest that defines them.
+static RESOURCE_SERVERS: LazyLock<Mutex<HashMap<PathBuf, Arc<Mutex<ResourceServer>>>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Debug, Serialize)]
+struct JsonRpcRequest<'a> {
+    jsonrpc: &'static str,
+    id: u64,
+    method: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    params: Option<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcResponse {
+    id: Option<Value>,
+    result: Option<Value>,
+    error: Option<JsonRpcError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcError {
+    code: i64,
+    message: String,
+    data: Option<Value>,
+}
+
+struct ResourceServer {
+    executable: String,
+    child: Child,
+    stdin: Option<ChildStdin>,
+    stdout: Receiver<String>,
+    stderr: Arc<Mutex<String>>,
+    /// The last structured error the server wrote to stderr, which describes a failed request.
+    resource_error: Arc<Mutex<Option<ResourceError>>>,
+    next_id: u64,
+    request_timeout: Duration,
+}
+
+impl ResourceServer {
+    fn start(server: &ServerMethod, directory: &Path) -> Result<Self, DscError> {
+        let executable = canonicalize_which(&server.executable, Some(directory))?;
+        verify_executable_digest(&executable, server.sha256.as_ref())?;
+        let mut command = Command::new(&executable);
+        command.stdin(Stdio::piped());
+        command.stdout(Stdio::piped());
+        command.stderr(Stdio::piped());
+        if let Some(args) = &server.args {
+            command.args(args);
+        }
+        let environment = CommandInput::default().with_environment(&server.environment, directory)?;
+        command.current_dir(environment.cwd.as_deref().unwrap_or(directory));
+        if let Some(env_passthrough) = &environment.env_passthrough {
+            command.env_clear();
+            for name in env_passthrough.iter().map(String::as_str).chain(DSC_ENV_PASSTHROUGH) {
+                if let Some(value) = env::var_os(name) {
+                    command.env(name, value);
+                }
+            }
+        }
+        if let Some(env) = environment.env {
+            command.envs(env);
+        }
+
+        let mut child = match command.spawn() {
+            Ok(c) => c,
+            Err(e) => {
+                return Err(DscError::CommandOperation(e.to_string(), executable));
+            }
+        };
+        let process_id = child.id();
+        info!("{}", t!("dscresources.resourceServer.started", executable = &executable, id = process_id));
+
+        let Some(stdin) = child.stdin.take() else {
+            return Err(DscError::CommandOperation(t!("dscresources.commandResource.processChildStdin").to_string(), executable));
+        };
+        let Some(stdout) = child.stdout.take() else {
+            return Err(DscError::CommandOperation(t!("dscresources.commandResource.processChildStdout").to_string(), executable));
+        };
+        let Some(stderr) = child.stderr.take() else {
+            return Err(DscError::CommandOperation(t!("dscresources.commandResource.processChildStderr").to_string(), executable));
+        };
+
+        // stdout is read on its own thread so a server that stops responding can be timed out,
+        // the channel disconnects when the server closes stdout
+        let (stdout_sender, stdout_receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                if stdout_sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        // stderr is drained continuously so the server never blocks on a full pipe
+        let filtered_stderr = Arc::new(Mutex::new(String::new()));
+        let stderr_buffer = Arc::clone(&filtered_stderr);
+        let resource_error = Arc::new(Mutex::new(None));
+        let stderr_error = Arc::clone(&resource_error);
+        thread::spawn(move || {
+            for line in BufReader::new(stderr).lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                if let Some(error_data) = get_resource_error_data(&line) {
+                    *stderr_error.lock().unwrap_or_else(PoisonError::into_inner) = Some(error_data.error);
+                }
+                let filtered_line = log_stderr_line(&process_id, &line);
+                if !filtered_line.is_empty() {
+                    let mut buffer = stderr_buffer.lock().unwrap_or_else(PoisonError::into_inner);
+                    buffer.push_str(filtered_line);
+                    buffer.push('\n');
+                }
+            }
+        });
+
+        Ok(Self {
+            executable,
+            child,
+            stdin: Some(stdin),
+            stdout: stdout_receiver,
+            stderr: filtered_stderr,
+            resource_error,
+            next_id: 1,
+            request_timeout: server.timeout.map_or(DEFAULT_REQUEST_TIMEOUT, |seconds| Duration::from_secs(u64::from(seconds))),
+        })
+    }
+
+    fn take_stderr(&self) -> String {
+        std::mem::take(&mut *self.stderr.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    fn take_resource_error(&self) -> Option<ResourceError> {
+        self.resource_error.lock().unwrap_or_else(PoisonError::into_inner).take()
+    }
+
+    fn send(&mut self, method: &str, params: Option<Value>) -> Result<u64, DscError> {
+        let id = self.next_id;
+        self.next_id += 1;
+        let request = serde_json::to_string(&JsonRpcRequest {
+            jsonrpc: JSONRPC_VERSION,
+            id,
+            method,
+            params,
+        })?;
+        trace!("{}", t!("dscresources.resourceServer.sendRequest", executable = &self.executable, request = redact_json_string(&request)));
+        let Some(stdin) = self.stdin.as_mut() else {
+            return Err(DscError::CommandOperation(t!("dscresources.commandResource.processWriteStdin").to_string(), self.executable.clone()));
+        };
+        if writeln!(stdin, "{request}").and_then(|()| stdin.flush()).is_err() {
+            return Err(DscError::CommandOperation(t!("dscresources.commandResource.processWriteStdin").to_string(), self.executable.clone()));
+        }
+        Ok(id)
+    }
+
+    fn request(&mut self, method: &str, params: Option<Value>) -> Result<Value, DscError> {
+        // an error written for an earlier request doesn't describe this one
+        self.take_resource_error();
+        let id = self.send(method, params)?;
+        let deadline = Instant::now() + self.request_timeout;
+        loop {
+            let line = match self.stdout.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                Ok(line) => line,
+                Err(RecvTimeoutError::Timeout) => {
+                    return Err(DscError::CommandOperation(t!("dscresources.resourceServer.requestTimeout", method = method, seconds = self.request_timeout.as_secs()).to_string(), self.executable.clone()));
+                },
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(DscError::CommandOperation(t!("dscresources.resourceServer.serverExited", stderr = self.take_stderr()).to_string(), self.executable.clone()));
+                },
+            };
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let response: JsonRpcResponse = match serde_json::from_str(line) {
+                Ok(response) => response,
+                Err(err) => {
+                    return Err(DscError::Operation(t!("dscresources.resourceServer.invalidResponse", executable = &self.executable, response = line, err = err).to_string()));
+                }
+            };
+            // responses without a matching id are notifications or late replies and are skipped
+            if response.id.as_ref().and_then(Value::as_u64) != Some(id) {
+                trace!("{}", t!("dscresources.resourceServer.skippingMessage", executable = &self.executable, message = line));
+                continue;
+            }
+            if let Some(error) = response.error {
+                let code = i32::try_from(error.code).unwrap_or(i32::MIN);
+                // a structured error from the resource is more specific than the error response
+                if let Some(resource_error) = self.take_resource_error() {
+                    error!("{}", t!("dscresources.commandResource.resourceError", pid = self.child.id(), error = resource_error));
+                    return Err(DscError::CommandResourceError(self.executable.clone(), code, Box::new(resource_error)));
+                }
+                let mut message = error.message;
+                if let Some(data) = error.data {
+                    message = format!("{message}: {data}");
+                }
+                return Err(DscError::Command(self.executable.clone(), code, message));
+            }
+            return Ok(response.result.unwrap_or(Value::Null));
+        }
+    }
+
+    fn shutdown(&mut self) {
+        debug!("{}", t!("dscresources.resourceServer.shuttingDown", executable = &self.executable, id = self.child.id()));
+        if let Err(err) = self.send("shutdown", None) {
+            debug!("{}", t!("dscresources.resourceServer.shutdownRequestFailed", executable = &self.executable, err = err));
+        }
+        // closing STDIN lets servers that don't handle the shutdown request exit
+        drop(self.stdin.take());
+        let start = Instant::now();
+        while start.elapsed() < SHUTDOWN_TIMEOUT {
+            match self.child.try_wait() {
+                Ok(Some(status)) => {
+                    debug!("{}", t!("dscresources.resourceServer.exited", executable = &self.executable, status = status));
+                    return;
+                },
+                Ok(None) => thread::sleep(Duration::from_millis(50)),
+                Err(_) => break,
+            }
+        }
+        warn!("{}", t!("dscresources.resourceServer.killing", executable = &self.executable, id = self.child.id()));
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Invoke an operation on the long-lived server of a resource, starting it if it isn't running yet.
+///
+/// # Arguments
+///
+/// * `manifest_path` - The path of the manifest defining the server, used to identify the server
+/// * `directory` - The directory the server is started in
+/// * `server` - The server definition from the manifest
+/// * `exit_codes` - The exit codes of the manifest, used to describe error codes returned by the server
+/// * `resource_type` - The type of the resource the operation is for
+/// * `operation` - The name of the operation, used as the JSON-RPC method
+/// * `input` - The JSON input of the operation, can be empty
+/// * `what_if` - Whether the operation should only report what would change
+///
+/// # Returns
+///
+/// The exit code, stdout, and stderr equivalent to invoking the operation as a command.
+///
+/// # Errors
+///
+/// Error is returned if the server can't be started, the request fails or times out, or the server
+/// returns an error.
+#[allow(clippy::too_many_arguments)]
+pub fn invoke_server(manifest_path: &Path, directory: &Path, server: &ServerMethod, exit_codes: &ExitCodesMap, resource_type: &str, operation: &str, input: &str, what_if: bool) -> Result<(i32, String, String), DscError> {
+    let resource_server = {
+        let mut servers = RESOURCE_SERVERS.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(resource_server) = servers.get(manifest_path) {
+            Arc::clone(resource_server)
+        } else {
+            let resource_server = Arc::new(Mutex::new(ResourceServer::start(server, directory)?));
+            servers.insert(manifest_path.to_path_buf(), Arc::clone(&resource_server));
+            resource_server
+        }
+    };
+
+    let input = if input.is_empty() {
+        Value::Null
+    } else {
+        serde_json::from_str(input)?
+    };
+    let params = json!({
+        "resourceType": resource_type,
+        "input": input,
+        "whatIf": what_if,
+    });
+
+    let mut resource_server = resource_server.lock().unwrap_or_else(PoisonError::into_inner);
+    info!("{}", t!("dscresources.resourceServer.invokeOperation", operation = operation, resource = resource_type, executable = &resource_server.executable));
+    let result = match resource_server.request(operation, Some(params)) {
+        Ok(result) => result,
+        Err(err) => {
+            // a server that exited or stopped responding is started again on the next request
+            if matches!(err, DscError::CommandOperation(..) | DscError::Io(_)) {
+                RESOURCE_SERVERS.lock().unwrap_or_else(PoisonError::into_inner).remove(manifest_path);
+                resource_server.shutdown();
+            }
+            // error codes returned by the server are described by the manifest like exit codes
+            if let DscError::Command(executable, code, _) = &err
+                && !exit_codes.is_empty_or_default()
+                && let Some(error_message) = exit_codes.get_code(*code) {
+                return Err(DscError::CommandExitFromManifest(executable.clone(), *code, error_message.clone()));
+            }
+            return Err(err);
+        }
+    };
+    let Value::Array(documents) = result else {
+        return Err(DscError::Operation(t!("dscresources.resourceServer.resultNotArray", executable = &resource_server.executable, operation = operation).to_string()));
+    };
+    let mut stdout = String::new();
+    for document in documents {
+        stdout.push_str(&serde_json::to_string(&document)?);
+        stdout.push('\n');
+    }
+    Ok((0, stdout, resource_server.take_stderr()))
+}
+
+/// Shut down all running resource servers.
+///
+/// Each server is sent a `shutdown` request and has its STDIN closed, servers that don't exit
+/// within a few seconds are killed.
+pub fn shutdown_resource_servers() {
+    let servers: Vec<Arc<Mutex<ResourceServer>>> = RESOURCE_SERVERS.lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .drain()
+        .map(|(_, server)| server)
+        .collect();
+    for server in servers {
+        server.lock().unwrap_or_else(PoisonError::into_inner).shutdown();
+    }
+}
@@ -8,7 +8,8 @@ use crate::{
     dscerror::DscError,
     dscresources::{
         command_resource::invoke_command,
-        dscresource::DscResource
+        dscresource::DscResource,
+        resource_manifest::ExecutableDigest,
     },
     extensions::{
         dscextension::{
@@ -31,6 +32,9 @@ use tracing::{info, trace, warn};
 pub struct DiscoverMethod {
     /// The command to run to get the state of the resource.
     pub executable: String,
+    /// The SHA-256 digest of the executable, or a list of allowed digests, checked before it's run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<ExecutableDigest>,
     /// The arguments to pass to the command to perform a Get.
     pub args: Option<Vec<DiscoverArgKind>>,
 }
@@ -99,6 +103,7 @@ impl DscExtension {
                 Some(&self.directory),
                 None,
                 extension.exit_codes.as_ref(),
+                discover.sha256.as_ref(),
             )?;
             if stdout.is_empty() {
                 info!("{}", t!("extensions.dscextension.discoverNoResults", extension = self.type_name));
@@ -2,7 +2,7 @@
 // Licensed under the MIT License.
 
 use crate::{
-    configure::context::Context, dscerror::DscError, dscresources::command_resource::invoke_command, extensions::{
+    configure::context::Context, dscerror::DscError, dscresources::{command_resource::{CommandInput, get_command_input, invoke_command}, resource_manifest::{ExecutableDigest, InputKind}}, extensions::{
         dscextension::{
             Capability,
             DscExtension,
@@ -17,6 +17,7 @@ use path_absolutize::Absolutize;
 use rust_i18n::t;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use std::path::Path;
 use tracing::{debug, info, warn};
 
@@ -28,10 +29,16 @@ pub struct ImportMethod {
     pub file_extensions: Vec<String>,
     /// The command to run to get the state of the resource.
     pub executable: String,
+    /// The SHA-256 digest of the executable, or a list of allowed digests, checked before it's run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<ExecutableDigest>,
     /// The arguments to pass to the command to perform an Import.
     pub args: Option<Vec<ImportArgKind>>,
     /// Enables modifying the resulting output from STDOUT after running the import command.
     pub output: Option<String>,
+    /// How to pass the path of the file to import as a JSON object.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input: Option<InputKind>,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
@@ -81,17 +88,29 @@ impl DscExtension {
             let Some(import) = extension.import else {
                 return Err(DscError::UnsupportedCapability(self.type_name.to_string(), Capability::Import.to_string()));
             };
-            let args = process_import_args(import.args.as_ref(), file)?;
+            // make path absolute
+            let Ok(full_path) = file.absolutize() else {
+                return Err(DscError::Extension(t!("util.failedToAbsolutizePath", path = file.display()).to_string()));
+            };
+            let args = process_import_args(import.args.as_ref(), &full_path);
+            let command_input = match &import.input {
+                Some(input_kind) => {
+                    let input = serde_json::to_string(&json!({ "file": full_path.to_string_lossy() }))?;
+                    get_command_input(Some(input_kind), &input)?
+                },
+                None => CommandInput::default(),
+            };
             if let Some(deprecation_message) = extension.deprecation_message.as_ref() {
                 warn!("{}", t!("extensions.dscextension.deprecationMessage", extension = self.type_name, message = deprecation_message));
             }
             let (_exit_code, stdout, _stderr) = invoke_command(
                 &import.executable,
-                args,
-                None,
+                command_input.process_args(args),
+                command_input.stdin.as_deref(),
                 Some(&self.directory),
-                None,
+                command_input.env,
                 extension.exit_codes.as_ref(),
+                import.sha256.as_ref(),
             )?;
             if stdout.is_empty() {
                 info!("{}", t!("extensions.dscextension.importNoResults", extension = self.type_name));
@@ -114,15 +133,10 @@ impl DscExtension {
     }
 }
 
-fn process_import_args(args: Option<&Vec<ImportArgKind>>, file: &Path) -> Result<Option<Vec<String>>, DscError> {
+fn process_import_args(args: Option<&Vec<ImportArgKind>>, full_path: &Path) -> Option<Vec<String>> {
     let Some(arg_values) = args else {
         debug!("{}", t!("dscresources.commandResource.noArgs"));
-        return Ok(None);
-    };
-
-    // make path absolute
-    let Ok(full_path) = file.absolutize() else {
-        return Err(DscError::Extension(t!("util.failedToAbsolutizePath", path = file.display()).to_string()));
+        return None;
     };
 
     let mut processed_args = Vec::<String>::new();
@@ -140,5 +154,5 @@ fn process_import_args(args: Option<&Vec<ImportArgKind>>, file: &Path) -> Result
         }
     }
 
-    Ok(Some(processed_args))
+    Some(processed_args)
 }
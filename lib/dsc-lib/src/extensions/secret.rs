@@ -5,7 +5,8 @@ use crate::{
     configure::parameters::SecureString,
     dscerror::DscError,
     dscresources::{
-        command_resource::invoke_command,
+        command_resource::{CommandInput, get_command_input, invoke_command},
+        resource_manifest::{ExecutableDigest, InputKind},
     },
     extensions::{
         dscextension::{
@@ -20,6 +21,7 @@ use crate::{
 use rust_i18n::t;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use tracing::{debug, warn};
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
@@ -46,8 +48,15 @@ pub enum SecretArgKind {
 pub struct SecretMethod {
     /// The command to run to get the state of the resource.
     pub executable: String,
+    /// The SHA-256 digest of the executable, or a list of allowed digests, checked before it's run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<ExecutableDigest>,
     /// The arguments to pass to the command to perform a Get.
     pub args: Option<Vec<SecretArgKind>>,
+    /// How to pass the secret name and vault as a JSON object.  If not specified, the vault is
+    /// passed via STDIN.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input: Option<InputKind>,
 }
 
 impl DscExtension {
@@ -81,13 +90,21 @@ impl DscExtension {
             if let Some(deprecation_message) = extension.deprecation_message.as_ref() {
                 warn!("{}", t!("extensions.dscextension.deprecationMessage", extension = self.type_name, message = deprecation_message));
             }
+            let command_input = match &secret.input {
+                Some(input_kind) => {
+                    let input = serde_json::to_string(&json!({ "name": name, "vault": vault }))?;
+                    get_command_input(Some(input_kind), &input)?
+                },
+                None => CommandInput { stdin: vault.map(ToString::to_string), ..CommandInput::default() },
+            };
             let (_exit_code, stdout, _stderr) = invoke_command(
                 &secret.executable,
-                args,
-                vault,
+                command_input.process_args(args),
+                command_input.stdin.as_deref(),
                 Some(&self.directory),
-                None,
+                command_input.env,
                 extension.exit_codes.as_ref(),
+                secret.sha256.as_ref(),
             )?;
             if stdout.is_empty() {
                 debug!("{}", t!("extensions.dscextension.extensionReturnedNoSecret", extension = self.type_name));
@@ -85,8 +85,10 @@ impl Function for Concat {
 
 #[cfg(test)]
 mod tests {
+    use crate::configure::config_doc::DataType;
     use crate::configure::context::Context;
     use crate::parser::Statement;
+    use serde_json::json;
 
     #[test]
     fn strings() {
@@ -143,4 +145,22 @@ mod tests {
         let result = parser.parse_and_execute("[concat(createArray('a','b'), 'c')]", &Context::new());
         assert!(result.is_err());
     }
+
+    #[test]
+    fn secure_string_taints_result() {
+        let mut context = Context::new();
+        context.parameters.insert("pw".to_string(), (json!({"secureString": "secret"}), DataType::SecureString));
+        let mut parser = Statement::new().unwrap();
+        let result = parser.parse_and_execute("[concat('pw=', parameters('pw'))]", &context).unwrap();
+        assert_eq!(result, json!({"secureString": "pw=secret"}));
+    }
+
+    #[test]
+    fn secure_object_member_taints_result() {
+        let mut context = Context::new();
+        context.parameters.insert("creds".to_string(), (json!({"secureObject": {"user": "admin"}}), DataType::SecureObject));
+        let mut parser = Statement::new().unwrap();
+        let result = parser.parse_and_execute("[concat('user=', parameters('creds').user)]", &context).unwrap();
+        assert_eq!(result, json!({"secureString": "user=admin"}));
+    }
 }
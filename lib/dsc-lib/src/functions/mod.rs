@@ -5,6 +5,7 @@ use std::collections::HashMap;
 
 use crate::DscError;
 use crate::configure::context::{Context, ProcessMode};
+use crate::configure::parameters::{contains_secure_value, to_secure_value, unwrap_secure_value};
 use crate::functions::user_function::invoke_user_function;
 use crate::schemas::dsc_repo::DscRepoSchema;
 use rust_i18n::t;
@@ -12,6 +13,7 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::fmt::Display;
+use tracing::debug;
 
 pub mod add;
 pub mod and;
@@ -73,6 +75,11 @@ pub mod reference;
 pub mod resource_id;
 pub mod restart_required;
 pub mod secret;
+pub mod semver_compare;
+pub mod semver_major;
+pub mod semver_minor;
+pub mod semver_patch;
+pub mod semver_satisfies;
 pub mod shallow_merge;
 pub mod skip;
 pub mod starts_with;
@@ -225,6 +232,11 @@ impl FunctionDispatcher {
             Box::new(resource_id::ResourceId{}),
             Box::new(restart_required::RestartRequired{}),
             Box::new(secret::Secret{}),
+            Box::new(semver_compare::SemverCompare{}),
+            Box::new(semver_major::SemverMajor{}),
+            Box::new(semver_minor::SemverMinor{}),
+            Box::new(semver_patch::SemverPatch{}),
+            Box::new(semver_satisfies::SemverSatisfies{}),
             Box::new(shallow_merge::ShallowMerge{}),
             Box::new(skip::Skip{}),
             Box::new(starts_with::StartsWith{}),
@@ -266,10 +278,30 @@ impl FunctionDispatcher {
     /// * `name` - The name of the function to invoke.
     /// * `args` - The arguments to the function.
     ///
+    /// If any argument is or contains a secure value, the result is returned as a secure value.
+    ///
     /// # Errors
     ///
     /// This function will return an error if the function fails to execute.
     pub fn invoke(&self, name: &str, args: &[Value], context: &Context) -> Result<Value, DscError> {
+        // values derived from secure inputs are also secure so they don't leak into outputs
+        if !args.iter().any(contains_secure_value) {
+            return self.invoke_function(name, args, context);
+        }
+
+        debug!("{}", t!("functions.secureArgs", name = name));
+        let result = if self.functions.contains_key(name) {
+            // builtin functions operate on the wrapped values
+            let unwrapped_args: Vec<Value> = args.iter().map(unwrap_secure_value).collect();
+            self.invoke_function(name, &unwrapped_args, context)?
+        } else {
+            // user functions declare their own secure parameters
+            self.invoke_function(name, args, context)?
+        };
+        Ok(to_secure_value(&result))
+    }
+
+    fn invoke_function(&self, name: &str, args: &[Value], context: &Context) -> Result<Value, DscError> {
         let Some(function) = self.functions.get(name) else {
             // if function name contains a period, it might be a user function
             if name.contains('.') {
@@ -64,6 +64,11 @@ impl Function for Parameters {
                             return Err(DscError::Parser(t!("functions.parameters.keyNotInt", key = key).to_string()));
                         };
                     },
+                    DataType::Number => {
+                        if !value.is_number() {
+                            return Err(DscError::Parser(t!("functions.parameters.keyNotNumber", key = key).to_string()));
+                        }
+                    },
                     DataType::Bool => {
                         let Some(_value) = value.as_bool() else {
                             return Err(DscError::Parser(t!("functions.parameters.keyNotBool", key = key).to_string()));
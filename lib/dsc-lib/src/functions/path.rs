@@ -35,7 +35,7 @@ impl Function for Path {
     }
 
     fn invoke(&self, args: &[Value], _context: &Context) -> Result<Value, DscError> {
-        trace!("{}", t!("functions.path.traceArgs", args = args : {:?}));
+        trace!("{}", t!("functions.path.traceArgs", count = args.len()));
 
         let mut path = PathBuf::new();
         for arg in args {
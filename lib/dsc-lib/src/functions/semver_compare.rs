@@ -0,0 +1,85 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use crate::DscError;
+use crate::configure::context::Context;
+use crate::functions::{FunctionArgKind, Function, FunctionCategory, FunctionMetadata};
+use crate::types::SemanticVersion;
+use rust_i18n::t;
+use serde_json::Value;
+use std::cmp::Ordering;
+use tracing::debug;
+
+#[derive(Debug, Default)]
+pub struct SemverCompare {}
+
+impl Function for SemverCompare {
+    fn get_metadata(&self) -> FunctionMetadata {
+        FunctionMetadata {
+            name: "semverCompare".to_string(),
+            description: t!("functions.semverCompare.description").to_string(),
+            syntax: t!("functions.semverCompare.syntax").to_string(),
+            constraints: None,
+            category: vec![FunctionCategory::Comparison],
+            min_args: 2,
+            max_args: 2,
+            accepted_arg_ordered_types: vec![
+                vec![FunctionArgKind::String],
+                vec![FunctionArgKind::String],
+            ],
+            remaining_arg_accepted_types: None,
+            return_types: vec![FunctionArgKind::Number],
+        }
+    }
+
+    fn invoke(&self, args: &[Value], _context: &Context) -> Result<Value, DscError> {
+        debug!("{}", t!("functions.semverCompare.invoked"));
+        let (Some(first), Some(second)) = (args[0].as_str(), args[1].as_str()) else {
+            return Err(DscError::Parser(t!("functions.invalidArguments").to_string()));
+        };
+
+        let first = SemanticVersion::parse(first)?;
+        let second = SemanticVersion::parse(second)?;
+        // build metadata doesn't affect precedence per the semver specification
+        let result = match first.cmp_precedence(&second) {
+            Ordering::Less => -1,
+            Ordering::Equal => 0,
+            Ordering::Greater => 1,
+        };
+        Ok(Value::Number(result.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::configure::context::Context;
+    use crate::parser::Statement;
+
+    #[test]
+    fn less() {
+        let mut parser = Statement::new().unwrap();
+        let result = parser.parse_and_execute("[semverCompare('1.2.3', '1.10.0')]", &Context::new()).unwrap();
+        assert_eq!(result, -1);
+    }
+
+    #[test]
+    fn equal_ignores_build_metadata() {
+        let mut parser = Statement::new().unwrap();
+        let result = parser.parse_and_execute("[semverCompare('1.2.3+ci', '1.2.3')]", &Context::new()).unwrap();
+        assert_eq!(result, 0);
+    }
+
+    #[test]
+    fn greater_than_prerelease() {
+        let mut parser = Statement::new().unwrap();
+        let result = parser.parse_and_execute("[semverCompare('2.0.0', '2.0.0-rc.1')]", &Context::new()).unwrap();
+        assert_eq!(result, 1);
+    }
+
+    #[test]
+    fn invalid_version() {
+        let mut parser = Statement::new().unwrap();
+        let result = parser.parse_and_execute("[semverCompare('1.2', '1.2.0')]", &Context::new());
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,67 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use crate::DscError;
+use crate::configure::context::Context;
+use crate::functions::{FunctionArgKind, Function, FunctionCategory, FunctionMetadata};
+use crate::types::SemanticVersion;
+use rust_i18n::t;
+use serde_json::Value;
+use tracing::debug;
+
+#[derive(Debug, Default)]
+pub struct SemverMinor {}
+
+impl Function for SemverMinor {
+    fn get_metadata(&self) -> FunctionMetadata {
+        FunctionMetadata {
+            name: "semverMinor".to_string(),
+            description: t!("functions.semverMinor.description").to_string(),
+            syntax: t!("functions.semverMinor.syntax").to_string(),
+            constraints: None,
+            category: vec![FunctionCategory::Comparison],
+            min_args: 1,
+            max_args: 1,
+            accepted_arg_ordered_types: vec![vec![FunctionArgKind::String]],
+            remaining_arg_accepted_types: None,
+            return_types: vec![FunctionArgKind::Number],
+        }
+    }
+
+    fn invoke(&self, args: &[Value], _context: &Context) -> Result<Value, DscError> {
+        debug!("{}", t!("functions.semverMinor.invoked"));
+        let Some(version) = args[0].as_str() else {
+            return Err(DscError::Parser(t!("functions.invalidArguments").to_string()));
+        };
+
+        let version = SemanticVersion::parse(version)?;
+        Ok(Value::Number(version.minor.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::configure::context::Context;
+    use crate::parser::Statement;
+
+    #[test]
+    fn stable_version() {
+        let mut parser = Statement::new().unwrap();
+        let result = parser.parse_and_execute("[semverMinor('3.2.1')]", &Context::new()).unwrap();
+        assert_eq!(result, 2);
+    }
+
+    #[test]
+    fn prerelease_version() {
+        let mut parser = Statement::new().unwrap();
+        let result = parser.parse_and_execute("[semverMinor('3.2.1-rc.1+ci')]", &Context::new()).unwrap();
+        assert_eq!(result, 2);
+    }
+
+    #[test]
+    fn invalid_version() {
+        let mut parser = Statement::new().unwrap();
+        let result = parser.parse_and_execute("[semverMinor('v3.2')]", &Context::new());
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,86 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use crate::DscError;
+use crate::configure::context::Context;
+use crate::functions::{FunctionArgKind, Function, FunctionCategory, FunctionMetadata};
+use crate::types::{SemanticVersion, SemanticVersionReq};
+use rust_i18n::t;
+use serde_json::Value;
+use tracing::debug;
+
+#[derive(Debug, Default)]
+pub struct SemverSatisfies {}
+
+impl Function for SemverSatisfies {
+    fn get_metadata(&self) -> FunctionMetadata {
+        FunctionMetadata {
+            name: "semverSatisfies".to_string(),
+            description: t!("functions.semverSatisfies.description").to_string(),
+            syntax: t!("functions.semverSatisfies.syntax").to_string(),
+            constraints: None,
+            category: vec![FunctionCategory::Comparison],
+            min_args: 2,
+            max_args: 2,
+            accepted_arg_ordered_types: vec![
+                vec![FunctionArgKind::String],
+                vec![FunctionArgKind::String],
+            ],
+            remaining_arg_accepted_types: None,
+            return_types: vec![FunctionArgKind::Boolean],
+        }
+    }
+
+    fn invoke(&self, args: &[Value], _context: &Context) -> Result<Value, DscError> {
+        debug!("{}", t!("functions.semverSatisfies.invoked"));
+        let (Some(version), Some(requirement)) = (args[0].as_str(), args[1].as_str()) else {
+            return Err(DscError::Parser(t!("functions.invalidArguments").to_string()));
+        };
+
+        let version = SemanticVersion::parse(version)?;
+        let requirement = SemanticVersionReq::parse(requirement)?;
+        Ok(Value::Bool(requirement.matches(&version)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::configure::context::Context;
+    use crate::parser::Statement;
+    use serde_json::Value;
+
+    #[test]
+    fn satisfied() {
+        let mut parser = Statement::new().unwrap();
+        let result = parser.parse_and_execute("[semverSatisfies('2.10.0', '>=2.1')]", &Context::new()).unwrap();
+        assert_eq!(result, Value::Bool(true));
+    }
+
+    #[test]
+    fn not_satisfied() {
+        let mut parser = Statement::new().unwrap();
+        let result = parser.parse_and_execute("[semverSatisfies('2.0.5', '>=2.1')]", &Context::new()).unwrap();
+        assert_eq!(result, Value::Bool(false));
+    }
+
+    #[test]
+    fn multiple_comparators() {
+        let mut parser = Statement::new().unwrap();
+        let result = parser.parse_and_execute("[semverSatisfies('1.4.0', '>=1.2, <2.0')]", &Context::new()).unwrap();
+        assert_eq!(result, Value::Bool(true));
+    }
+
+    #[test]
+    fn prerelease_not_matched_by_stable_requirement() {
+        let mut parser = Statement::new().unwrap();
+        let result = parser.parse_and_execute("[semverSatisfies('2.1.0-rc.1', '>=2.0')]", &Context::new()).unwrap();
+        assert_eq!(result, Value::Bool(false));
+    }
+
+    #[test]
+    fn invalid_requirement() {
+        let mut parser = Statement::new().unwrap();
+        let result = parser.parse_and_execute("[semverSatisfies('1.0.0', '1.0.0+build')]", &Context::new());
+        assert!(result.is_err());
+    }
+}
@@ -1,7 +1,7 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
-use crate::configure::{{config_doc::{DataType, UserFunctionDefinition}, context::ProcessMode}, validate_parameter_type};
+use crate::configure::{{config_doc::{DataType, UserFunctionDefinition}, context::ProcessMode, parameters::unwrap_secure_value}, validate_parameter_type};
 use crate::dscerror::DscError;
 use crate::functions::Context;
 use crate::parser::Statement;
@@ -38,7 +38,8 @@ pub fn invoke_user_function(name: &str, args: &[Value], context: &Context) -> Re
         }
         let mut parser = Statement::new()?;
         let result = parser.parse_and_execute(&function_definition.output.value, &user_context)?;
-        validate_output_type(name, function_definition, &result)?;
+        // the output may be secure if derived from secure parameters
+        validate_output_type(name, function_definition, &unwrap_secure_value(&result))?;
         Ok(result)
     } else {
         Err(DscError::Parser(t!("functions.userFunction.unknownUserFunction", name = name).to_string()))
@@ -69,6 +70,11 @@ fn validate_output_type(name: &str, function_definition: &UserFunctionDefinition
                 return Err(DscError::Validation(t!("functions.userFunction.incorrectOutputType", name = name, expected_type = "int").to_string()));
             }
         },
+        DataType::Number => {
+            if !output.is_number() {
+                return Err(DscError::Validation(t!("functions.userFunction.incorrectOutputType", name = name, expected_type = "number").to_string()));
+            }
+        },
         DataType::Bool => {
             if !output.is_boolean() {
                 return Err(DscError::Validation(t!("functions.userFunction.incorrectOutputType", name = name, expected_type = "bool").to_string()));
@@ -7,7 +7,7 @@ use tracing::{debug, trace, warn};
 use tree_sitter::Node;
 
 use crate::configure::context::Context;
-use crate::configure::parameters::{SecureObject, SecureString, is_secure_value};
+use crate::configure::parameters::{is_secure_value, to_secure_value};
 use crate::dscerror::DscError;
 use crate::functions::FunctionDispatcher;
 use crate::parser::functions::Function;
@@ -163,7 +163,7 @@ impl Expression {
                                 return Err(DscError::Parser(t!("parser.expression.memberNameNotFound", member = member).to_string()));
                             }
                             if is_secure {
-                                value = convert_to_secure(&object[member]);
+                                value = to_secure_value(&object[member]);
                             } else {
                                 value = object[member].clone();
                             }
@@ -175,7 +175,7 @@ impl Expression {
                     Accessor::Index(index_value) => {
                         debug!("{}", t!("parser.expression.evaluatingIndexAccessor", index = index_value : {:?}));
                         if is_secure {
-                            index = convert_to_secure(index_value);
+                            index = to_secure_value(index_value);
                         } else {
                             index = index_value.clone();
                         }
@@ -197,7 +197,7 @@ impl Expression {
                             return Err(DscError::Parser(t!("parser.expression.indexOutOfBounds").to_string()));
                         }
                         if is_secure {
-                            value = convert_to_secure(&array[index]);
+                            value = to_secure_value(&array[index]);
                         } else {
                             value = array[index].clone();
                         }
@@ -212,7 +212,7 @@ impl Expression {
                             return Err(DscError::Parser(t!("parser.expression.memberNameNotFound", member = index).to_string()));
                         }
                         if is_secure {
-                            value = convert_to_secure(&object[index]);
+                            value = to_secure_value(&object[index]);
                         } else {
                             value = object[index].clone();
                         }
@@ -225,43 +225,12 @@ impl Expression {
                 }
             }
 
-            trace!("{}", t!("parser.expression.accessorResult", result = value : {:?}));
+            if is_secure {
+                trace!("{}", t!("parser.expression.functionResultSecure"));
+            } else {
+                trace!("{}", t!("parser.expression.accessorResult", result = value : {:?}));
+            }
             Ok(value)
         }
     }
 }
-
-/// Convert a JSON value to a secure value if it is a string or an array of strings.
-///
-/// Arguments
-///
-/// * `value` - The JSON value to convert.
-///
-/// Returns
-///
-/// The converted JSON value.
-fn convert_to_secure(value: &Value) -> Value {
-    if is_secure_value(value) {
-        return value.clone();
-    }
-
-    if let Some(string) = value.as_str() {
-        let secure_string = SecureString {
-            secure_string: string.to_string(),
-        };
-        return serde_json::to_value(secure_string).unwrap_or(value.clone());
-    }
-
-    if value.as_object().is_some() {
-        let secure_object = SecureObject {
-            secure_object: value.clone(),
-        };
-        return serde_json::to_value(secure_object).unwrap_or(value.clone());
-    }
-
-    if let Some(array) = value.as_array() {
-        let new_array: Vec<Value> = array.iter().map(convert_to_secure).collect();
-        return Value::Array(new_array);
-    }
-    value.clone()
-}
@@ -2,15 +2,18 @@
 // Licensed under the MIT License.
 
 use crate::DscError;
+use crate::dscresources::dscresource::redact;
+use crate::dscresources::invoke_result::ResourceError;
 use crate::types::FullyQualifiedTypeName;
 
 use clap::ValueEnum;
 use indicatif::ProgressStyle;
 use rust_i18n::t;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::sync::{LazyLock, Mutex};
 use tracing_indicatif::span_ext::IndicatifSpanExt;
-use tracing::{trace, warn_span};
+use tracing::{debug, trace, warn_span};
 use tracing::span::Span;
 use uuid::Uuid;
 
@@ -29,6 +32,9 @@ pub enum ProgressFormat {
 pub struct Failure {
     pub message: String,
     pub exit_code: i32,
+    /// The structured error reported by the resource.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ResourceError>,
 }
 
 #[derive(Default, Debug, Clone, Serialize)]
@@ -60,6 +66,93 @@ pub struct Progress {
     /// Failure information.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub failure: Option<Failure>,
+    /// The progress reported by the resource being operated on.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_progress: Option<ResourceProgress>,
+}
+
+/// Progress reported by a resource on stderr as `{"progress": {"percent": 40, "activity": "..."}}`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct ResourceProgress {
+    /// The percentage of the resource operation completed, from 0 to 100.
+    pub percent: f64,
+    /// The activity the resource is performing.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub activity: Option<String>,
+    /// The status of the activity.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    /// The number of seconds the resource expects the operation to take.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seconds_remaining: Option<u64>,
+}
+
+/// The progress of the resource currently being operated on, which resource stderr is forwarded to.
+struct ActiveProgress {
+    id: String,
+    progress_value: Progress,
+    format: ProgressFormat,
+    console_bar: Span,
+    sub_bar: Option<Span>,
+}
+
+static ACTIVE_PROGRESS: LazyLock<Mutex<Option<ActiveProgress>>> = LazyLock::new(|| Mutex::new(None));
+
+/// Forward progress reported by a resource to the progress bar of the operation invoking it.
+///
+/// If no operation is showing progress, the progress is only traced.
+///
+/// # Arguments
+///
+/// * `process_id` - The process ID of the resource reporting progress
+/// * `resource_progress` - The progress reported by the resource
+///
+pub fn report_resource_progress(process_id: u32, resource_progress: ResourceProgress) {
+    let percent = if resource_progress.percent.is_nan() { 0.0 } else { resource_progress.percent.clamp(0.0, 100.0) };
+    let resource_progress = ResourceProgress {
+        percent,
+        ..resource_progress
+    };
+    debug!("{}", t!("progress.resourceProgress", pid = process_id, percent = resource_progress.percent, activity = resource_progress.activity.clone().unwrap_or_default()));
+    let Ok(mut active) = ACTIVE_PROGRESS.lock() else {
+        return;
+    };
+    let Some(active) = active.as_mut() else {
+        return;
+    };
+    match active.format {
+        ProgressFormat::Json => {
+            active.progress_value.sub_progress = Some(resource_progress);
+            write_json(&active.progress_value);
+        },
+        ProgressFormat::Default => {
+            let sub_bar = active.sub_bar.get_or_insert_with(|| {
+                let bar = warn_span!(parent: &active.console_bar, "");
+                if let Ok(style) = ProgressStyle::with_template("  [{bar:40.green/blue}] {pos:>3}% {msg:.yellow}") {
+                    bar.pb_set_style(&style);
+                }
+                bar.pb_set_length(100);
+                {
+                    let _guard = bar.enter();
+                }
+                bar
+            });
+            sub_bar.pb_set_position(resource_progress.percent.round() as u64);
+            if let Some(activity) = resource_progress.activity.as_deref() {
+                sub_bar.pb_set_message(activity);
+            }
+        },
+        ProgressFormat::None => {}
+    }
+}
+
+fn write_json(progress_value: &Progress) {
+    if let Ok(json) = serde_json::to_string(progress_value) {
+        eprintln!("{json}");
+    } else {
+        trace!("{}", t!("progress.failedToSerialize", json = progress_value : {:?}));
+    }
 }
 
 impl Progress {
@@ -76,7 +169,9 @@ impl Progress {
 pub struct ProgressBar {
     progress_value:  Progress,
     console_bar: Span,
-    format: ProgressFormat
+    format: ProgressFormat,
+    /// The progress bar resource progress was forwarded to before this one, restored when this one stops.
+    previous_progress: Option<ActiveProgress>,
 }
 
 impl ProgressBar {
@@ -109,7 +204,8 @@ impl ProgressBar {
         Ok(ProgressBar {
             progress_value: Progress::new(total_items),
             console_bar: bar,
-            format
+            format,
+            previous_progress: None,
         })
     }
 
@@ -124,7 +220,9 @@ impl ProgressBar {
             return;
         }
 
+        self.clear_resource_progress();
         self.progress_value.completed_items += delta;
+        self.progress_value.sub_progress = None;
         if self.format == ProgressFormat::Json {
             self.write_json();
         } else {
@@ -145,9 +243,37 @@ impl ProgressBar {
         self.progress_value.resource_type = Some(resource_type.clone());
         self.progress_value.result = None;
         self.progress_value.failure = None;
+        self.progress_value.sub_progress = None;
+        if self.format == ProgressFormat::None {
+            return;
+        }
+        if let Ok(mut active) = ACTIVE_PROGRESS.lock() {
+            let previous = active.replace(ActiveProgress {
+                id: self.progress_value.id.clone(),
+                progress_value: self.progress_value.clone(),
+                format: self.format,
+                console_bar: self.console_bar.clone(),
+                sub_bar: None,
+            });
+            // a nested operation takes over from the outer progress bar until it stops
+            if let Some(previous) = previous
+                && previous.id != self.progress_value.id {
+                self.previous_progress = Some(previous);
+            }
+        }
+    }
+
+    /// Stop forwarding resource progress to this progress bar and resume forwarding it to the
+    /// progress bar that was active before.
+    fn clear_resource_progress(&mut self) {
+        if let Ok(mut active) = ACTIVE_PROGRESS.lock()
+            && active.as_ref().is_some_and(|active| active.id == self.progress_value.id) {
+            *active = self.previous_progress.take();
+        }
     }
 
     /// Set the result of the operation. This will clear any error.
+    /// Secure values in the result are redacted.
     ///
     /// # Arguments
     ///
@@ -155,7 +281,7 @@ impl ProgressBar {
     ///
     pub fn set_result(&mut self, result: &Value) {
         self.progress_value.failure = None;
-        self.progress_value.result = Some(result.clone());
+        self.progress_value.result = Some(redact(result));
     }
 
     /// Indicate that the operation failed.  This will clear any result.
@@ -203,10 +329,48 @@ impl ProgressBar {
     }
 
     fn write_json(&mut self) {
-        if let Ok(json) = serde_json::to_string(&self.progress_value) {
-            eprintln!("{json}");
-        } else {
-            trace!("{}", t!("progress.failedToSerialize", json = self.progress_value : {:?}));
+        write_json(&self.progress_value);
+    }
+}
+
+impl Drop for ProgressBar {
+    fn drop(&mut self) {
+        self.clear_resource_progress();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn active_id() -> Option<String> {
+        ACTIVE_PROGRESS.lock().unwrap().as_ref().map(|active| active.id.clone())
+    }
+
+    #[test]
+    fn resource_progress_accepts_fractional_percent() {
+        let progress = serde_json::from_str::<ResourceProgress>(r#"{"percent": 40.5, "activity": "Installing"}"#).unwrap();
+        assert!((progress.percent - 40.5).abs() < f64::EPSILON);
+        assert!(serde_json::from_str::<ResourceProgress>(r#"{"percent": 40, "unknown": true}"#).is_err());
+    }
+
+    #[test]
+    fn nested_progress_restores_outer_progress() {
+        let resource_type = FullyQualifiedTypeName::parse("Test/Progress").unwrap();
+        let mut outer = ProgressBar::new(1, ProgressFormat::Json).unwrap();
+        outer.set_resource("Outer", &resource_type);
+        assert_eq!(active_id(), Some(outer.progress_value.id.clone()));
+        {
+            let mut inner = ProgressBar::new(2, ProgressFormat::Json).unwrap();
+            inner.set_resource("Inner", &resource_type);
+            assert_eq!(active_id(), Some(inner.progress_value.id.clone()));
+            inner.write_increment(1);
+            assert_eq!(active_id(), Some(outer.progress_value.id.clone()));
+            inner.set_resource("Inner2", &resource_type);
+            assert_eq!(active_id(), Some(inner.progress_value.id.clone()));
         }
+        assert_eq!(active_id(), Some(outer.progress_value.id.clone()));
+        outer.write_increment(1);
+        assert_eq!(active_id(), None);
     }
 }
@@ -2,14 +2,19 @@
 // Licensed under the MIT License.
 
 use crate::dscerror::DscError;
+use crate::dscresources::resource_manifest::ExecutableDigest;
 use rust_i18n::t;
+use serde::Deserialize;
 use serde_json::Value;
+use sha2::{Digest, Sha256};
 use std::{
+    fmt::Write,
     fs,
     fs::{canonicalize, File},
     io::BufReader,
     path::{Path, PathBuf},
     env,
+    sync::{LazyLock, PoisonError, RwLock},
 };
 use tracing::{debug, warn};
 use which::which;
@@ -21,6 +26,17 @@ pub struct DscSettingValue {
     pub policy: Value,
 }
 
+/// The `executableIntegrity` setting read by the last discovery, or the error if it's malformed.
+static EXECUTABLE_INTEGRITY_SETTING: LazyLock<RwLock<Option<Result<ExecutableIntegritySetting, String>>>> = LazyLock::new(|| RwLock::new(None));
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExecutableIntegritySetting {
+    /// whether every executable run for a manifest must have a `sha256` digest in the manifest
+    #[serde(rename = "requirePinning")]
+    pub require_pinning: bool,
+}
+
 impl Default for DscSettingValue {
     fn default() -> DscSettingValue {
         DscSettingValue {
@@ -110,6 +126,38 @@ mod tests {
         assert_eq!(regex, r"^a\+b\(c\)\[d\]\^e\$f\|g\\h$");
     }
 
+    #[test]
+    fn test_verify_executable_digest() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let path = dir.path().join("executable");
+        fs::write(&path, "abc").expect("write test file");
+        let executable = path.to_string_lossy().to_string();
+        let expected = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+        assert_eq!(get_file_sha256(&path).unwrap(), expected);
+
+        let pinned = ExecutableDigest::Multiple(vec!["0".repeat(64), expected.to_uppercase()]);
+        assert!(verify_executable_digest(&executable, Some(&pinned)).is_ok());
+
+        let pinned = ExecutableDigest::Single("0".repeat(64));
+        let result = verify_executable_digest(&executable, Some(&pinned));
+        assert!(matches!(result, Err(DscError::ExecutableHashMismatch(_, digest)) if digest == expected));
+    }
+
+    #[test]
+    fn test_parse_executable_integrity_setting() {
+        let value = DscSettingValue { setting: serde_json::json!({"requirePinning": false}), policy: serde_json::json!({"requirePinning": true}) };
+        assert!(parse_executable_integrity_setting(value).unwrap().require_pinning);
+
+        let value = DscSettingValue::default();
+        assert!(!parse_executable_integrity_setting(value).unwrap().require_pinning);
+
+        let value = DscSettingValue { setting: serde_json::json!({"requirePining": true}), policy: Value::Null };
+        assert!(matches!(parse_executable_integrity_setting(value), Err(DscError::Setting(_))));
+
+        let value = DscSettingValue { setting: Value::Null, policy: serde_json::json!({"requirePinning": "yes"}) };
+        assert!(matches!(parse_executable_integrity_setting(value), Err(DscError::Setting(_))));
+    }
+
     #[cfg(not(target_os = "windows"))]
     mod linux_permission_tests {
         use super::*;
@@ -477,6 +525,96 @@ pub fn canonicalize_which(executable: &str, cwd: Option<&Path>) -> Result<String
     Ok(executable.to_string())
 }
 
+/// Verify the executable matches the SHA-256 digests pinned in a manifest.
+///
+/// If no digest is pinned, the executable is only allowed to run if the `executableIntegrity`
+/// setting doesn't require pinning. No executable is allowed to run if the setting is malformed.
+///
+/// # Arguments
+///
+/// * `executable` - The executable as returned by `canonicalize_which()`
+/// * `sha256` - The digests allowed for the executable
+///
+/// # Errors
+///
+/// Will return `Err` if the setting is malformed, the executable can't be read, the digest doesn't
+/// match, or a digest is required but not pinned.
+pub fn verify_executable_digest(executable: &str, sha256: Option<&ExecutableDigest>) -> Result<(), DscError> {
+    let setting = get_executable_integrity_setting()?;
+    let Some(sha256) = sha256 else {
+        if setting.require_pinning {
+            return Err(DscError::ExecutableNotPinned(executable.to_string()));
+        }
+        return Ok(());
+    };
+
+    let path = if Path::new(executable).is_absolute() {
+        PathBuf::from(executable)
+    } else {
+        match which(executable) {
+            Ok(path) => path,
+            Err(_) => return Err(DscError::CommandOperation(t!("util.executableNotFound", executable = executable).to_string(), executable.to_string())),
+        }
+    };
+    let digest = get_file_sha256(&path)?;
+    if !sha256.matches(&digest) {
+        return Err(DscError::ExecutableHashMismatch(path.to_string_lossy().to_string(), digest));
+    }
+    debug!("{}", t!("util.executableDigestVerified", executable = path.display(), digest = digest));
+    Ok(())
+}
+
+/// Get the SHA-256 digest of a file as a lowercase hexadecimal string.
+///
+/// # Errors
+///
+/// Will return `Err` if the file can't be read.
+pub fn get_file_sha256(path: &Path) -> Result<String, DscError> {
+    let digest = Sha256::digest(fs::read(path)?);
+    let mut hex = String::with_capacity(digest.len() * 2);
+    for byte in digest {
+        let _ = write!(hex, "{byte:02x}");
+    }
+    Ok(hex)
+}
+
+/// Read the `executableIntegrity` setting so executables launched after a discovery are verified
+/// against the setting read by that discovery.
+pub(crate) fn load_executable_integrity_setting() {
+    let setting = read_executable_integrity_setting().map_err(|err| err.to_string());
+    *EXECUTABLE_INTEGRITY_SETTING.write().unwrap_or_else(PoisonError::into_inner) = Some(setting);
+}
+
+fn get_executable_integrity_setting() -> Result<ExecutableIntegritySetting, DscError> {
+    if EXECUTABLE_INTEGRITY_SETTING.read().unwrap_or_else(PoisonError::into_inner).is_none() {
+        load_executable_integrity_setting();
+    }
+    match EXECUTABLE_INTEGRITY_SETTING.read().unwrap_or_else(PoisonError::into_inner).clone() {
+        Some(Ok(setting)) => Ok(setting),
+        Some(Err(err)) => Err(DscError::Setting(err)),
+        None => Ok(ExecutableIntegritySetting::default()),
+    }
+}
+
+fn read_executable_integrity_setting() -> Result<ExecutableIntegritySetting, DscError> {
+    let Ok(value) = get_setting("executableIntegrity") else {
+        return Ok(ExecutableIntegritySetting::default());
+    };
+    parse_executable_integrity_setting(value)
+}
+
+/// Parse the `executableIntegrity` setting, the policy takes precedence over the settings file.
+fn parse_executable_integrity_setting(value: DscSettingValue) -> Result<ExecutableIntegritySetting, DscError> {
+    let setting = if value.policy.is_null() { value.setting } else { value.policy };
+    if setting.is_null() {
+        return Ok(ExecutableIntegritySetting::default());
+    }
+    // fail closed so a malformed setting or policy doesn't silently allow unpinned executables
+    serde_json::from_value::<ExecutableIntegritySetting>(setting).map_err(|err| {
+        DscError::Setting(t!("util.invalidExecutableIntegritySetting", error = err).to_string())
+    })
+}
+
 #[macro_export]
 macro_rules! locked_clear {
     ($lockable:expr) => {{
@@ -49,6 +49,7 @@ mod invoke_command {
             None,
             None,
             &exit_codes,
+            None,
         ).expect("invoke_command should succeed");
 
         let (exit_code, stdout, _stderr) = result;
@@ -64,6 +64,11 @@ macro_rules! test_schema_for {
             test_schema_for!(dsc_lib::configure::config_result::ConfigurationExportResult);
         }
         #[allow(unused_must_use)]
+        #[cfg(test)] mod function_library {
+            test_schema_for!(dsc_lib::configure::function_library::FunctionImport);
+            test_schema_for!(dsc_lib::configure::function_library::FunctionLibrary);
+        }
+        #[allow(unused_must_use)]
         #[cfg(test)] mod parameters {
             test_schema_for!(dsc_lib::configure::parameters::SimpleInput);
             test_schema_for!(dsc_lib::configure::parameters::SecureString);
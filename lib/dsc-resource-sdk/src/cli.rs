@@ -0,0 +1,163 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
+use rust_i18n::t;
+use serde::Serialize;
+use serde_json::Value;
+use std::{io::{IsTerminal, Read}, process::exit};
+use tracing::{debug, error};
+
+use crate::{
+    manifest::{generate_manifest, get_schema},
+    trace::{enable_tracing, TraceFormat, TraceLevel},
+    DscResource, Operation, ResourceError, EXIT_INVALID_ARGS, IN_DESIRED_STATE,
+};
+
+#[derive(Parser)]
+struct Args {
+    #[clap(subcommand)]
+    operation: OperationCommand,
+    #[clap(short = 'l', long, help = t!("args.traceLevel").to_string(), value_enum)]
+    trace_level: Option<TraceLevel>,
+    #[clap(short = 'f', long, help = t!("args.traceFormat").to_string(), value_enum, default_value = "json")]
+    trace_format: TraceFormat,
+}
+
+#[derive(Subcommand)]
+enum OperationCommand {
+    #[clap(name = "get", about = t!("args.getAbout").to_string())]
+    Get {
+        #[clap(short = 'i', long, help = t!("args.input").to_string())]
+        input: Option<String>,
+    },
+    #[clap(name = "set", about = t!("args.setAbout").to_string())]
+    Set {
+        #[clap(short = 'i', long, help = t!("args.input").to_string())]
+        input: Option<String>,
+        #[clap(short = 'w', long = "what-if", help = t!("args.whatIf").to_string())]
+        what_if: bool,
+    },
+    #[clap(name = "test", about = t!("args.testAbout").to_string())]
+    Test {
+        #[clap(short = 'i', long, help = t!("args.input").to_string())]
+        input: Option<String>,
+    },
+    #[clap(name = "delete", about = t!("args.deleteAbout").to_string())]
+    Delete {
+        #[clap(short = 'i', long, help = t!("args.input").to_string())]
+        input: Option<String>,
+    },
+    #[clap(name = "export", about = t!("args.exportAbout").to_string())]
+    Export {
+        #[clap(short = 'i', long, help = t!("args.input").to_string())]
+        input: Option<String>,
+    },
+    #[clap(name = "schema", about = t!("args.schemaAbout").to_string())]
+    Schema,
+    #[clap(name = "manifest", about = t!("args.manifestAbout").to_string())]
+    Manifest,
+}
+
+/// Run the command line of a resource and exit the process.
+///
+/// The input of an operation is read from the `--input` argument or STDIN, the result is written
+/// to STDOUT as JSON and traces are written to STDERR.
+pub fn run<R: DscResource>() -> ! {
+    let command = Args::command()
+        .name(R::EXECUTABLE)
+        .version(R::VERSION)
+        .about(R::DESCRIPTION);
+    let args = match Args::from_arg_matches(&command.get_matches()) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("{err}");
+            exit(EXIT_INVALID_ARGS);
+        }
+    };
+    enable_tracing(args.trace_level.as_ref(), &args.trace_format);
+
+    let result = match args.operation {
+        OperationCommand::Get { input } => invoke::<R>(Operation::Get, input, |input| {
+            write_json(&R::get(input)?)
+        }),
+        OperationCommand::Set { input, what_if } => invoke::<R>(Operation::Set, input, |input| {
+            let desired = require_input(input)?;
+            write_json(&R::set(desired, what_if)?)
+        }),
+        OperationCommand::Test { input } => invoke::<R>(Operation::Test, input, |input| {
+            let desired = require_input(input)?;
+            let response = R::test(desired)?;
+            let mut actual_state = serde_json::to_value(&response.actual_state)?;
+            if let Some(properties) = actual_state.as_object_mut() {
+                properties.insert(IN_DESIRED_STATE.to_string(), Value::Bool(response.in_desired_state));
+            }
+            write_json(&actual_state)
+        }),
+        OperationCommand::Delete { input } => invoke::<R>(Operation::Delete, input, |input| {
+            R::delete(require_input(input)?)
+        }),
+        OperationCommand::Export { input } => invoke::<R>(Operation::Export, input, |input| {
+            for instance in R::export(input)? {
+                write_json(&instance)?;
+            }
+            Ok(())
+        }),
+        OperationCommand::Schema => write_json(&get_schema::<R>()),
+        OperationCommand::Manifest => {
+            match serde_json::to_string_pretty(&generate_manifest::<R>()) {
+                Ok(json) => {
+                    println!("{json}");
+                    Ok(())
+                },
+                Err(err) => Err(err.into()),
+            }
+        },
+    };
+
+    match result {
+        Ok(()) => exit(crate::EXIT_SUCCESS),
+        Err(err) => {
+            error!("{err}");
+            exit(err.exit_code());
+        }
+    }
+}
+
+fn invoke<R: DscResource>(operation: Operation, input: Option<String>, action: impl FnOnce(Option<R::Instance>) -> Result<(), ResourceError>) -> Result<(), ResourceError> {
+    if !R::OPERATIONS.contains(&operation) {
+        return Err(ResourceError::NotImplemented(format!("{operation:?}").to_lowercase()));
+    }
+    let input = match input {
+        Some(input) => input,
+        None => read_stdin()?,
+    };
+    if input.trim().is_empty() {
+        return action(None);
+    }
+    debug!("{}", t!("cli.parsingInput"));
+    match serde_json::from_str::<R::Instance>(&input) {
+        Ok(instance) => action(Some(instance)),
+        Err(err) => Err(ResourceError::InvalidInput(err.to_string())),
+    }
+}
+
+fn read_stdin() -> Result<String, ResourceError> {
+    let mut stdin = std::io::stdin();
+    let mut input = String::new();
+    if !stdin.is_terminal() {
+        debug!("{}", t!("cli.readingStdin"));
+        stdin.read_to_string(&mut input)?;
+    }
+    Ok(input)
+}
+
+fn require_input<T>(input: Option<T>) -> Result<T, ResourceError> {
+    input.ok_or_else(|| ResourceError::InvalidInput(t!("cli.inputRequired").to_string()))
+}
+
+fn write_json<T: Serialize>(value: &T) -> Result<(), ResourceError> {
+    let json = serde_json::to_string(value)?;
+    println!("{json}");
+    Ok(())
+}
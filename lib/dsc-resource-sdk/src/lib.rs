@@ -0,0 +1,161 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+//! SDK for writing DSC command resources in Rust.
+//!
+//! A resource implements the [`DscResource`] trait for a type describing its instances and calls
+//! [`run`] from `main`. The SDK provides the command line, reads the JSON input, handles tracing
+//! and exit codes, and writes the results in the shape DSC expects. The manifest of the resource
+//! is generated from the same implementation with the `manifest` subcommand.
+
+use rust_i18n::t;
+use schemars::{JsonSchema, schema_for};
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::Value;
+use thiserror::Error;
+
+mod cli;
+pub mod manifest;
+pub mod trace;
+
+pub use cli::run;
+
+rust_i18n::i18n!("locales", fallback = "en-us");
+
+/// The property DSC reads to determine whether a tested instance is in the desired state.
+pub const IN_DESIRED_STATE: &str = "_inDesiredState";
+/// The property indicating whether an instance should exist.
+pub const EXIST: &str = "_exist";
+/// The property containing the name of an exported instance.
+pub const NAME: &str = "_name";
+
+pub const EXIT_SUCCESS: i32 = 0;
+pub const EXIT_INVALID_ARGS: i32 = 1;
+pub const EXIT_INVALID_INPUT: i32 = 2;
+pub const EXIT_RESOURCE_ERROR: i32 = 3;
+pub const EXIT_NOT_IMPLEMENTED: i32 = 4;
+pub const EXIT_JSON_ERROR: i32 = 5;
+
+#[derive(Error, Debug)]
+pub enum ResourceError {
+    #[error("{t}: {0}", t = t!("error.invalidInput"))]
+    InvalidInput(String),
+
+    #[error("{t}: {0}", t = t!("error.io"))]
+    Io(#[from] std::io::Error),
+
+    #[error("{t}: {0}", t = t!("error.json"))]
+    Json(#[from] serde_json::Error),
+
+    #[error("{t}: {0}", t = t!("error.notImplemented"))]
+    NotImplemented(String),
+
+    #[error("{0}")]
+    Operation(String),
+}
+
+impl ResourceError {
+    /// The exit code the resource returns for the error.
+    #[must_use]
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            ResourceError::InvalidInput(_) => EXIT_INVALID_INPUT,
+            ResourceError::Json(_) => EXIT_JSON_ERROR,
+            ResourceError::NotImplemented(_) => EXIT_NOT_IMPLEMENTED,
+            ResourceError::Io(_) | ResourceError::Operation(_) => EXIT_RESOURCE_ERROR,
+        }
+    }
+}
+
+/// The operations a resource implements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    Get,
+    Set,
+    Test,
+    Delete,
+    Export,
+}
+
+/// The result of testing an instance.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestResponse<T> {
+    /// The actual state of the instance.
+    pub actual_state: T,
+    /// Whether the actual state matches the desired state.
+    pub in_desired_state: bool,
+}
+
+/// A DSC command resource.
+///
+/// Only the operations listed in `OPERATIONS` are exposed on the command line and in the
+/// generated manifest, the default implementations return `ResourceError::NotImplemented`.
+pub trait DscResource {
+    /// The properties of an instance of the resource.
+    type Instance: Serialize + DeserializeOwned + JsonSchema;
+
+    /// The fully qualified type name of the resource.
+    const TYPE_NAME: &'static str;
+    /// The semantic version of the resource.
+    const VERSION: &'static str;
+    /// A short description of the resource.
+    const DESCRIPTION: &'static str;
+    /// The name of the executable implementing the resource.
+    const EXECUTABLE: &'static str;
+    /// The operations the resource implements.
+    const OPERATIONS: &'static [Operation];
+    /// The tags describing the resource.
+    const TAGS: &'static [&'static str] = &[];
+    /// Whether the set operation supports reporting what would change.
+    const SUPPORTS_WHAT_IF: bool = false;
+
+    /// Get the actual state of an instance.
+    ///
+    /// # Errors
+    ///
+    /// Error if the actual state can't be retrieved.
+    fn get(_filter: Option<Self::Instance>) -> Result<Self::Instance, ResourceError> {
+        Err(ResourceError::NotImplemented("get".to_string()))
+    }
+
+    /// Set an instance to the desired state and return the resulting state.
+    ///
+    /// # Errors
+    ///
+    /// Error if the desired state can't be applied.
+    fn set(_desired: Self::Instance, _what_if: bool) -> Result<Self::Instance, ResourceError> {
+        Err(ResourceError::NotImplemented("set".to_string()))
+    }
+
+    /// Test whether an instance is in the desired state.
+    ///
+    /// # Errors
+    ///
+    /// Error if the actual state can't be retrieved.
+    fn test(_desired: Self::Instance) -> Result<TestResponse<Self::Instance>, ResourceError> {
+        Err(ResourceError::NotImplemented("test".to_string()))
+    }
+
+    /// Delete an instance.
+    ///
+    /// # Errors
+    ///
+    /// Error if the instance can't be deleted.
+    fn delete(_instance: Self::Instance) -> Result<(), ResourceError> {
+        Err(ResourceError::NotImplemented("delete".to_string()))
+    }
+
+    /// Export all instances matching the optional filter.
+    ///
+    /// # Errors
+    ///
+    /// Error if the instances can't be enumerated.
+    fn export(_filter: Option<Self::Instance>) -> Result<Vec<Self::Instance>, ResourceError> {
+        Err(ResourceError::NotImplemented("export".to_string()))
+    }
+
+    /// The JSON schema of an instance, generated from `Instance` unless overridden.
+    fn schema() -> Value {
+        serde_json::to_value(schema_for!(Self::Instance)).unwrap_or_default()
+    }
+}
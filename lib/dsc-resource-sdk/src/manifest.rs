@@ -0,0 +1,192 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use rust_i18n::t;
+use serde_json::{json, Map, Value};
+
+use crate::{
+    DscResource, Operation, EXIT_INVALID_ARGS, EXIT_INVALID_INPUT, EXIT_JSON_ERROR, EXIT_NOT_IMPLEMENTED,
+    EXIT_RESOURCE_ERROR, EXIT_SUCCESS, IN_DESIRED_STATE,
+};
+
+const MANIFEST_SCHEMA: &str = "https://aka.ms/dsc/schemas/v3/bundled/resource/manifest.json";
+const INPUT_ARG: &str = "--input";
+const WHAT_IF_ARG: &str = "--what-if";
+
+/// The declarations of a resource its manifest is generated from.
+///
+/// These are the constants of [`DscResource`], for callers that only know them at runtime like the
+/// scaffolding of a new resource project.
+#[derive(Debug, Clone)]
+pub struct ManifestInfo<'a> {
+    pub type_name: &'a str,
+    pub version: &'a str,
+    pub description: &'a str,
+    pub executable: &'a str,
+    pub operations: &'a [Operation],
+    pub tags: &'a [&'a str],
+    pub supports_what_if: bool,
+}
+
+impl ManifestInfo<'static> {
+    /// Get the declarations of a resource implementing [`DscResource`].
+    #[must_use]
+    pub fn of<R: DscResource>() -> Self {
+        ManifestInfo {
+            type_name: R::TYPE_NAME,
+            version: R::VERSION,
+            description: R::DESCRIPTION,
+            executable: R::EXECUTABLE,
+            operations: R::OPERATIONS,
+            tags: R::TAGS,
+            supports_what_if: R::SUPPORTS_WHAT_IF,
+        }
+    }
+}
+
+/// Generate the resource manifest for a resource.
+///
+/// The manifest invokes the command line provided by [`crate::run`] for each implemented operation
+/// and embeds the schema of the resource.
+#[must_use]
+pub fn generate_manifest<R: DscResource>() -> Value {
+    generate_manifest_from(&ManifestInfo::of::<R>(), R::schema())
+}
+
+/// Generate the resource manifest from the declarations of a resource and the schema of an instance.
+#[must_use]
+pub fn generate_manifest_from(info: &ManifestInfo<'_>, schema: Value) -> Value {
+    let mut manifest = Map::new();
+    manifest.insert("$schema".to_string(), Value::String(MANIFEST_SCHEMA.to_string()));
+    manifest.insert("type".to_string(), Value::String(info.type_name.to_string()));
+    manifest.insert("version".to_string(), Value::String(info.version.to_string()));
+    manifest.insert("description".to_string(), Value::String(info.description.to_string()));
+    if !info.tags.is_empty() {
+        manifest.insert("tags".to_string(), json!(info.tags));
+    }
+
+    for operation in info.operations {
+        let (name, method) = match operation {
+            Operation::Get => ("get", json!({
+                "executable": info.executable,
+                "args": ["get", { "jsonInputArg": INPUT_ARG, "mandatory": true }],
+            })),
+            Operation::Set => {
+                let mut args = vec![json!("set"), json!({ "jsonInputArg": INPUT_ARG, "mandatory": true })];
+                if info.supports_what_if {
+                    args.push(json!({ "whatIfArg": WHAT_IF_ARG }));
+                }
+                ("set", json!({
+                    "executable": info.executable,
+                    "args": args,
+                    "return": "state",
+                }))
+            },
+            Operation::Test => ("test", json!({
+                "executable": info.executable,
+                "args": ["test", { "jsonInputArg": INPUT_ARG, "mandatory": true }],
+                "return": "state",
+            })),
+            Operation::Delete => ("delete", json!({
+                "executable": info.executable,
+                "args": ["delete", { "jsonInputArg": INPUT_ARG, "mandatory": true }],
+            })),
+            Operation::Export => ("export", json!({
+                "executable": info.executable,
+                "args": ["export", { "jsonInputArg": INPUT_ARG }],
+            })),
+        };
+        manifest.insert(name.to_string(), method);
+    }
+
+    manifest.insert("exitCodes".to_string(), json!({
+        EXIT_SUCCESS.to_string(): t!("manifest.exitSuccess"),
+        EXIT_INVALID_ARGS.to_string(): t!("manifest.exitInvalidArgs"),
+        EXIT_INVALID_INPUT.to_string(): t!("manifest.exitInvalidInput"),
+        EXIT_RESOURCE_ERROR.to_string(): t!("manifest.exitResourceError"),
+        EXIT_NOT_IMPLEMENTED.to_string(): t!("manifest.exitNotImplemented"),
+        EXIT_JSON_ERROR.to_string(): t!("manifest.exitJsonError"),
+    }));
+    manifest.insert("schema".to_string(), json!({ "embedded": add_in_desired_state(schema, info.operations) }));
+    Value::Object(manifest)
+}
+
+/// Get the schema of a resource, allowing `_inDesiredState` in the output of the test operation.
+#[must_use]
+pub fn get_schema<R: DscResource>() -> Value {
+    add_in_desired_state(R::schema(), R::OPERATIONS)
+}
+
+fn add_in_desired_state(mut schema: Value, operations: &[Operation]) -> Value {
+    if operations.contains(&Operation::Test)
+        && let Some(properties) = schema.get_mut("properties").and_then(Value::as_object_mut)
+        && !properties.contains_key(IN_DESIRED_STATE) {
+        properties.insert(IN_DESIRED_STATE.to_string(), json!({
+            "type": "boolean",
+            "readOnly": true,
+            "description": t!("manifest.inDesiredStateDescription"),
+        }));
+    }
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use schemars::JsonSchema;
+    use serde::{Deserialize, Serialize};
+    use serde_json::Value;
+
+    use crate::{DscResource, Operation, ResourceError, TestResponse};
+    use super::{generate_manifest, generate_manifest_from, ManifestInfo};
+
+    #[derive(Debug, Default, Deserialize, Serialize, JsonSchema)]
+    #[serde(deny_unknown_fields)]
+    struct Instance {
+        name: Option<String>,
+    }
+
+    struct TestResource;
+
+    impl DscResource for TestResource {
+        type Instance = Instance;
+        const TYPE_NAME: &'static str = "Test/Sdk";
+        const VERSION: &'static str = "0.1.0";
+        const DESCRIPTION: &'static str = "Test resource";
+        const EXECUTABLE: &'static str = "sdktest";
+        const OPERATIONS: &'static [Operation] = &[Operation::Get, Operation::Set, Operation::Test];
+        const SUPPORTS_WHAT_IF: bool = true;
+
+        fn get(_filter: Option<Instance>) -> Result<Instance, ResourceError> {
+            Ok(Instance::default())
+        }
+
+        fn test(desired: Instance) -> Result<TestResponse<Instance>, ResourceError> {
+            Ok(TestResponse { actual_state: desired, in_desired_state: true })
+        }
+    }
+
+    #[test]
+    fn manifest_contains_implemented_operations() {
+        let manifest = generate_manifest::<TestResource>();
+        assert_eq!(manifest["type"], "Test/Sdk");
+        assert_eq!(manifest["get"]["args"][0], "get");
+        assert_eq!(manifest["set"]["args"][2]["whatIfArg"], "--what-if");
+        assert_eq!(manifest["test"]["return"], "state");
+        assert!(manifest.get("delete").is_none());
+        assert!(manifest.get("export").is_none());
+    }
+
+    #[test]
+    fn schema_allows_in_desired_state() {
+        let manifest = generate_manifest::<TestResource>();
+        let properties = &manifest["schema"]["embedded"]["properties"];
+        assert!(properties.get("name").is_some());
+        assert_eq!(properties["_inDesiredState"]["type"], Value::String("boolean".to_string()));
+    }
+
+    #[test]
+    fn manifest_from_info_matches_resource() {
+        let info = ManifestInfo::of::<TestResource>();
+        assert_eq!(generate_manifest_from(&info, TestResource::schema()), generate_manifest::<TestResource>());
+    }
+}
@@ -0,0 +1,107 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use clap::ValueEnum;
+use rust_i18n::t;
+use tracing::Level;
+use tracing_subscriber::{filter::EnvFilter, layer::SubscriberExt, Layer};
+
+/// The environment variable DSC uses to pass its trace level to resources.
+pub const DSC_TRACE_LEVEL: &str = "DSC_TRACE_LEVEL";
+
+#[derive(Debug, Clone, PartialEq, Eq, ValueEnum)]
+pub enum TraceFormat {
+    Default,
+    Plaintext,
+    Json,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, ValueEnum)]
+pub enum TraceLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl From<&TraceLevel> for Level {
+    fn from(trace_level: &TraceLevel) -> Self {
+        match trace_level {
+            TraceLevel::Error => Level::ERROR,
+            TraceLevel::Warn => Level::WARN,
+            TraceLevel::Info => Level::INFO,
+            TraceLevel::Debug => Level::DEBUG,
+            TraceLevel::Trace => Level::TRACE,
+        }
+    }
+}
+
+/// Get the trace level from the `DSC_TRACE_LEVEL` environment variable, defaulting to `Info`.
+#[must_use]
+pub fn get_env_trace_level() -> TraceLevel {
+    let Ok(trace_level) = std::env::var(DSC_TRACE_LEVEL) else {
+        return TraceLevel::Info;
+    };
+    match trace_level.to_lowercase().as_str() {
+        "error" => TraceLevel::Error,
+        "warn" => TraceLevel::Warn,
+        "info" => TraceLevel::Info,
+        "debug" => TraceLevel::Debug,
+        "trace" => TraceLevel::Trace,
+        _ => {
+            eprintln!("{}: {trace_level}", t!("trace.invalidTraceLevel"));
+            TraceLevel::Info
+        }
+    }
+}
+
+/// Setup tracing to STDERR so DSC can read the traces of the resource.
+///
+/// # Arguments
+///
+/// * `trace_level` - The level of information to output, if not specified the level DSC passed
+///   in `DSC_TRACE_LEVEL` is used
+/// * `trace_format` - The format of the output
+pub fn enable_tracing(trace_level: Option<&TraceLevel>, trace_format: &TraceFormat) {
+    let tracing_level = match trace_level {
+        Some(trace_level) => Level::from(trace_level),
+        None => Level::from(&get_env_trace_level()),
+    };
+
+    let filter = EnvFilter::try_from_default_env()
+        .or_else(|_| EnvFilter::try_new("warn"))
+        .unwrap_or_default()
+        .add_directive(tracing_level.into());
+    let layer = tracing_subscriber::fmt::Layer::default().with_writer(std::io::stderr);
+    let fmt = match trace_format {
+        TraceFormat::Default => {
+            layer
+                .with_ansi(true)
+                .with_level(true)
+                .with_line_number(true)
+                .boxed()
+        },
+        TraceFormat::Plaintext => {
+            layer
+                .with_ansi(false)
+                .with_level(true)
+                .with_line_number(false)
+                .boxed()
+        },
+        TraceFormat::Json => {
+            layer
+                .with_ansi(false)
+                .with_level(true)
+                .with_line_number(true)
+                .json()
+                .boxed()
+        }
+    };
+
+    let subscriber = tracing_subscriber::Registry::default().with(fmt).with(filter);
+
+    if tracing::subscriber::set_global_default(subscriber).is_err() {
+        eprintln!("{}", t!("trace.unableToTrace"));
+    }
+}
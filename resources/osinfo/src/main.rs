@@ -1,45 +1,44 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
-use dsc_lib_osinfo::{perform_test, OsInfo};
-use std::io::Read;
+use dsc_lib_osinfo::{test_os_info, OsInfo};
+use dsc_resource_sdk::{run, DscResource, Operation, ResourceError, TestResponse};
+use schemars::generate::SchemaSettings;
+use serde_json::Value;
 
-fn main() {
-    let args: Vec<String> = std::env::args().collect();
-    match args.get(1).map(String::as_str) {
-        Some("export") => {
-            let json = serde_json::to_string(&OsInfo::new(true)).unwrap_or_else(|e| {
-                eprintln!("Failed to serialize OS info as JSON: {e}");
-                std::process::exit(1);
-            });
-            println!("{json}");
-        },
-        Some("test") => {
-            let mut input = String::new();
-            if let Err(e) = std::io::stdin().read_to_string(&mut input) {
-                eprintln!("Failed to read stdin: {e}");
-                std::process::exit(1);
-            }
-            match perform_test(&input) {
-                Ok(result) => {
-                    let json = serde_json::to_string(&result).unwrap_or_else(|e| {
-                        eprintln!("Failed to serialize test result as JSON: {e}");
-                        std::process::exit(1);
-                    });
-                    println!("{json}");
-                },
-                Err(e) => {
-                    eprintln!("{e}");
-                    std::process::exit(1);
-                },
-            }
-        },
-        _ => {
-            let json = serde_json::to_string(&OsInfo::new(false)).unwrap_or_else(|e| {
-                eprintln!("Failed to serialize OS info as JSON: {e}");
-                std::process::exit(1);
-            });
-            println!("{json}");
-        },
+struct OsInfoResource;
+
+impl DscResource for OsInfoResource {
+    type Instance = OsInfo;
+
+    const TYPE_NAME: &'static str = "Microsoft/OSInfo";
+    const VERSION: &'static str = "0.1.0";
+    const DESCRIPTION: &'static str = "Returns information about the operating system.";
+    const EXECUTABLE: &'static str = "osinfo";
+    const OPERATIONS: &'static [Operation] = &[Operation::Get, Operation::Test, Operation::Export];
+    const TAGS: &'static [&'static str] = &["os", "linux", "windows", "macos"];
+
+    fn get(_filter: Option<OsInfo>) -> Result<OsInfo, ResourceError> {
+        Ok(OsInfo::new(false))
     }
+
+    fn test(desired: OsInfo) -> Result<TestResponse<OsInfo>, ResourceError> {
+        let actual_state = test_os_info(&desired);
+        let in_desired_state = actual_state.in_desired_state().unwrap_or_default();
+        Ok(TestResponse { actual_state, in_desired_state })
+    }
+
+    fn export(_filter: Option<OsInfo>) -> Result<Vec<OsInfo>, ResourceError> {
+        Ok(vec![OsInfo::new(true)])
+    }
+
+    /// The schema is published as draft-07 without null types, as in earlier versions of the manifest.
+    fn schema() -> Value {
+        let settings = SchemaSettings::draft07();
+        serde_json::to_value(settings.into_generator().into_root_schema_for::<OsInfo>()).unwrap_or_default()
+    }
+}
+
+fn main() {
+    run::<OsInfoResource>();
 }
@@ -185,4 +185,18 @@ Describe 'osinfo test subcommand property tests' {
             $out.inDesiredState | Should -BeFalse
         }
     }
+
+    Context 'generated manifest' {
+        It 'should match the manifest shipped with the resource' {
+            $generated = osinfo manifest | ConvertFrom-Json
+            $LASTEXITCODE | Should -Be 0
+            $shipped = Get-Content -Raw -Path "$PSScriptRoot/../osinfo.dsc.resource.json" | ConvertFrom-Json
+            ($generated | ConvertTo-Json -Depth 10 -Compress) | Should -BeExactly ($shipped | ConvertTo-Json -Depth 10 -Compress)
+        }
+
+        It 'should return the exit code for invalid input' {
+            $null = osinfo test --input '{"unknown":1}' 2>$null
+            $LASTEXITCODE | Should -Be 2
+        }
+    }
 }
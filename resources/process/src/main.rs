@@ -2,9 +2,9 @@
 // Licensed under the MIT License.
 
 mod process_info;
-use std::env;
-use std::process::exit;
-use std::io::{self, Read};
+use dsc_resource_sdk::{run, DscResource, Operation, ResourceError, TestResponse};
+use schemars::schema_for;
+use serde_json::{Map, Value};
 use sysinfo::{ProcessesToUpdate, System};
 use crate::process_info::ProcessInfo;
 
@@ -30,42 +30,40 @@ fn get_task_list() -> Vec<ProcessInfo>
     result
 }
 
-fn help() {
-    println!("usage: process list");
-}
+struct ProcessResource;
 
-fn print_input() {
-    let mut buffer: Vec<u8> = Vec::new();
-    io::stdin().read_to_end(&mut buffer).unwrap();
-    let input = String::from_utf8(buffer);
-    println!("{}", input.unwrap());
-}
+// get, set, and test return the input unchanged and are used for testing only, so instances are
+// passed through as JSON instead of being restricted to the properties of `ProcessInfo`
+impl DscResource for ProcessResource {
+    type Instance = Value;
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() == 2 {
-        // one argument passed
-        match args[1].as_str() {
-            "list" => {
-                for p in get_task_list()
-                {
-                    let json = serde_json::to_string(&p).unwrap();
-                    println!("{json}");
-                }
-                exit(0);
-            },
-            "get" | "set" | "test" => { // used for testing only
-                print_input();
-                exit(0);
-            },
-            _ => {
-                help();
-                exit(1);
-            },
-        }
+    const TYPE_NAME: &'static str = "Microsoft/Process";
+    const VERSION: &'static str = "0.1.0";
+    const DESCRIPTION: &'static str = "Returns information about running processes.";
+    const EXECUTABLE: &'static str = "process";
+    const OPERATIONS: &'static [Operation] = &[Operation::Get, Operation::Set, Operation::Test, Operation::Export];
+
+    fn get(filter: Option<Value>) -> Result<Value, ResourceError> {
+        Ok(filter.unwrap_or_else(|| Value::Object(Map::new())))
+    }
+
+    fn set(desired: Value, _what_if: bool) -> Result<Value, ResourceError> {
+        Ok(desired)
+    }
+
+    fn test(desired: Value) -> Result<TestResponse<Value>, ResourceError> {
+        Ok(TestResponse { actual_state: desired, in_desired_state: true })
+    }
+
+    fn export(_filter: Option<Value>) -> Result<Vec<Value>, ResourceError> {
+        get_task_list().iter().map(|process| Ok(serde_json::to_value(process)?)).collect()
     }
-    else {
-        help();
-        exit(1);
+
+    fn schema() -> Value {
+        serde_json::to_value(schema_for!(ProcessInfo)).unwrap_or_default()
     }
-}
\ No newline at end of file
+}
+
+fn main() {
+    run::<ProcessResource>();
+}
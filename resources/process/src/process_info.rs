@@ -1,9 +1,13 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
-use serde::Serialize;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, PartialEq, Serialize)]
+/// Information about a running process.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[serde(default)]
+#[schemars(title = "Process", deny_unknown_fields)]
 pub struct ProcessInfo {
     pub pid: u32,
     pub name: String,
@@ -12,11 +16,6 @@ pub struct ProcessInfo {
 
 impl ProcessInfo {
     pub fn new() -> Self {
-        
-        Self {
-            pid: 0,
-            name: String::new(),
-            cmdline: String::new(),
-        }
+        Self::default()
     }
 }
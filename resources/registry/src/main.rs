@@ -10,11 +10,11 @@ use adapter::{adapter_export, adapter_get, adapter_set, AdaptedRegistryValue};
 use args::{AdapterSubCommand, Arguments, ConfigSubCommand, SubCommand};
 use clap::Parser;
 use dsc_lib_registry::{config::Registry, RegistryHelper};
+use dsc_resource_sdk::trace::{enable_tracing, TraceFormat, TraceLevel};
 use rust_i18n::t;
 use schemars::schema_for;
 use std::process::exit;
 use tracing::{error, trace};
-use tracing_subscriber::{filter::LevelFilter, prelude::__tracing_subscriber_SubscriberExt, EnvFilter, Layer};
 use types::RegistryList;
 
 mod adapter;
@@ -33,7 +33,7 @@ fn main() {
     #[cfg(debug_assertions)]
     check_debug();
 
-    enable_tracing();
+    enable_tracing(Some(&TraceLevel::Trace), &TraceFormat::Json);
 
     let args = Arguments::parse();
     match args.subcommand {
@@ -243,24 +243,6 @@ fn import_input(input: &str, list: bool) -> RegistryList {
     }
 }
 
-pub fn enable_tracing() {
-    // default filter to trace level
-    let filter = EnvFilter::builder().with_default_directive(LevelFilter::TRACE.into()).parse("").unwrap_or_default();
-    let layer = tracing_subscriber::fmt::Layer::default().with_writer(std::io::stderr);
-    let fmt = layer
-                .with_ansi(false)
-                .with_level(true)
-                .with_line_number(true)
-                .json()
-                .boxed();
-
-    let subscriber = tracing_subscriber::Registry::default().with(fmt).with(filter);
-
-    if tracing::subscriber::set_global_default(subscriber).is_err() {
-        eprintln!("{}", t!("main.tracingInitError"));
-    }
-}
-
 #[cfg(debug_assertions)]
 fn check_debug() {
     if env::var("DEBUG_REGISTRY").is_ok() {
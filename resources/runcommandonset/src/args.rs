@@ -1,23 +1,8 @@
 // Copyright (c) Microsoft Corporation.
 // Licensed under the MIT License.
 
-use clap::{Parser, Subcommand, ValueEnum};
-
-#[derive(Debug, Clone, PartialEq, Eq, ValueEnum)]
-pub enum TraceFormat {
-    Default,
-    Plaintext,
-    Json,
-}
-
-#[derive(Debug, Clone, PartialEq, Eq, ValueEnum)]
-pub enum TraceLevel {
-    Error,
-    Warn,
-    Info,
-    Debug,
-    Trace
-}
+use clap::{Parser, Subcommand};
+pub use dsc_resource_sdk::trace::{TraceFormat, TraceLevel};
 
 #[derive(Parser)]
 #[clap(name = "runcommandonset", version = "0.0.1", about = "Run a command on set", long_about = None)]
@@ -2,13 +2,14 @@
 // Licensed under the MIT License.
 
 use clap::Parser;
+use dsc_resource_sdk::trace::enable_tracing;
 use rust_i18n::{i18n, t};
 use std::{io::{self, Read, IsTerminal}, process::exit};
 use tracing::{error, warn, debug, trace};
 
-use args::{Arguments, SubCommand, TraceLevel};
+use args::{Arguments, SubCommand};
 use runcommand::RunCommand;
-use utils::{enable_tracing, invoke_command, parse_input, EXIT_INVALID_ARGS};
+use utils::{invoke_command, parse_input, EXIT_INVALID_ARGS};
 
 pub mod args;
 pub mod runcommand;
@@ -18,29 +19,7 @@ i18n!("locales", fallback = "en-us");
 
 fn main() {
     let args = Arguments::parse();
-    let trace_level = match args.trace_level {
-        Some(trace_level) => trace_level,
-        None => {
-            // get from DSC_TRACE_LEVEL env var
-            if let Ok(trace_level) = std::env::var("DSC_TRACE_LEVEL") {
-                match trace_level.to_lowercase().as_str() {
-                    "error" => TraceLevel::Error,
-                    "warn" => TraceLevel::Warn,
-                    "info" => TraceLevel::Info,
-                    "debug" => TraceLevel::Debug,
-                    "trace" => TraceLevel::Trace,
-                    _ => {
-                        warn!("{}: {trace_level}", t!("main.invalidTraceLevel"));
-                        TraceLevel::Info
-                    }
-                }
-            } else {
-                // default to info
-                TraceLevel::Info
-            }
-        }
-    };
-    enable_tracing(&trace_level, &args.trace_format);
+    enable_tracing(args.trace_level.as_ref(), &args.trace_format);
     warn!("{}", t!("main.notIdempotent"));
 
     let stdin = if std::io::stdin().is_terminal() {
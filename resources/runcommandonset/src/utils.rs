@@ -3,10 +3,8 @@
 
 use rust_i18n::t;
 use std::{io::Read, process::{Command, exit, Stdio}};
-use tracing::{Level, error, debug, trace};
-use tracing_subscriber::{filter::EnvFilter, layer::SubscriberExt, Layer};
+use tracing::{error, debug, trace};
 
-use crate::args::{TraceFormat, TraceLevel};
 use crate::runcommand;
 
 pub const EXIT_INVALID_ARGS: i32 = 1;
@@ -52,63 +50,6 @@ pub fn parse_input(arguments: Option<Vec<String>>, executable: Option<String>, e
     command
 }
 
-/// Setup tracing subscriber based on the provided trace level and format.
-///
-/// # Arguments
-///
-/// * `trace_level` - The level of information of to output
-/// * `trace_format` - The format of the output
-///
-/// # Errors
-///
-/// If unable to initialize the tracing subscriber, an error message is printed and tracing is disabled.
-pub fn enable_tracing(trace_level: &TraceLevel, trace_format: &TraceFormat) {
-    // originally implemented in dsc/src/util.rs
-    let tracing_level = match trace_level {
-        TraceLevel::Error => Level::ERROR,
-        TraceLevel::Warn => Level::WARN,
-        TraceLevel::Info => Level::INFO,
-        TraceLevel::Debug => Level::DEBUG,
-        TraceLevel::Trace => Level::TRACE,
-    };
-
-    let filter = EnvFilter::try_from_default_env()
-        .or_else(|_| EnvFilter::try_new("warn"))
-        .unwrap_or_default()
-        .add_directive(tracing_level.into());
-    let layer = tracing_subscriber::fmt::Layer::default().with_writer(std::io::stderr);
-    let fmt = match trace_format {
-        TraceFormat::Default => {
-            layer
-                .with_ansi(true)
-                .with_level(true)
-                .with_line_number(true)
-                .boxed()
-        },
-        TraceFormat::Plaintext => {
-            layer
-                .with_ansi(false)
-                .with_level(true)
-                .with_line_number(false)
-                .boxed()
-        },
-        TraceFormat::Json => {
-            layer
-                .with_ansi(false)
-                .with_level(true)
-                .with_line_number(true)
-                .json()
-                .boxed()
-        }
-    };
-
-    let subscriber = tracing_subscriber::Registry::default().with(fmt).with(filter);
-
-    if tracing::subscriber::set_global_default(subscriber).is_err() {
-        eprintln!("{}", t!("utils.unableToTrace"));
-    }
-}
-
 /// Invoke a command and return the exit code, stdout, and stderr.
 ///
 /// # Arguments
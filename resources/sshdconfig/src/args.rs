@@ -6,21 +6,7 @@ use rust_i18n::t;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, PartialEq, Eq, ValueEnum)]
-pub enum TraceFormat {
-    Default,
-    Plaintext,
-    Json,
-}
-
-#[derive(Debug, Clone, PartialEq, Eq, ValueEnum)]
-pub enum TraceLevel {
-    Error,
-    Warn,
-    Info,
-    Debug,
-    Trace,
-}
+pub use dsc_resource_sdk::trace::{TraceFormat, TraceLevel};
 
 #[derive(Parser)]
 pub struct Args {
@@ -6,6 +6,7 @@ use rust_i18n::{i18n, t};
 use schemars::schema_for;
 use serde_json::Map;
 use std::process::exit;
+use dsc_resource_sdk::trace::enable_tracing;
 use tracing::{debug, error};
 
 use args::{Args, Command, DefaultShell, Setting};
@@ -14,7 +15,7 @@ use get::{get_sshd_settings, invoke_get};
 use parser::SshdConfigParser;
 use repeat_keyword::{RepeatInput, RepeatListInput};
 use set::invoke_set;
-use util::{build_command_info, enable_tracing};
+use util::build_command_info;
 
 mod args;
 mod canonical_properties;
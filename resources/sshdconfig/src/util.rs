@@ -4,93 +4,14 @@
 use rust_i18n::t;
 use serde_json::{Map, Value};
 use std::{path::PathBuf, process::Command};
-use tracing::{debug, warn, Level};
-use tracing_subscriber::{EnvFilter, Layer, prelude::__tracing_subscriber_SubscriberExt};
+use tracing::{debug, warn};
 
-use crate::args::{TraceFormat, TraceLevel};
 use crate::canonical_properties::{CanonicalProperty, CanonicalProperties};
 use crate::error::SshdConfigError;
 use crate::inputs::{CommandInfo, SshdCommandArgs, SSHD_CONFIG_FILEPATH};
 use crate::metadata::{SSHD_CONFIG_DEFAULT_PATH_UNIX, SSHD_CONFIG_DEFAULT_PATH_WINDOWS};
 use crate::parser::parse_text_to_map;
 
-/// Enable tracing.
-///
-/// # Arguments
-///
-/// * `trace_level` - The level of information to output
-/// * `trace_format` - The format of the output
-///
-/// # Errors
-///
-/// This function will return an error if it fails to initialize tracing.
-pub fn enable_tracing(trace_level: Option<&TraceLevel>, trace_format: &TraceFormat) {
-    let trace_level = match trace_level {
-        Some(trace_level) => trace_level,
-        None => {
-            if let Ok(trace_level) = std::env::var("DSC_TRACE_LEVEL") {
-                &match trace_level.to_lowercase().as_str() {
-                    "error" => TraceLevel::Error,
-                    "warn" => TraceLevel::Warn,
-                    "info" => TraceLevel::Info,
-                    "debug" => TraceLevel::Debug,
-                    "trace" => TraceLevel::Trace,
-                    _ => {
-                        eprintln!("{}: {trace_level}", t!("main.invalidTraceLevel"));
-                        TraceLevel::Info
-                    }
-                }
-            } else {
-                &TraceLevel::Info
-            }
-        }
-    };
-
-    let tracing_level = match trace_level {
-        TraceLevel::Error => Level::ERROR,
-        TraceLevel::Warn => Level::WARN,
-        TraceLevel::Info => Level::INFO,
-        TraceLevel::Debug => Level::DEBUG,
-        TraceLevel::Trace => Level::TRACE,
-    };
-
-    let filter = EnvFilter::try_from_default_env()
-        .or_else(|_| EnvFilter::try_new("warn"))
-        .unwrap_or_default()
-        .add_directive(tracing_level.into());
-    let layer = tracing_subscriber::fmt::Layer::default().with_writer(std::io::stderr);
-    let fmt = match trace_format {
-        TraceFormat::Default => {
-            layer
-                .with_ansi(true)
-                .with_level(true)
-                .with_line_number(true)
-                .boxed()
-        },
-        TraceFormat::Plaintext => {
-            layer
-                .with_ansi(false)
-                .with_level(true)
-                .with_line_number(false)
-                .boxed()
-        },
-        TraceFormat::Json => {
-            layer
-                .with_ansi(false)
-                .with_level(true)
-                .with_line_number(true)
-                .json()
-                .boxed()
-        }
-    };
-
-    let subscriber = tracing_subscriber::Registry::default().with(fmt).with(filter);
-
-    if tracing::subscriber::set_global_default(subscriber).is_err() {
-        eprintln!("{}", t!("util.tracingInitError"));
-    }
-}
-
 /// Get the `sshd_config` path
 /// Uses the input value, if provided.
 /// If input value not provided, get default path for the OS.
@@ -6,8 +6,11 @@ use clap::{Parser, Subcommand, ValueEnum};
 #[derive(Debug, Clone, PartialEq, Eq, ValueEnum)]
 pub enum Schemas {
     Adapter,
+    Batch,
+    Compare,
     CopyResource,
     Delete,
+    Environment,
     Exist,
     ExitCode,
     Export,
@@ -16,9 +19,13 @@ pub enum Schemas {
     Exporter,
     Get,
     InDesiredState,
+    InputFile,
     Metadata,
+    Normalize,
     Operation,
+    Progress,
     RefreshEnv,
+    ResourceError,
     RestartRequired,
     SchemaDefault,
     Set,
@@ -71,6 +78,20 @@ pub enum SubCommand {
         operation: AdapterOperation,
     },
 
+    #[clap(name = "batch", about = "Return the state of every instance in a batch, one per line")]
+    Batch {
+        #[clap(name = "input", short, long, help = "The instances as a JSON array")]
+        input: String,
+        #[clap(name = "state-and-diff", long, help = "Write the changed properties after the state of each instance")]
+        state_and_diff: bool,
+    },
+
+    #[clap(name = "compare", about = "Test resource for schema comparison keywords in synthetic test")]
+    Compare {
+        #[clap(name = "input", short, long, help = "The input to the compare command as JSON")]
+        input: String,
+    },
+
     #[clap(name = "copy-resource", about = "Copy a resource")]
     CopyResource {
         #[clap(name = "input", short, long, help = "The input to the copy resource command as JSON")]
@@ -83,6 +104,12 @@ pub enum SubCommand {
         input: String,
     },
 
+    #[clap(name = "environment", about = "Return the working directory and environment variables")]
+    Environment {
+        #[clap(name = "input", short, long, help = "The input to the environment command as JSON")]
+        input: String,
+    },
+
     #[clap(name = "exist", about = "Check if a resource exists")]
     Exist {
         #[clap(name = "input", short, long, help = "The input to the exist command as JSON")]
@@ -136,6 +163,18 @@ pub enum SubCommand {
     #[clap(name = "no-op", about = "Perform no operation, just return success")]
     NoOp,
 
+    #[clap(name = "normalize", about = "Return the input, which must use the canonical enum casing and include defaults")]
+    Normalize {
+        #[clap(name = "input", short, long, help = "The input to the normalize command as JSON")]
+        input: String,
+    },
+
+    #[clap(name = "input-file", about = "Read the input from a file")]
+    InputFile {
+        #[clap(name = "path", short, long, help = "The path to the file containing the input as JSON")]
+        path: String,
+    },
+
     #[clap(name = "operation", about = "Perform an operation")]
     Operation {
         #[clap(name = "operation", short, long, help = "The name of the operation to perform")]
@@ -144,6 +183,12 @@ pub enum SubCommand {
         input: String,
     },
 
+    #[clap(name = "progress", about = "Report progress on stderr")]
+    Progress {
+        #[clap(name = "input", short, long, help = "The input to the progress command as JSON")]
+        input: String,
+    },
+
     #[clap(name = "refresh-env", about = "Refresh an environment variable in the registry")]
     RefreshEnv {
         #[clap(name = "operation", short, long, help = "The operation to perform: get or set")]
@@ -152,6 +197,12 @@ pub enum SubCommand {
         input: String,
     },
 
+    #[clap(name = "resource-error", about = "Write a structured error to stderr and exit with an error")]
+    ResourceError {
+        #[clap(name = "input", short, long, help = "The input to the resource error command as JSON")]
+        input: String,
+    },
+
     #[clap(name = "restart-required", about = "Check if a restart is required based on the input")]
     RestartRequired {
         #[clap(name = "input", short, long, help = "The input to the restart required command as JSON")]
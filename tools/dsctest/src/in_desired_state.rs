@@ -14,4 +14,14 @@ pub struct InDesiredState {
     pub value_one: i32,
     #[serde(rename = "valueTwo")]
     pub value_two: i32,
+    #[serde(rename = "_reasons", skip_serializing_if = "Option::is_none")]
+    pub reasons: Option<Vec<Reason>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Reason {
+    pub code: String,
+    pub phrase: String,
+    pub property: String,
 }
@@ -0,0 +1,14 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT License.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct InputFile {
+    pub value: String,
+    /// The path of the file the input was read from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+}
@@ -2,8 +2,11 @@
 // Licensed under the MIT License.
 
 mod args;
+mod batch;
+mod compare;
 mod copy_resource;
 mod delete;
+mod environment;
 mod exist;
 mod exit_code;
 mod export;
@@ -11,10 +14,14 @@ mod export_schema;
 mod exporter;
 mod get;
 mod in_desired_state;
+mod input_file;
 mod metadata;
+mod normalize;
 mod operation;
 mod adapter;
+mod progress;
 mod refresh_env;
+mod resource_error;
 mod restart_required;
 mod schema_default;
 mod set;
@@ -29,8 +36,11 @@ use args::{Args, RefreshEnvOperation, Schemas, SubCommand};
 use clap::Parser;
 use schemars::schema_for;
 use serde_json::Map;
+use crate::batch::Batch;
+use crate::compare::Compare;
 use crate::copy_resource::{CopyResource, copy_the_resource};
 use crate::delete::Delete;
+use crate::environment::Environment;
 use crate::exist::{Exist, State};
 use crate::exit_code::ExitCode;
 use crate::export::Export;
@@ -38,9 +48,13 @@ use crate::export_schema::{ExportSchema, invoke_export_schema};
 use crate::exporter::{Exporter, Resource};
 use crate::get::Get;
 use crate::in_desired_state::InDesiredState;
+use crate::input_file::InputFile;
 use crate::metadata::Metadata;
+use crate::normalize::Normalize;
 use crate::operation::Operation;
+use crate::progress::Progress;
 use crate::refresh_env::RefreshEnv;
+use crate::resource_error::ResourceError;
 use crate::restart_required::RestartRequired;
 use crate::schema_default::SchemaDefault;
 use crate::set::{Set, invoke_set};
@@ -65,6 +79,36 @@ fn main() {
                 }
             }
         },
+        SubCommand::Batch { input, state_and_diff } => {
+            let instances = match serde_json::from_str::<Vec<Batch>>(&input) {
+                Ok(instances) => instances,
+                Err(err) => {
+                    eprintln!("Error JSON does not match schema: {err}");
+                    std::process::exit(1);
+                }
+            };
+            instances.iter().map(|instance| {
+                let state = serde_json::to_string(instance).unwrap();
+                if state_and_diff { format!("{state}\n[]") } else { state }
+            }).collect::<Vec<String>>().join("\n")
+        },
+        SubCommand::Compare { input } => {
+            let compare = match serde_json::from_str::<Compare>(&input) {
+                Ok(compare) => compare,
+                Err(err) => {
+                    eprintln!("Error JSON does not match schema: {err}");
+                    std::process::exit(1);
+                }
+            };
+            let actual = Compare {
+                name: compare.name,
+                tags: Some(vec!["b".to_string(), "a".to_string(), "a".to_string()]),
+                state: Some("Running".to_string()),
+                features: Some(vec!["x".to_string(), "y".to_string(), "z".to_string()]),
+                last_modified: Some("2026-01-01T00:00:00Z".to_string()),
+            };
+            serde_json::to_string(&actual).unwrap()
+        },
         SubCommand::CopyResource { input } => {
             let copy_resource = match serde_json::from_str::<CopyResource>(&input) {
                 Ok(copy_resource) => copy_resource,
@@ -90,6 +134,20 @@ fn main() {
             delete.delete_called = Some(true);
             serde_json::to_string(&delete).unwrap()
         },
+        SubCommand::Environment { input } => {
+            let mut environment = match serde_json::from_str::<Environment>(&input) {
+                Ok(environment) => environment,
+                Err(err) => {
+                    eprintln!("Error JSON does not match schema: {err}");
+                    std::process::exit(1);
+                }
+            };
+            environment.cwd = std::env::current_dir().ok().map(|cwd| cwd.to_string_lossy().to_string());
+            environment.variables = Some(environment.names.iter()
+                .filter_map(|name| std::env::var(name).ok().map(|value| (name.clone(), value)))
+                .collect());
+            serde_json::to_string(&environment).unwrap()
+        },
         SubCommand::Exist { input } => {
             let mut exist = match serde_json::from_str::<Exist>(&input) {
                 Ok(exist) => exist,
@@ -222,8 +280,33 @@ fn main() {
             };
             in_desired_state.value_one = 1;
             in_desired_state.value_two = 2;
+            if in_desired_state.in_desired_state == Some(false) {
+                in_desired_state.reasons = Some(vec![in_desired_state::Reason {
+                    code: "Test:InDesiredState:NotCompliant".to_string(),
+                    phrase: "The resource is not in the desired state".to_string(),
+                    property: "/_inDesiredState".to_string(),
+                }]);
+            }
             serde_json::to_string(&in_desired_state).unwrap()
         },
+        SubCommand::InputFile { path } => {
+            let input = match std::fs::read_to_string(&path) {
+                Ok(input) => input,
+                Err(err) => {
+                    eprintln!("Error reading input file: {err}");
+                    std::process::exit(1);
+                }
+            };
+            let mut input_file = match serde_json::from_str::<InputFile>(&input) {
+                Ok(input_file) => input_file,
+                Err(err) => {
+                    eprintln!("Error JSON does not match schema: {err}");
+                    std::process::exit(1);
+                }
+            };
+            input_file.path = Some(path);
+            serde_json::to_string(&input_file).unwrap()
+        },
         SubCommand::Metadata { input, export } => {
             let count = if export {
                 3
@@ -248,6 +331,16 @@ fn main() {
             // do nothing and just return success
             String::new()
         },
+        SubCommand::Normalize { input } => {
+            let normalize = match serde_json::from_str::<Normalize>(&input) {
+                Ok(normalize) => normalize,
+                Err(err) => {
+                    eprintln!("Error JSON does not match schema: {err}");
+                    std::process::exit(1);
+                }
+            };
+            serde_json::to_string(&normalize).unwrap()
+        },
         SubCommand::Operation { operation, input } => {
             let mut operation_result = match serde_json::from_str::<Operation>(&input) {
                 Ok(op) => op,
@@ -259,6 +352,20 @@ fn main() {
             operation_result.operation = Some(operation.to_lowercase());
             serde_json::to_string(&operation_result).unwrap()
         },
+        SubCommand::Progress { input } => {
+            let progress = match serde_json::from_str::<Progress>(&input) {
+                Ok(progress) => progress,
+                Err(err) => {
+                    eprintln!("Error JSON does not match schema: {err}");
+                    std::process::exit(1);
+                }
+            };
+            for step in 1..=progress.steps {
+                let percent = u32::from(step) * 100 / u32::from(progress.steps);
+                eprintln!("{}", serde_json::json!({"progress": {"percent": percent, "activity": format!("Step {step}")}}));
+            }
+            serde_json::to_string(&progress).unwrap()
+        },
         SubCommand::RefreshEnv { operation, input } => {
             let mut refresh_env = match serde_json::from_str::<refresh_env::RefreshEnv>(&input) {
                 Ok(re) => re,
@@ -280,6 +387,17 @@ fn main() {
                 }
             }
         },
+        SubCommand::ResourceError { input } => {
+            let resource_error = match serde_json::from_str::<ResourceError>(&input) {
+                Ok(resource_error) => resource_error,
+                Err(err) => {
+                    eprintln!("Error JSON does not match schema: {err}");
+                    std::process::exit(1);
+                }
+            };
+            eprintln!("{}", serde_json::json!({"errorData": {"error": resource_error}}));
+            std::process::exit(2);
+        },
         SubCommand::RestartRequired { input } => {
             let restart_required = match serde_json::from_str::<RestartRequired>(&input) {
                 Ok(rr) => rr,
@@ -312,12 +430,21 @@ fn main() {
                 Schemas::Adapter => {
                     schema_for!(adapter::DscResource)
                 },
+                Schemas::Batch => {
+                    schema_for!(Batch)
+                },
+                Schemas::Compare => {
+                    schema_for!(Compare)
+                },
                 Schemas::CopyResource => {
                     schema_for!(CopyResource)
                 },
                 Schemas::Delete => {
                     schema_for!(Delete)
                 },
+                Schemas::Environment => {
+                    schema_for!(Environment)
+                },
                 Schemas::Exist => {
                     schema_for!(Exist)
                 },
@@ -342,15 +469,27 @@ fn main() {
                 Schemas::InDesiredState => {
                     schema_for!(InDesiredState)
                 },
+                Schemas::InputFile => {
+                    schema_for!(InputFile)
+                },
                 Schemas::Metadata => {
                     schema_for!(Metadata)
                 },
+                Schemas::Normalize => {
+                    schema_for!(Normalize)
+                },
                 Schemas::Operation => {
                     schema_for!(Operation)
                 },
+                Schemas::Progress => {
+                    schema_for!(Progress)
+                },
                 Schemas::RefreshEnv => {
                     schema_for!(RefreshEnv)
                 },
+                Schemas::ResourceError => {
+                    schema_for!(ResourceError)
+                },
                 Schemas::RestartRequired => {
                     schema_for!(RestartRequired)
                 },
@@ -5,6 +5,7 @@ mod args;
 
 use args::{Args, SubCommand};
 use clap::Parser;
+use dsc_lib::discovery::manifest_signature::ManifestTrust;
 use dsc_lib::dscresources::dscresource::{Capability, DscResource, ImplementedAs};
 use dsc_lib::dscresources::resource_manifest::{GetMethod, Kind, ResourceManifest};
 use dsc_lib::schemas::dsc_repo::DscRepoSchema;
@@ -28,6 +29,7 @@ fn main() {
                 properties: Some(vec!["Property1".to_string(), "Property2".to_string()]),
                 require_adapter: Some("Test/TestGroup".parse().unwrap()),
                 adapted_content: None,
+                trust: ManifestTrust::NotVerified,
                 target_resource: None,
                 schema: None,
                 manifest: Some(ResourceManifest {
@@ -57,6 +59,7 @@ fn main() {
                 properties: Some(vec!["Property1".to_string(), "Property2".to_string()]),
                 require_adapter: Some("Test/TestGroup".parse().unwrap()),
                 adapted_content: None,
+                trust: ManifestTrust::NotVerified,
                 target_resource: None,
                 schema: None,
                 manifest: Some(ResourceManifest {
@@ -90,6 +93,7 @@ fn main() {
                 properties: Some(vec!["Property1".to_string(), "Property2".to_string()]),
                 require_adapter: None,
                 adapted_content: None,
+                trust: ManifestTrust::NotVerified,
                 target_resource: None,
                 manifest: None,
                 schema: None,
@@ -80,6 +80,8 @@ pub(crate) fn export_schemas(
         dsc_lib::configure::config_doc::UserFunctionDefinition,
         dsc_lib::configure::config_doc::UserFunctionOutput,
         dsc_lib::configure::config_doc::UserFunctionParameter,
+        dsc_lib::configure::function_library::FunctionImport,
+        dsc_lib::configure::function_library::FunctionLibrary,
         dsc_lib::configure::config_result::ConfigurationExportResult,
         dsc_lib::configure::config_result::ConfigurationGetResult,
         dsc_lib::configure::config_result::ConfigurationSetResult,